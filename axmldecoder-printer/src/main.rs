@@ -10,7 +10,7 @@ fn main() -> Result<()> {
     let mut f = File::open(fname)?;
     let xml = axmldecoder::parse(&mut f)?;
 
-    let root = xml.get_root().as_ref().unwrap();
+    let root = xml.get_root().unwrap();
     let mut s = String::new();
     s.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
     format_xml(&root, 0_usize, &mut s);
@@ -74,7 +74,7 @@ fn format_start_element(e: &Element) -> String {
         s.push_str("xmlns:android=\"http://schemas.android.com/apk/res/android\"");
     }
 
-    for (key, val) in e.get_attributes().iter() {
+    for (key, val) in e.attributes() {
         s.push(' ');
         s.push_str(key);
         s.push('=');
@@ -0,0 +1,139 @@
+use crate::intent::IntentFilter;
+use crate::manifest::{child_elements, ComponentKind, Manifest};
+use crate::xml::Element;
+
+///An `activity-alias` element, which starts another activity of the app
+///under its own name, intent filters and enabled state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ActivityAlias {
+    ///The fully qualified name of the alias.
+    pub name: String,
+    ///The fully qualified name of the activity in `android:targetActivity`.
+    pub target_activity: String,
+    ///The `android:enabled` attribute, if given.
+    pub enabled: Option<bool>,
+    ///The `android:exported` attribute, if given.
+    pub exported: Option<bool>,
+    ///The `intent-filter` children.
+    pub intent_filters: Vec<IntentFilter>,
+}
+
+///An alias together with the activity it starts, as returned by
+///[Manifest::resolve_alias].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ResolvedAlias {
+    ///The fully qualified name of the alias.
+    pub alias: String,
+    ///The fully qualified name of the target activity.
+    pub activity: String,
+    ///Whether neither the alias nor the activity is disabled.
+    pub enabled: bool,
+    ///The intent filters of the alias followed by those of the activity.
+    pub intent_filters: Vec<IntentFilter>,
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the `activity-alias` elements of the application.
+    pub fn activity_aliases(&self) -> Vec<ActivityAlias> {
+        self.application()
+            .into_iter()
+            .flat_map(child_elements)
+            .filter(|e| e.get_tag() == "activity-alias")
+            .map(|e| ActivityAlias {
+                name: self.resolve_class_name(e.android_value("name").unwrap_or_default()),
+                target_activity: self
+                    .resolve_class_name(e.android_value("targetActivity").unwrap_or_default()),
+                enabled: e.android_bool("enabled"),
+                exported: e.android_bool("exported"),
+                intent_filters: intent_filters(e),
+            })
+            .collect()
+    }
+
+    ///Finds the activity `alias` starts. Returns `None` if the manifest
+    ///declares no activity by that name, which Android rejects.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut activity = Element::new("activity");
+    ///activity.set_attribute("android:name", ".RealActivity");
+    ///let mut alias = Element::new("activity-alias");
+    ///alias.set_attribute("android:name", ".Launcher");
+    ///alias.set_attribute("android:targetActivity", ".RealActivity");
+    ///let mut application = Element::new("application");
+    ///application.get_children_mut().push(Node::Element(activity));
+    ///application.get_children_mut().push(Node::Element(alias));
+    ///let mut root = Element::new("manifest");
+    ///root.set_attribute("package", "com.example");
+    ///root.get_children_mut().push(Node::Element(application));
+    ///
+    ///let xml = XmlDocument::new(Node::Element(root));
+    ///let manifest = xml.manifest().unwrap();
+    ///let resolved = manifest.resolve_alias(&manifest.activity_aliases()[0]).unwrap();
+    ///assert_eq!(resolved.activity, "com.example.RealActivity");
+    ///```
+    pub fn resolve_alias(&self, alias: &ActivityAlias) -> Option<ResolvedAlias> {
+        let (activity, kind) = self.find_component(&alias.target_activity)?;
+        if kind != ComponentKind::Activity {
+            return None;
+        }
+        let mut intent_filters = alias.intent_filters.clone();
+        intent_filters.extend(self::intent_filters(activity));
+        Some(ResolvedAlias {
+            alias: alias.name.clone(),
+            activity: alias.target_activity.clone(),
+            enabled: alias.enabled != Some(false)
+                && activity.android_bool("enabled") != Some(false),
+            intent_filters,
+        })
+    }
+}
+
+///Reads the `intent-filter` children of a component.
+pub(crate) fn intent_filters(component: &Element) -> Vec<IntentFilter> {
+    child_elements(component)
+        .filter(|e| e.get_tag() == "intent-filter")
+        .map(IntentFilter::from_element)
+        .collect()
+}
+
+#[test]
+fn test_resolve_alias() {
+    use crate::{Node, XmlDocument};
+
+    let mut filter = Element::new("intent-filter");
+    let mut action = Element::new("action");
+    action.set_attribute("android:name", "android.intent.action.VIEW");
+    filter.get_children_mut().push(Node::Element(action));
+    let mut activity = Element::new("activity");
+    activity.set_attribute("android:name", "com.example.Real");
+    activity.set_attribute("android:enabled", "false");
+    activity.get_children_mut().push(Node::Element(filter));
+
+    let mut application = Element::new("application");
+    application.get_children_mut().push(Node::Element(activity));
+    for (name, target) in [(".Alias", ".Real"), (".Dangling", ".Missing")] {
+        let mut alias = Element::new("activity-alias");
+        alias.set_attribute("android:name", name);
+        alias.set_attribute("android:targetActivity", target);
+        application.get_children_mut().push(Node::Element(alias));
+    }
+    let mut root = Element::new("manifest");
+    root.set_attribute("package", "com.example");
+    root.get_children_mut().push(Node::Element(application));
+    let xml = XmlDocument::new(Node::Element(root));
+    let manifest = xml.manifest().unwrap();
+
+    let aliases = manifest.activity_aliases();
+    assert_eq!(aliases.len(), 2);
+    assert_eq!(aliases[0].name, "com.example.Alias");
+    assert_eq!(aliases[0].target_activity, "com.example.Real");
+    assert_eq!(aliases[0].enabled, None);
+
+    let resolved = manifest.resolve_alias(&aliases[0]).unwrap();
+    assert!(!resolved.enabled);
+    assert!(resolved.intent_filters[0].has_action("android.intent.action.VIEW"));
+    assert_eq!(manifest.resolve_alias(&aliases[1]), None);
+}
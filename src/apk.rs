@@ -0,0 +1,68 @@
+use std::fs::File;
+#[cfg(feature = "proto")]
+use std::io::Read;
+use std::path::Path;
+
+use crate::{parse, ParseError, XmlDocument};
+
+const MANIFEST_NAME: &str = "AndroidManifest.xml";
+
+///Extracts and parses the `AndroidManifest.xml` of an APK. Both stored and
+///deflated entries are supported.
+///
+///```rust,no_run
+///use axmldecoder::parse_apk;
+///# use axmldecoder::ParseError;
+///let xml = parse_apk("app.apk")?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_apk<P: AsRef<Path>>(path: P) -> Result<XmlDocument, ParseError> {
+    let file = File::open(path).map_err(ParseError::IoError)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(ParseError::ZipError)?;
+    let mut entry = archive
+        .by_name(MANIFEST_NAME)
+        .map_err(ParseError::ZipError)?;
+    parse(&mut entry)
+}
+
+///Reads the entry `name` of the zip archive at `path` into memory.
+#[cfg(feature = "proto")]
+pub(crate) fn read_entry<P: AsRef<Path>>(path: P, name: &str) -> Result<Vec<u8>, ParseError> {
+    let file = File::open(path).map_err(ParseError::IoError)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(ParseError::ZipError)?;
+    let mut entry = archive.by_name(name).map_err(ParseError::ZipError)?;
+
+    // Protocol buffers are decoded from memory. Manifests are small enough
+    // for this not to matter.
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data).map_err(ParseError::IoError)?;
+    Ok(data)
+}
+
+#[test]
+fn test_parse_apk() {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::CompressionMethod;
+
+    let manifest = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    for (method, name) in [
+        (CompressionMethod::Stored, "stored"),
+        (CompressionMethod::Deflated, "deflated"),
+    ] {
+        let path = std::env::temp_dir().join(format!("axmldecoder-test-{}.apk", name));
+        let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(method);
+        writer.start_file("classes.dex", options).unwrap();
+        writer.write_all(b"dex\n035\0").unwrap();
+        writer.start_file(MANIFEST_NAME, options).unwrap();
+        writer.write_all(&manifest).unwrap();
+        writer.finish().unwrap();
+
+        let xml = parse_apk(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(
+            matches!(xml.get_root(), Some(crate::Node::Element(e)) if e.get_tag() == "manifest")
+        );
+    }
+}
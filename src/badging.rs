@@ -0,0 +1,152 @@
+use std::fmt::Write;
+
+use crate::aliases::intent_filters;
+use crate::launcher::{has_launcher_filter, CATEGORY_LAUNCHER};
+use crate::manifest::{child_elements, Manifest};
+use crate::xml::Element;
+
+impl<'a> Manifest<'a> {
+    ///Summarizes the manifest in the format of `aapt dump badging`, so that
+    ///scripts parsing that output can be fed without running `aapt`.
+    ///
+    ///Only the lines that can be derived from the manifest alone are
+    ///written. Labels and icons are shown as stored rather than resolved
+    ///through the resource table, and lines describing the rest of the APK,
+    ///such as `locales` or `native-code`, are left out.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let badging = xml.manifest().unwrap().badging();
+    ///assert!(badging.starts_with("package: name='org.t0t0.androguard.TC' versionCode='1'"));
+    ///```
+    pub fn badging(&self) -> String {
+        let mut output = String::new();
+        let root = self.root();
+        let attribute = |e: &'a Element, name: &str| {
+            e.android_attribute(name)
+                .map_or("", |attr| attr.value.as_str())
+        };
+
+        let _ = write!(
+            output,
+            "package: name='{}' versionCode='{}' versionName='{}'",
+            self.package().unwrap_or_default(),
+            attribute(root, "versionCode"),
+            attribute(root, "versionName"),
+        );
+        for name in ["platformBuildVersionName", "platformBuildVersionCode"] {
+            if let Some(value) = root.get_attribute(name) {
+                let _ = write!(output, " {}='{}'", name, value);
+            }
+        }
+        for name in ["compileSdkVersion", "compileSdkVersionCodename"] {
+            if let Some(attr) = root.android_attribute(name) {
+                let _ = write!(output, " {}='{}'", name, attr.value);
+            }
+        }
+        output.push('\n');
+
+        for e in child_elements(root) {
+            match e.get_tag() {
+                "uses-sdk" => {
+                    for (name, line) in [
+                        ("minSdkVersion", "sdkVersion"),
+                        ("targetSdkVersion", "targetSdkVersion"),
+                        ("maxSdkVersion", "maxSdkVersion"),
+                    ] {
+                        if let Some(attr) = e.android_attribute(name) {
+                            let _ = writeln!(output, "{}:'{}'", line, attr.value);
+                        }
+                    }
+                }
+                tag @ ("uses-permission" | "uses-permission-sdk-23") => {
+                    let _ = write!(output, "{}: name='{}'", tag, attribute(e, "name"));
+                    if let Some(attr) = e.android_attribute("maxSdkVersion") {
+                        let _ = write!(output, " maxSdkVersion='{}'", attr.value);
+                    }
+                    output.push('\n');
+                }
+                "uses-feature" => {
+                    let required = e
+                        .android_attribute("required")
+                        .map_or(true, |attr| attr.value != "false");
+                    let line = if required {
+                        "uses-feature"
+                    } else {
+                        "uses-feature-not-required"
+                    };
+                    match e.android_attribute("glEsVersion") {
+                        Some(attr) if e.android_attribute("name").is_none() => {
+                            let _ = writeln!(output, "{}: glEsVersion='{}'", line, attr.value);
+                        }
+                        _ => {
+                            let _ = writeln!(output, "{}: name='{}'", line, attribute(e, "name"));
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(application) = self.application() {
+            let _ = writeln!(
+                output,
+                "application: label='{}' icon='{}'",
+                attribute(application, "label"),
+                attribute(application, "icon"),
+            );
+            if application
+                .android_attribute("debuggable")
+                .is_some_and(|attr| attr.value == "true")
+            {
+                output.push_str("application-debuggable\n");
+            }
+
+            for e in child_elements(application) {
+                match e.get_tag() {
+                    "uses-library" => {
+                        let required = e
+                            .android_attribute("required")
+                            .map_or(true, |attr| attr.value != "false");
+                        let line = if required {
+                            "uses-library"
+                        } else {
+                            "uses-library-not-required"
+                        };
+                        let _ = writeln!(output, "{}:'{}'", line, attribute(e, "name"));
+                    }
+                    "activity" | "activity-alias"
+                        if has_launcher_filter(&intent_filters(e), CATEGORY_LAUNCHER) =>
+                    {
+                        let _ = writeln!(
+                            output,
+                            "launchable-activity: name='{}'  label='{}' icon='{}'",
+                            self.resolve_class_name(attribute(e, "name")),
+                            attribute(e, "label"),
+                            attribute(e, "icon"),
+                        );
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        output
+    }
+}
+
+#[test]
+fn test_badging() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    assert_eq!(
+        xml.manifest().unwrap().badging(),
+        "package: name='org.t0t0.androguard.TC' versionCode='1' versionName='1.0'\n\
+         application: label='@0x7f040000' icon='@0x7f020000'\n\
+         application-debuggable\n\
+         launchable-activity: name='org.t0t0.androguard.TC.TCActivity'  label='@0x7f040000' icon=''\n"
+    );
+}
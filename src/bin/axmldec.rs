@@ -0,0 +1,203 @@
+//!`axmldec` inspects and edits Android binary XML files from the command
+//!line. It is built with the `cli` feature.
+
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::process;
+
+use axmldecoder::{ElementPath, FormatOptions, ParseConfig, XmlDocument};
+
+const USAGE: &str = "\
+usage: axmldec <command> [options] <input>
+
+commands:
+  print <input>                          print the decoded XML
+  json <input>                           print the document as JSON
+  badging <input>                        print a summary like aapt dump badging
+  get <input> <path>                     print the elements or attributes at a path
+  edit <input> --set <path>=<value>...   set attributes and write binary XML
+  encode <input>                         encode a textual XML file as binary XML
+
+options:
+  --hardened     parse obfuscated files the way Android does
+  -o <output>    write binary XML to a file instead of stdout
+
+Paths look like manifest/application/activity[2]/@android:name, where [n]
+picks the n-th sibling with that tag, counting from 1, and
+[@android:name='value'] the siblings with that attribute value.";
+
+struct Args {
+    command: String,
+    hardened: bool,
+    output: Option<String>,
+    sets: Vec<String>,
+    positional: Vec<String>,
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Args, String> {
+    let command = args.next().ok_or("missing command")?;
+    let mut parsed = Args {
+        command,
+        hardened: false,
+        output: None,
+        sets: Vec::new(),
+        positional: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hardened" => parsed.hardened = true,
+            "-o" => parsed.output = Some(args.next().ok_or("-o requires a file name")?),
+            "--set" => parsed
+                .sets
+                .push(args.next().ok_or("--set requires an assignment")?),
+            "-h" | "--help" => return Err(String::new()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+            _ => parsed.positional.push(arg),
+        }
+    }
+
+    Ok(parsed)
+}
+
+///Splits a trailing `/@name` attribute step off `path`, for commands that
+///read or write attributes. Steps ending in a quoted filter value are left
+///alone, as the value may contain `/@`.
+fn split_attribute(path: &str) -> Result<(ElementPath, Option<&str>), Box<dyn Error>> {
+    let (elements, attribute) = match path.rsplit_once('/') {
+        Some((elements, step))
+            if step.starts_with('@') && !step.contains(&['\'', '"', ']'][..]) =>
+        {
+            (elements, Some(&step[1..]))
+        }
+        _ => (path, None),
+    };
+    Ok((elements.parse()?, attribute))
+}
+
+///Splits `<path>=<value>` at the first `=` outside of the filters of the
+///path, which may compare attributes with `=` themselves.
+fn split_assignment(assignment: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in assignment.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth -= 1,
+            (None, '=') if depth == 0 => return Some((&assignment[..i], &assignment[i + 1..])),
+            _ => (),
+        }
+    }
+    None
+}
+
+fn read_document(path: &str, hardened: bool) -> Result<XmlDocument, Box<dyn Error>> {
+    let config = if hardened {
+        ParseConfig::hardened()
+    } else {
+        ParseConfig::strict()
+    };
+    let mut f = File::open(path)?;
+    Ok(axmldecoder::parse_with_config(&mut f, &config)?.0)
+}
+
+fn write_binary(document: &XmlDocument, output: &Option<String>) -> Result<(), Box<dyn Error>> {
+    let data = axmldecoder::encode_to_vec(document)?;
+
+    match output {
+        Some(path) => std::fs::write(path, &data)?,
+        None => std::io::stdout().write_all(&data)?,
+    }
+    Ok(())
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let input = args.positional.first().ok_or("missing input file")?;
+
+    match args.command.as_str() {
+        "print" => {
+            let document = read_document(input, args.hardened)?;
+            let mut stdout = std::io::BufWriter::new(std::io::stdout().lock());
+            document.to_writer(&mut stdout, &FormatOptions::default())?;
+            stdout.flush()?;
+        }
+        "json" => {
+            let document = read_document(input, args.hardened)?;
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+        "badging" => {
+            let document = read_document(input, args.hardened)?;
+            let manifest = document.manifest().ok_or("not an AndroidManifest.xml")?;
+            print!("{}", manifest.badging());
+        }
+        "get" => {
+            let document = read_document(input, args.hardened)?;
+            let (path, attribute) = split_attribute(args.positional.get(1).ok_or("missing path")?)?;
+
+            let mut found = false;
+            for element in document.select(&path) {
+                match attribute {
+                    Some(name) => {
+                        if let Some(value) = element.get_attribute(name) {
+                            println!("{}", value);
+                            found = true;
+                        }
+                    }
+                    None => {
+                        print!("{}", element.to_xml_string(&FormatOptions::default()));
+                        found = true;
+                    }
+                }
+            }
+            if !found {
+                return Err("nothing matches the path".into());
+            }
+        }
+        "edit" => {
+            let mut document = read_document(input, args.hardened)?;
+
+            for assignment in &args.sets {
+                let (path, value) = split_assignment(assignment)
+                    .ok_or_else(|| format!("expected <path>=<value> in {}", assignment))?;
+                let (path, name) = split_attribute(path)?;
+                let name =
+                    name.ok_or_else(|| format!("{} does not name an attribute", assignment))?;
+
+                if document.select_mut(&path, |e| e.set_attribute(name, value)) == 0 {
+                    return Err(format!("no element matches {}", assignment).into());
+                }
+            }
+
+            write_binary(&document, &args.output)?;
+        }
+        "encode" => {
+            let document = axmldecoder::parse_text(&std::fs::read_to_string(input)?)?;
+            write_binary(&document, &args.output)?;
+        }
+        command => return Err(format!("unknown command {}", command).into()),
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("axmldec: {}", e);
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = run(args) {
+        eprintln!("axmldec: {}", e);
+        process::exit(1);
+    }
+}
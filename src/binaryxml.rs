@@ -1,110 +1,573 @@
-use std::convert::{TryFrom, TryInto};
-use std::io::{Read, Seek, Write, SeekFrom};
+use std::convert::TryFrom;
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
 
+use crate::chunks::RawChunk;
+use crate::config::ParseConfig;
+use crate::diagnostics::{push_warning, Warning};
+use crate::events::XmlEvent;
+use crate::metadata::{ChunkInfo, DocumentMetadata, ResourceMapEntry};
 use crate::resource_value::ResourceValue;
-use crate::stringpool::StringPool;
-use crate::{ParseError, read_u16, read_u32, write_u16, write_u32};
-use byteorder::WriteBytesExt;
-use num_enum::{TryFromPrimitive, IntoPrimitive};
+use crate::stats::ParseStats;
+use crate::stringpool::ResStringPool;
+use crate::{read_u16, read_u32, write_u16, write_u32, ParseError};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 
+///A binary XML document as its chunks: the string pool, the resource map
+///and the node chunks, available as [XmlEvent](crate::XmlEvent)s.
+///
+///Unlike [XmlDocument](crate::XmlDocument), nothing is resolved, so a
+///document can be read, changed and written again without losing anything
+///the tree does not represent.
+///
+///```rust
+///use axmldecoder::BinaryXmlDocument;
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let data = std::fs::read(manifest_file).unwrap();
+///let mut document = BinaryXmlDocument::read_from(&mut std::io::Cursor::new(&data))?;
+///println!("{} strings, {} chunks", document.string_pool().len(), document.events().count());
+///
+///let mut output = std::io::Cursor::new(Vec::new());
+///document.write_to(&mut output).unwrap();
+///assert_eq!(output.into_inner(), data);
+///# Ok::<(), ParseError>(())
+///```
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct BinaryXmlDocument {
-    pub elements: Vec<XmlElement>,
-    pub string_pool: StringPool,
-    pub resource_map: Vec<u32>,
+    pub(crate) elements: Vec<XmlElement>,
+    pub(crate) string_pool: ResStringPool,
+    pub(crate) resource_map: Vec<u32>,
+    #[cfg_attr(feature = "fuzzing", arbitrary(default))]
+    source: Option<Source>,
 }
 
-impl BinaryXmlDocument {
-    pub fn read_from_file<F: Read + Seek>(input: &mut F) -> Result<Self, ParseError> {
-        let header = ChunkHeader::read_from_file(input)?;
+///The bytes a document was read from, and how the document encoded right
+///after reading them.
+struct Source {
+    bytes: Vec<u8>,
+    encoded: Vec<u8>,
+}
 
-        if header.typ != ResourceType::Xml {
-            return Err(ParseError::InvalidFile);
+impl BinaryXmlDocument {
+    pub(crate) fn new(
+        elements: Vec<XmlElement>,
+        string_pool: ResStringPool,
+        resource_map: Vec<u32>,
+    ) -> Self {
+        Self {
+            elements,
+            string_pool,
+            resource_map,
+            source: None,
         }
+    }
 
-        let mut elements = Vec::new();
-        let mut string_pool = None;
-        let mut resource_map = None;
+    ///Creates a document from its node chunks, a string pool holding the
+    ///strings they refer to, and the resource ids of the attribute names at
+    ///the start of the pool.
+    pub fn from_events<I: IntoIterator<Item = XmlEvent>>(
+        events: I,
+        string_pool: ResStringPool,
+        resource_map: Vec<u32>,
+    ) -> Self {
+        let elements = events.into_iter().map(XmlEvent::into_element).collect();
+        Self::new(elements, string_pool, resource_map)
+    }
 
-        loop {
-            let header = ChunkHeader::read_from_file(input);
-            if let Err(ParseError::IoError(_)) = &header {
-                break;
-            }
-            let header = header?;
+    ///Returns the string pool.
+    pub fn string_pool(&self) -> &ResStringPool {
+        &self.string_pool
+    }
 
-            match header.typ {
-                ResourceType::StringPool => {
-                    string_pool = Some(StringPool::read_from_file(input, &header)?);
-                }
-                ResourceType::XmlResourceMap => {
-                    resource_map = Some(parse_resource_map(input, &header)?);
-                }
-                ResourceType::XmlStartNameSpace => {
-                    elements.push(XmlElement::XmlStartNameSpace(
-                        XmlStartNameSpace::read_from_file(input, &header)?,
-                    ));
-                }
-                ResourceType::XmlEndNameSpace => {
-                    elements.push(XmlElement::XmlEndNameSpace(
-                        XmlEndNameSpace::read_from_file(input, &header)?,
-                    ));
-                }
-                ResourceType::XmlStartElement => {
-                    elements.push(XmlElement::XmlStartElement(
-                        XmlStartElement::read_from_file(input, &header)?,
-                    ));
-                }
-                ResourceType::XmlEndElement => {
-                    elements.push(XmlElement::XmlEndElement(XmlEndElement::read_from_file(
-                        input, &header,
-                    )?));
-                }
-                ResourceType::XmlCdata => {
-                    elements.push(XmlElement::XmlCdata(XmlCdata::read_from_file(
-                        input, &header,
-                    )?));
-                }
-                _ => return Err(ParseError::InvalidFile),
-            }
+    ///Returns a mutable reference to the string pool.
+    pub fn string_pool_mut(&mut self) -> &mut ResStringPool {
+        &mut self.string_pool
+    }
+
+    ///Returns the resource ids of the attribute names, by string pool index.
+    pub fn resource_map(&self) -> &[u32] {
+        &self.resource_map
+    }
+
+    ///Returns a mutable reference to the resource map.
+    pub fn resource_map_mut(&mut self) -> &mut Vec<u32> {
+        &mut self.resource_map
+    }
+
+    ///Reads a document using the default [ParseConfig].
+    ///
+    ///The document remembers the bytes it was read from, and writes them
+    ///back verbatim as long as it is not modified. This keeps padding,
+    ///unused header fields and chunk order that the writer would otherwise
+    ///normalize.
+    pub fn read_from<F: Read>(input: &mut F) -> Result<Self, ParseError> {
+        Self::read_from_with_config(input, &ParseConfig::default())
+    }
+
+    #[deprecated(note = "renamed to `read_from`")]
+    pub fn read_from_file<F: Read>(input: &mut F) -> Result<Self, ParseError> {
+        Self::read_from(input)
+    }
+
+    #[deprecated(note = "renamed to `read_from_with_config`")]
+    pub fn read_from_file_with_config<F: Read>(
+        input: &mut F,
+        config: &ParseConfig,
+    ) -> Result<Self, ParseError> {
+        Self::read_from_with_config(input, config)
+    }
+
+    ///Reads a document using the given [ParseConfig]. Chunks the
+    ///configuration skips are kept as [XmlEvent::Unknown].
+    pub fn read_from_with_config<F: Read>(
+        input: &mut F,
+        config: &ParseConfig,
+    ) -> Result<Self, ParseError> {
+        let mut recorder = Recorder {
+            inner: input,
+            bytes: Vec::new(),
+        };
+        let (mut document, _, stats, _) =
+            Self::read_with_config(&mut recorder, InputExtent::default(), config)?;
+        let mut bytes = recorder.bytes;
+        bytes.truncate(usize::try_from(stats.bytes_consumed).unwrap_or(usize::MAX));
+        let mut encoded = Cursor::new(Vec::new());
+        document
+            .write_chunks(&mut encoded)
+            .map_err(ParseError::IoError)?;
+        document.source = Some(Source {
+            bytes,
+            encoded: encoded.into_inner(),
+        });
+
+        Ok(document)
+    }
+
+    pub(crate) fn read_with_config<F: Read>(
+        input: &mut F,
+        extent: InputExtent,
+        config: &ParseConfig,
+    ) -> Result<(Self, Vec<Warning>, ParseStats, DocumentMetadata), ParseError> {
+        let (mut partial, error) = PartialBinaryXmlDocument::read_from_file(input, extent, config);
+        if let Some(e) = error {
+            return Err(e);
         }
 
-        Ok(Self {
-            elements,
-            string_pool: string_pool.ok_or(ParseError::MissingStringPoolChunk)?,
-            resource_map: resource_map.ok_or(ParseError::MissingResourceMapChunk)?,
-        })
+        let string_pool = partial
+            .string_pool
+            .ok_or(ParseError::MissingStringPoolChunk)?;
+        partial.stats.string_count = string_pool.len();
+        partial.stats.utf8_strings = string_pool.is_utf8();
+
+        let document = Self::new(
+            partial.elements,
+            string_pool,
+            // Only attributes without a usable name need the resource map,
+            // and minimal documents such as compiled xml/ resources omit it.
+            partial.resource_map.unwrap_or_default(),
+        );
+
+        Ok((document, partial.warnings, partial.stats, partial.metadata))
+    }
+
+    #[deprecated(note = "renamed to `write_to`")]
+    pub fn write_to_file<F: Write + Seek>(&self, output: &mut F) -> Result<usize, std::io::Error> {
+        self.write_to(output)
+    }
+
+    ///Writes the document, returning the number of bytes written. Documents
+    ///that were read and not modified since are written exactly as read.
+    pub fn write_to<F: Write + Seek>(&self, output: &mut F) -> Result<usize, std::io::Error> {
+        let source = match &self.source {
+            Some(source) => source,
+            None => return self.write_chunks(output),
+        };
+
+        let mut encoded = Cursor::new(Vec::new());
+        self.write_chunks(&mut encoded)?;
+        let encoded = encoded.into_inner();
+        let data = if encoded == source.encoded {
+            &source.bytes
+        } else {
+            &encoded
+        };
+        output.write_all(data)?;
+        Ok(data.len())
     }
 
-    pub fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    ///Writes the string pool, then the resource map, then the node chunks.
+    fn write_chunks<F: Write + Seek>(&self, output: &mut F) -> Result<usize, std::io::Error> {
         let header = ChunkHeader {
             typ: ResourceType::Xml,
             header_size: 8,
             size: 0,
         };
-        let offset = output.seek(SeekFrom::Current(0))?;
+        let offset = output.stream_position()?;
         let n = header.write_to_file(output)?;
         let mut n = self.string_pool.write_to_file(output)? + n;
         // let n = self.resource_map.write_to_file(output)? + n;
+        let resource_map_size = u32::try_from(self.resource_map.len())
+            .ok()
+            .and_then(|len| len.checked_mul(4))
+            .and_then(|size| size.checked_add(8))
+            .ok_or_else(|| {
+                std::io::Error::new(ErrorKind::InvalidInput, "resource map too large to encode")
+            })?;
         let resource_header = ChunkHeader {
             typ: ResourceType::XmlResourceMap,
             header_size: 8,
-            size: self.resource_map.len() as u32 * 4 + 8
+            size: resource_map_size,
         };
         n += resource_header.write_to_file(output)?;
         for i in &self.resource_map {
-            n += write_u32(output,*i)?;
+            n += write_u32(output, *i)?;
         }
         for el in &self.elements {
             n += el.write_to_file(output)?;
         }
         output.seek(SeekFrom::Start(offset + 4))?;
-        write_u32(output,n as u32)?;
+        write_u32(output, n as u32)?;
         Ok(n)
     }
 }
 
+///Chunks that were successfully read before the input ended or an error
+///was encountered.
+#[derive(Default)]
+pub(crate) struct PartialBinaryXmlDocument {
+    pub(crate) elements: Vec<XmlElement>,
+    pub(crate) string_pool: Option<ResStringPool>,
+    pub(crate) resource_map: Option<Vec<u32>>,
+    pub(crate) warnings: Vec<Warning>,
+    pub(crate) stats: ParseStats,
+    pub(crate) metadata: DocumentMetadata,
+    ///Nesting depth of elements at the chunk being read.
+    depth: usize,
+}
+
+impl PartialBinaryXmlDocument {
+    pub(crate) fn read_from_file<F: Read>(
+        input: &mut F,
+        extent: InputExtent,
+        config: &ParseConfig,
+    ) -> (Self, Option<ParseError>) {
+        let mut document = Self::default();
+        let error = document.read_chunks(input, extent, config).err();
+        if let Some(string_pool) = &document.string_pool {
+            document.metadata.utf8_strings = string_pool.is_utf8();
+            document.metadata.has_styles = !string_pool.get_styles().is_empty();
+        }
+        let resource_map = document.resource_map.as_deref().unwrap_or_default();
+        document.metadata.resource_map_len = resource_map.len();
+        document.metadata.resource_map = (0..)
+            .zip(resource_map)
+            .map(|(i, &id)| ResourceMapEntry {
+                id,
+                name: document
+                    .string_pool
+                    .as_ref()
+                    .and_then(|pool| pool.get_string(i))
+                    .map(str::to_string),
+            })
+            .collect();
+        #[cfg(feature = "tracing")]
+        if let Some(e) = &error {
+            tracing::debug!(error = %e, "stopped reading chunks");
+        }
+        (document, error)
+    }
+
+    fn read_chunks<F: Read>(
+        &mut self,
+        input: &mut F,
+        extent: InputExtent,
+        config: &ParseConfig,
+    ) -> Result<(), ParseError> {
+        let (typ, header_size, size) = ChunkHeader::read_raw(input)?;
+        let header = match ResourceType::try_from(typ) {
+            Ok(ResourceType::Xml) => ChunkHeader {
+                typ: ResourceType::Xml,
+                header_size,
+                size,
+            },
+            _ => return Err(ParseError::BadMagic { found: typ }),
+        };
+
+        // Inner chunks must fit within both the declared end of the document
+        // and the actual length of the input, when it is known. Anything past
+        // the declared end is trailing garbage appended by some packers, and
+        // is never looked at.
+        let document_start = extent.start;
+        let document_end = extent.len.map_or(u64::from(header.size), |len| {
+            len.min(u64::from(header.size))
+        });
+        self.stats.count_chunk(header.typ.into());
+        self.metadata.declared_size = header.size;
+        self.metadata.chunks.push(ChunkInfo {
+            typ: header.typ.into(),
+            offset: 0,
+            declared_size: header.size,
+            bytes_read: u64::from(CHUNK_HEADER_SIZE),
+        });
+
+        // Positions are counted rather than asked for, so that the input
+        // only ever needs to be read forward.
+        let mut consumed = u64::from(CHUNK_HEADER_SIZE);
+        loop {
+            self.stats.bytes_consumed = consumed;
+            self.stats.file_size = extent.len.unwrap_or(consumed);
+            self.metadata.file_size = self.stats.file_size;
+            self.metadata.chunks[0].bytes_read = consumed;
+            if consumed >= document_end {
+                break;
+            }
+            let offset = document_start + consumed;
+            let (typ, header_size, size) = match ChunkHeader::read_raw(input) {
+                Err(ParseError::IoError(_)) => break,
+                header => header?,
+            };
+            // Unknown chunk types are rejected or skipped just like known
+            // types that do not belong in an XML document.
+            let header = ChunkHeader {
+                typ: ResourceType::try_from(typ).unwrap_or(ResourceType::NullType),
+                header_size,
+                size,
+            };
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                typ = format_args!("{:#06x}", typ),
+                offset,
+                size,
+                "read chunk"
+            );
+            header.validate(offset, document_end.saturating_sub(consumed), config)?;
+            if header.typ == ResourceType::StringPool {
+                ResStringPool::data_size(&header, config)?;
+            }
+            self.stats.count_chunk(typ);
+
+            // Each chunk is read into memory on its own, so that its parts can
+            // be located by offset without seeking in the input.
+            let mut chunk = Vec::with_capacity(usize::from(CHUNK_HEADER_SIZE));
+            chunk.extend_from_slice(&typ.to_le_bytes());
+            chunk.extend_from_slice(&header_size.to_le_bytes());
+            chunk.extend_from_slice(&size.to_le_bytes());
+            input
+                .take(u64::from(size) - u64::from(CHUNK_HEADER_SIZE))
+                .read_to_end(&mut chunk)
+                .map_err(|source| ParseError::ChunkIo {
+                    chunk_type: typ,
+                    offset,
+                    source,
+                })?;
+            consumed += chunk.len() as u64;
+            if chunk.len() < size as usize {
+                return Err(ParseError::ChunkOutOfBounds {
+                    offset,
+                    size,
+                    remaining: chunk.len() as u64,
+                });
+            }
+
+            let mut cursor = Cursor::new(&chunk[..]);
+            cursor.set_position(u64::from(CHUNK_HEADER_SIZE));
+            match self.read_chunk(&mut cursor, offset, &header, typ, config) {
+                // Never let a chunk consume more than it declared.
+                Err(ParseError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof => {
+                    return Err(ParseError::ChunkOverrun { offset, size })
+                }
+                result => result?,
+            }
+            self.metadata.chunks.push(ChunkInfo {
+                typ,
+                offset: offset - document_start,
+                declared_size: size,
+                bytes_read: cursor.position().min(u64::from(size)),
+            });
+        }
+
+        Ok(())
+    }
+
+    ///Reads the chunk in `chunk`, positioned after its chunk header.
+    fn read_chunk(
+        &mut self,
+        chunk: &mut Cursor<&[u8]>,
+        offset: u64,
+        header: &ChunkHeader,
+        typ: u16,
+        config: &ParseConfig,
+    ) -> Result<(), ParseError> {
+        // Node chunks are only resolved once every chunk has been read, so
+        // the string pool and resource map may appear anywhere in the
+        // document, as they may for Android.
+        match header.typ {
+            // Later pools are decoys added by packers. Android only ever
+            // looks at the first one.
+            ResourceType::StringPool if self.string_pool.is_some() => {
+                self.skip_chunk(chunk, offset, header, typ);
+                push_warning(&mut self.warnings, Warning::DuplicateStringPool { offset })
+            }
+            ResourceType::StringPool => {
+                match ResStringPool::read_chunk(chunk, header, config, &mut self.warnings) {
+                    Ok(string_pool) => self.string_pool = Some(string_pool),
+                    // A broken pool may be a decoy placed before the real
+                    // one, which is then used instead. Limits still apply.
+                    Err(cause)
+                        if config.tolerate_obfuscation
+                            && !matches!(cause, ParseError::LimitExceeded { .. }) =>
+                    {
+                        self.skip_chunk(chunk, offset, header, typ);
+                        push_warning(
+                            &mut self.warnings,
+                            Warning::DroppedStringPool { offset, cause },
+                        )
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            ResourceType::XmlResourceMap => {
+                self.resource_map = Some(parse_resource_map(chunk, header)?);
+            }
+            ResourceType::XmlStartNameSpace => {
+                self.elements.push(XmlElement::XmlStartNameSpace(
+                    XmlStartNameSpace::read_from_file(chunk, header)?,
+                ));
+            }
+            ResourceType::XmlEndNameSpace => {
+                self.elements.push(XmlElement::XmlEndNameSpace(
+                    XmlEndNameSpace::read_from_file(chunk, header)?,
+                ));
+            }
+            ResourceType::XmlStartElement => {
+                // Limits are checked before the element is stored, so a
+                // crafted document fails before it takes up memory.
+                let elements = self.stats.chunk_counts[&typ];
+                for (limit, max, actual) in [
+                    ("nesting depth", config.max_depth, self.depth + 1),
+                    ("element count", config.max_elements, elements),
+                ]
+                .iter()
+                {
+                    if let Some(max) = *max {
+                        if *actual > max {
+                            return Err(ParseError::LimitExceeded { limit, max });
+                        }
+                    }
+                }
+                let e = XmlStartElement::read_from_file(chunk, header, config)?;
+                let actual = u16::try_from(e.attributes.len()).unwrap_or(u16::MAX);
+                if actual < e.attr_ext.attribute_count {
+                    push_warning(
+                        &mut self.warnings,
+                        Warning::ClampedAttributeCount {
+                            offset,
+                            declared: e.attr_ext.attribute_count,
+                            actual,
+                        },
+                    );
+                }
+                self.stats.attribute_count += e.attributes.len();
+                self.depth += 1;
+                self.stats.max_depth = self.stats.max_depth.max(self.depth);
+                self.elements.push(XmlElement::XmlStartElement(e));
+            }
+            ResourceType::XmlEndElement => {
+                self.depth = self.depth.saturating_sub(1);
+                self.elements
+                    .push(XmlElement::XmlEndElement(XmlEndElement::read_from_file(
+                        chunk, header,
+                    )?));
+            }
+            ResourceType::XmlCdata => {
+                self.elements
+                    .push(XmlElement::XmlCdata(XmlCdata::read_from_file(
+                        chunk, header,
+                    )?));
+            }
+            // Some tools emit an end marker or stray resource table chunks
+            // into the document. Android skips them, so they are skipped even
+            // by the strict configuration.
+            ResourceType::XmlLastChunk | ResourceType::Table => {
+                self.skip_chunk(chunk, offset, header, typ);
+                push_warning(&mut self.warnings, Warning::SkippedChunk { offset, typ })
+            }
+            _ if config.skip_unknown_chunks => {
+                self.skip_chunk(chunk, offset, header, typ);
+                push_warning(&mut self.warnings, Warning::SkippedChunk { offset, typ })
+            }
+            _ => return Err(ParseError::UnexpectedChunkType { found: typ, offset }),
+        }
+        Ok(())
+    }
+
+    ///Keeps a chunk that is not otherwise read as a [XmlElement::Raw], so
+    ///that it is written back in place.
+    fn skip_chunk(
+        &mut self,
+        chunk: &mut Cursor<&[u8]>,
+        offset: u64,
+        header: &ChunkHeader,
+        typ: u16,
+    ) {
+        let data = *chunk.get_ref();
+        chunk.set_position(data.len() as u64);
+        let header_size = usize::from(header.header_size.max(CHUNK_HEADER_SIZE)).min(data.len());
+
+        self.elements.push(XmlElement::Raw(RawChunk {
+            typ,
+            offset,
+            header_size: header.header_size,
+            size: header.size,
+            header: data[..header_size].to_vec(),
+            bytes: data[header_size..].to_vec(),
+        }));
+    }
+}
+
+///Keeps a copy of everything read through it.
+struct Recorder<'a, R> {
+    inner: &'a mut R,
+    bytes: Vec<u8>,
+}
+
+impl<R: Read> Read for Recorder<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+}
+
+///What is known about the input a document is read from.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct InputExtent {
+    ///Position of the document in the input, which offsets in diagnostics
+    ///are counted from.
+    pub(crate) start: u64,
+    ///Number of bytes from the start of the document to the end of the
+    ///input, if known.
+    pub(crate) len: Option<u64>,
+}
+
+impl InputExtent {
+    ///Measures an input that can seek, leaving its position unchanged.
+    pub(crate) fn measure<F: Seek>(input: &mut F) -> Result<Self, ParseError> {
+        let start = input.stream_position().map_err(ParseError::IoError)?;
+        let end = input.seek(SeekFrom::End(0)).map_err(ParseError::IoError)?;
+        input
+            .seek(SeekFrom::Start(start))
+            .map_err(ParseError::IoError)?;
+        Ok(Self {
+            start,
+            len: Some(end.saturating_sub(start)),
+        })
+    }
+}
+
 #[repr(u16)]
 #[derive(Debug, PartialEq, Copy, Clone, TryFromPrimitive, IntoPrimitive)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub(crate) enum ResourceType {
     NullType = 0x000,
     StringPool = 0x0001,
@@ -123,8 +586,21 @@ pub(crate) enum ResourceType {
     TableLibrary = 0x0203,
 }
 
+pub(crate) const CHUNK_HEADER_SIZE: u16 = 8;
+
+///Size of the header of node chunks, the chunk header followed by the line
+///number and comment.
+const NODE_HEADER_SIZE: u16 = 16;
+
+///Size of the attribute extension of start element chunks.
+const ATTR_EXT_SIZE: u16 = 20;
+
+///Size of a single attribute of a start element chunk.
+const ATTRIBUTE_SIZE: u16 = 20;
+
 #[repr(C)]
 #[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub(crate) struct ChunkHeader {
     pub(crate) typ: ResourceType,
     pub(crate) header_size: u16,
@@ -132,10 +608,12 @@ pub(crate) struct ChunkHeader {
 }
 
 impl ChunkHeader {
-    pub(crate) fn read_from_file<F: Read + Seek>(input: &mut F) -> Result<Self, ParseError> {
-        let typ = ResourceType::try_from(read_u16(input)?).map_err(|_| ParseError::InvalidFile)?;
-        let header_size = read_u16(input)?;
-        let size = read_u32(input)?;
+    ///Reads a chunk header found at `offset`, which is only used to report
+    ///unknown chunk types.
+    pub(crate) fn read_from_file<F: Read>(input: &mut F, offset: u64) -> Result<Self, ParseError> {
+        let (typ, header_size, size) = Self::read_raw(input)?;
+        let typ = ResourceType::try_from(typ)
+            .map_err(|_| ParseError::UnexpectedChunkType { found: typ, offset })?;
 
         let header = ChunkHeader {
             typ,
@@ -146,8 +624,57 @@ impl ChunkHeader {
         Ok(header)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let t: u16 = self.typ.try_into().unwrap();
+    ///Reads the type, header size and size of a chunk without interpreting
+    ///the type.
+    pub(crate) fn read_raw<F: Read>(input: &mut F) -> Result<(u16, u16, u32), ParseError> {
+        let typ = read_u16(input)?;
+        let header_size = read_u16(input)?;
+        let size = read_u32(input)?;
+
+        Ok((typ, header_size, size))
+    }
+
+    ///Checks that the header sizes are consistent with each other and that the
+    ///chunk fits within the `remaining` bytes of input starting at `offset`.
+    ///
+    ///When tolerating obfuscation `header_size` is not trusted, as the chunks
+    ///are read using their fixed layout anyway.
+    pub(crate) fn validate(
+        &self,
+        offset: u64,
+        remaining: u64,
+        config: &ParseConfig,
+    ) -> Result<(), ParseError> {
+        let header_size_valid = if config.tolerate_obfuscation {
+            self.size >= u32::from(CHUNK_HEADER_SIZE)
+        } else {
+            self.header_size >= CHUNK_HEADER_SIZE && u32::from(self.header_size) <= self.size
+        };
+
+        if !header_size_valid {
+            return Err(ParseError::InvalidChunkHeaderSize {
+                offset,
+                header_size: self.header_size,
+                size: self.size,
+            });
+        }
+
+        if u64::from(self.size) > remaining {
+            return Err(ParseError::ChunkOutOfBounds {
+                offset,
+                size: self.size,
+                remaining,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let t: u16 = self.typ.into();
         let n = write_u16(output, t)?;
         let n = write_u16(output, self.header_size)? + n;
         let n = write_u32(output, self.size)? + n;
@@ -158,21 +685,28 @@ impl ChunkHeader {
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum XmlElement {
     XmlStartNameSpace(XmlStartNameSpace),
     XmlEndNameSpace(XmlEndNameSpace),
     XmlStartElement(XmlStartElement),
     XmlEndElement(XmlEndElement),
     XmlCdata(XmlCdata),
+    ///A chunk that was skipped while reading, kept as it was read.
+    Raw(RawChunk),
 }
 
 pub(crate) fn parse_resource_map<F: Read + Seek>(
     input: &mut F,
     header: &ChunkHeader,
 ) -> Result<Vec<u32>, ParseError> {
-    let id_count = (header.size - u32::from(header.header_size)) / 4;
+    let id_count = header
+        .size
+        .checked_sub(u32::from(header.header_size))
+        .ok_or(ParseError::SizeMismatch("resource map"))?
+        / 4;
 
-    let mut ids = Vec::with_capacity(usize::try_from(id_count).unwrap());
+    let mut ids = Vec::new();
     for _ in 0..id_count {
         ids.push(read_u32(input)?);
     }
@@ -181,6 +715,7 @@ pub(crate) fn parse_resource_map<F: Read + Seek>(
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub(crate) struct XmlNodeHeader {
     pub(crate) chunk_header: ChunkHeader,
     pub(crate) line_no: u32,
@@ -192,7 +727,7 @@ impl XmlNodeHeader {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let chunk_header = chunk_header.clone();
+        let chunk_header = *chunk_header;
         let line_no = read_u32(input)?;
         let comment = read_u32(input)?;
 
@@ -204,15 +739,27 @@ impl XmlNodeHeader {
 
         Ok(header)
     }
-    fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let n = self.chunk_header.write_to_file(output)?;
-        let n = n + write_u32(output,self.line_no)?;
-        let n = n + write_u32(output,self.comment)?;
+    ///Writes the header of a node chunk of `size` bytes. The header size is
+    ///always written as 16, whatever was read.
+    fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+        size: u32,
+    ) -> Result<usize, std::io::Error> {
+        let chunk_header = ChunkHeader {
+            typ: self.chunk_header.typ,
+            header_size: NODE_HEADER_SIZE,
+            size,
+        };
+        let n = chunk_header.write_to_file(output)?;
+        let n = n + write_u32(output, self.line_no)?;
+        let n = n + write_u32(output, self.comment)?;
         Ok(n)
     }
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlStartNameSpace {
     pub(crate) header: XmlNodeHeader,
     pub(crate) prefix: u32,
@@ -224,7 +771,7 @@ impl XmlStartNameSpace {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let header = XmlNodeHeader::read_from_file(input, &chunk_header)?;
+        let header = XmlNodeHeader::read_from_file(input, chunk_header)?;
         let prefix = read_u32(input)?;
         let uri = read_u32(input)?;
 
@@ -236,8 +783,13 @@ impl XmlStartNameSpace {
 
         Ok(node)
     }
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let n = self.header.write_to_file(output)?;
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let n = self
+            .header
+            .write_to_file(output, u32::from(NODE_HEADER_SIZE) + 8)?;
         let n = n + write_u32(output, self.prefix)?;
         let n = n + write_u32(output, self.uri)?;
         Ok(n)
@@ -245,6 +797,7 @@ impl XmlStartNameSpace {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlEndNameSpace {
     pub(crate) header: XmlNodeHeader,
     pub(crate) prefix: u32,
@@ -256,7 +809,7 @@ impl XmlEndNameSpace {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let header = XmlNodeHeader::read_from_file(input, &chunk_header)?;
+        let header = XmlNodeHeader::read_from_file(input, chunk_header)?;
         let prefix = read_u32(input)?;
         let uri = read_u32(input)?;
 
@@ -268,8 +821,13 @@ impl XmlEndNameSpace {
 
         Ok(node)
     }
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let n = self.header.write_to_file(output)?;
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let n = self
+            .header
+            .write_to_file(output, u32::from(NODE_HEADER_SIZE) + 8)?;
         let n = n + write_u32(output, self.prefix)?;
         let n = n + write_u32(output, self.uri)?;
         Ok(n)
@@ -277,6 +835,7 @@ impl XmlEndNameSpace {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlAttrExt {
     pub(crate) ns: u32,
     pub(crate) name: u32,
@@ -314,7 +873,23 @@ impl XmlAttrExt {
         Ok(header)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    ///Returns the offset of the first attribute relative to the start of the
+    ///extension, and the distance between consecutive attributes.
+    ///
+    ///Both must leave room for the structures they describe. When tolerating
+    ///obfuscation, values that do not are replaced by the standard layout.
+    fn attribute_layout(&self, config: &ParseConfig) -> Result<(u16, u16), ParseError> {
+        match (self.attribute_start, self.attribute_size) {
+            (start, size) if start >= ATTR_EXT_SIZE && size >= ATTRIBUTE_SIZE => Ok((start, size)),
+            _ if config.tolerate_obfuscation => Ok((ATTR_EXT_SIZE, ATTRIBUTE_SIZE)),
+            _ => Err(ParseError::SizeMismatch("attribute layout")),
+        }
+    }
+
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
         let n = write_u32(output, self.ns)?;
         let n = n + write_u32(output, self.name)?;
         let n = n + write_u16(output, self.attribute_start)?;
@@ -325,10 +900,10 @@ impl XmlAttrExt {
         let n = n + write_u16(output, self.style_index)?;
         Ok(n)
     }
-
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlAttribute {
     pub(crate) ns: u32,
     pub(crate) name: u32,
@@ -340,7 +915,9 @@ impl XmlAttribute {
     fn read_from_file<F: Read + Seek>(input: &mut F) -> Result<Self, ParseError> {
         let ns = read_u32(input)?;
         let name = read_u32(input)?;
-        let raw_value = read_u32(input)?; // raw_value stored in the chunk. There does not seem to be any value in keeping it around since `typed_value` is available...
+        // Pool index of the raw string, which aapt keeps alongside typed values
+        // and which is exposed through `TypedAttribute::raw_value`.
+        let raw_value = read_u32(input)?;
         let typed_value = ResourceValue::read_from_file(input)?;
 
         let attr = Self {
@@ -353,7 +930,10 @@ impl XmlAttribute {
         Ok(attr)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
         let n = write_u32(output, self.ns)?;
         let n = n + write_u32(output, self.name)?;
         let n = n + write_u32(output, self.raw_value)?;
@@ -363,6 +943,7 @@ impl XmlAttribute {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlStartElement {
     pub(crate) header: XmlNodeHeader,
     pub(crate) attr_ext: XmlAttrExt,
@@ -373,12 +954,42 @@ impl XmlStartElement {
     pub(crate) fn read_from_file<F: Read + Seek>(
         input: &mut F,
         chunk_header: &ChunkHeader,
+        config: &ParseConfig,
     ) -> Result<Self, ParseError> {
-        let header = XmlNodeHeader::read_from_file(input, &chunk_header)?;
+        let header = XmlNodeHeader::read_from_file(input, chunk_header)?;
+        let ext_start = input.stream_position().map_err(ParseError::IoError)?;
         let attr_ext = XmlAttrExt::read_from_file(input)?;
+        let (attribute_start, attribute_size) = attr_ext.attribute_layout(config)?;
 
-        let mut attributes = Vec::with_capacity(attr_ext.attribute_count.into());
-        for _ in 0..attr_ext.attribute_count {
+        // Obfuscators inflate attribute_count; Android only ever looks at the
+        // attributes that actually fit in the chunk.
+        let mut attribute_count = attr_ext.attribute_count;
+        if config.tolerate_obfuscation {
+            let available = chunk_header
+                .size
+                .saturating_sub(u32::from(NODE_HEADER_SIZE) + u32::from(attribute_start))
+                / u32::from(attribute_size);
+            attribute_count = attribute_count.min(u16::try_from(available).unwrap_or(u16::MAX));
+        }
+
+        if let Some(max) = config.max_attributes {
+            if usize::from(attribute_count) > max {
+                return Err(ParseError::LimitExceeded {
+                    limit: "attributes per element",
+                    max,
+                });
+            }
+        }
+
+        let mut attributes = Vec::with_capacity(attribute_count.into());
+        for i in 0..attribute_count {
+            // Attributes may be followed by extra bytes we know nothing about,
+            // so each one is located from the declared layout.
+            let attribute_offset =
+                ext_start + u64::from(attribute_start) + u64::from(i) * u64::from(attribute_size);
+            input
+                .seek(SeekFrom::Start(attribute_offset))
+                .map_err(ParseError::IoError)?;
             attributes.push(XmlAttribute::read_from_file(input)?);
         }
 
@@ -391,25 +1002,31 @@ impl XmlStartElement {
         Ok(node)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        
-        let mut h = self.header.clone();
-        h.chunk_header.size = (std::mem::size_of::<XmlAttrExt>() + std::mem::size_of::<XmlNodeHeader>() + std::mem::size_of::<XmlAttribute>() * self.attributes.len()) as u32;
-        let mut attrext = self.attr_ext.clone();
-        attrext.attribute_start = std::mem::size_of::<XmlAttrExt>() as u16;
-        attrext.attribute_size = std::mem::size_of::<XmlAttribute>() as u16;
-        attrext.attribute_count = self.attributes.len() as u16;
-        let n = h.write_to_file(output)?;
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let attribute_count = u16::try_from(self.attributes.len()).map_err(|_| {
+            std::io::Error::new(ErrorKind::InvalidInput, "too many attributes to encode")
+        })?;
+        let size = u32::from(NODE_HEADER_SIZE)
+            + u32::from(ATTR_EXT_SIZE)
+            + u32::from(ATTRIBUTE_SIZE) * u32::from(attribute_count);
+        let mut attrext = self.attr_ext;
+        attrext.attribute_start = ATTR_EXT_SIZE;
+        attrext.attribute_size = ATTRIBUTE_SIZE;
+        attrext.attribute_count = attribute_count;
+        let n = self.header.write_to_file(output, size)?;
         let mut n = n + attrext.write_to_file(output)?;
         for attr in &self.attributes {
             n += attr.write_to_file(output)?;
         }
         Ok(n)
     }
-
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlEndElement {
     pub(crate) header: XmlNodeHeader,
     pub(crate) ns: u32,
@@ -421,7 +1038,7 @@ impl XmlEndElement {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let header = XmlNodeHeader::read_from_file(input, &chunk_header)?;
+        let header = XmlNodeHeader::read_from_file(input, chunk_header)?;
         let ns = read_u32(input)?;
         let name = read_u32(input)?;
 
@@ -430,8 +1047,13 @@ impl XmlEndElement {
         Ok(node)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let n = self.header.write_to_file(output)?;
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let n = self
+            .header
+            .write_to_file(output, u32::from(NODE_HEADER_SIZE) + 8)?;
         let n = n + write_u32(output, self.ns)?;
         let n = n + write_u32(output, self.name)?;
         Ok(n)
@@ -439,6 +1061,7 @@ impl XmlEndElement {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct XmlCdata {
     pub(crate) header: XmlNodeHeader,
     pub(crate) data: u32,
@@ -450,7 +1073,7 @@ impl XmlCdata {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let header = XmlNodeHeader::read_from_file(input, &chunk_header)?;
+        let header = XmlNodeHeader::read_from_file(input, chunk_header)?;
         let data = read_u32(input)?;
         let typed_data = ResourceValue::read_from_file(input)?;
 
@@ -463,8 +1086,13 @@ impl XmlCdata {
         Ok(node)
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
-        let n = self.header.write_to_file(output)?;
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
+        let n = self
+            .header
+            .write_to_file(output, u32::from(NODE_HEADER_SIZE) + 12)?;
         let n = n + write_u32(output, self.data)?;
         let n = n + self.typed_data.write_to_file(output)?;
         Ok(n)
@@ -472,19 +1100,27 @@ impl XmlCdata {
 }
 
 impl XmlElement {
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
         match self {
             XmlElement::XmlStartNameSpace(d) => d.write_to_file(output),
             XmlElement::XmlEndNameSpace(d) => d.write_to_file(output),
             XmlElement::XmlStartElement(d) => d.write_to_file(output),
             XmlElement::XmlEndElement(d) => d.write_to_file(output),
             XmlElement::XmlCdata(d) => d.write_to_file(output),
+            XmlElement::Raw(chunk) => {
+                output.write_all(&chunk.header)?;
+                output.write_all(&chunk.bytes)?;
+                Ok(chunk.header.len() + chunk.bytes.len())
+            }
         }
     }
 }
 
 #[test]
-fn test_xml_cdata_rw(){
+fn test_xml_cdata_rw() {
     let src = [
         0x04, 0x01, // type
         0x10, 0x00, // header_size
@@ -492,14 +1128,133 @@ fn test_xml_cdata_rw(){
         0x99, 0x00, 0x00, 0x00, // line_no
         0xff, 0xff, 0xff, 0xff, // comment
         0x12, 0x34, 0x56, 0x78, // data
-        0x08, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x00 // data_typed
+        0x08, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x00, // data_typed
     ];
     let mut dst: Vec<u8> = vec![];
     let mut cursor = std::io::Cursor::new(src);
-    let ch = ChunkHeader::read_from_file(&mut cursor).unwrap();
+    let ch = ChunkHeader::read_from_file(&mut cursor, 0).unwrap();
     let v = XmlCdata::read_from_file(&mut cursor, &ch).unwrap();
-    
+
     let mut cursor = std::io::Cursor::new(&mut dst);
     let n = v.write_to_file(&mut cursor).unwrap();
+    assert_eq!(n, src.len());
     assert_eq!(&src, dst.as_slice());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_chunk_out_of_bounds() {
+    let src = [
+        0x03, 0x00, 0x08, 0x00, 24, 0, 0, 0, // xml chunk
+        0x04, 0x01, 0x10, 0x00, 0xe8, 0x03, 0, 0, // cdata chunk claiming 1000 bytes
+        0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    let mut cursor = std::io::Cursor::new(src);
+    assert!(matches!(
+        BinaryXmlDocument::read_from(&mut cursor),
+        Err(ParseError::ChunkOutOfBounds {
+            offset: 8,
+            size: 1000,
+            remaining: 16
+        })
+    ));
+
+    // A stream of unknown length is bounded by the declared document size,
+    // and an oversized string pool is rejected before its body is read.
+    let src = [
+        0x03, 0x00, 0x08, 0x00, 16, 0, 0, 0, // xml chunk
+        0x01, 0x00, 0x1c, 0x00, 0xff, 0xff, 0xff, 0xff, // string pool claiming 4 GiB
+    ];
+    let mut input = src.chain(std::io::repeat(0));
+    assert!(matches!(
+        crate::parse(&mut input),
+        Err(ParseError::ChunkOutOfBounds {
+            offset: 8,
+            size: u32::MAX,
+            remaining: 8
+        })
+    ));
+
+    let src = [
+        0x03, 0x00, 0x08, 0x00, 0xff, 0xff, 0xff, 0xff, // xml chunk claiming 4 GiB
+        0x01, 0x00, 0x1c, 0x00, 0, 0, 0, 0x70, // string pool claiming 1.75 GiB
+    ];
+    let config = ParseConfig {
+        max_string_pool_size: Some(1 << 20),
+        ..ParseConfig::hardened()
+    };
+    assert!(matches!(
+        crate::parse_with_config(&mut src.chain(std::io::repeat(0)), &config),
+        Err(ParseError::LimitExceeded {
+            limit: "string pool size",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_xml_start_element_attribute_layout() {
+    let src = [
+        0x02, 0x01, 0x10, 0x00, 64, 0, 0, 0, // chunk header
+        0x01, 0x00, 0x00, 0x00, // line_no
+        0xff, 0xff, 0xff, 0xff, // comment
+        0xff, 0xff, 0xff, 0xff, // ns
+        0x00, 0x00, 0x00, 0x00, // name
+        24, 0, 24, 0, 1, 0, // attribute_start, attribute_size, attribute_count
+        0, 0, 0, 0, 0, 0, // id_index, class_index, style_index
+        0xaa, 0xaa, 0xaa, 0xaa, // padding before the attributes
+        0xff, 0xff, 0xff, 0xff, // attribute ns
+        0x01, 0x00, 0x00, 0x00, // attribute name
+        0xff, 0xff, 0xff, 0xff, // attribute raw_value
+        0x08, 0x00, 0x00, 0x10, 42, 0, 0, 0, // attribute typed_value
+        0xbb, 0xbb, 0xbb, 0xbb, // padding after the attribute
+    ];
+    let mut cursor = std::io::Cursor::new(src);
+    let ch = ChunkHeader::read_from_file(&mut cursor, 0).unwrap();
+    let e = XmlStartElement::read_from_file(&mut cursor, &ch, &ParseConfig::default()).unwrap();
+
+    assert_eq!(e.attributes.len(), 1);
+    assert_eq!(e.attributes[0].name, 1);
+    assert_eq!(e.attributes[0].typed_value.data, 42);
+
+    let mut e = e;
+    e.attributes = (0..=usize::from(u16::MAX))
+        .map(|_| XmlAttribute::read_from_file(&mut std::io::Cursor::new(&src[40..60])).unwrap())
+        .collect();
+    let err = e
+        .write_to_file(&mut std::io::Cursor::new(Vec::new()))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_parse_error_context() {
+    let src = [
+        0x03, 0x00, 0x08, 0x00, 16, 0, 0, 0, // xml chunk
+        0x77, 0x77, 0x08, 0x00, 8, 0, 0, 0, // chunk of an unknown type
+    ];
+    let config = ParseConfig::strict();
+    assert!(matches!(
+        BinaryXmlDocument::read_from_with_config(&mut std::io::Cursor::new(src), &config),
+        Err(ParseError::UnexpectedChunkType {
+            found: 0x7777,
+            offset: 8
+        })
+    ));
+
+    let mut bad_magic = src;
+    bad_magic[0] = 0x02;
+    assert!(matches!(
+        BinaryXmlDocument::read_from(&mut std::io::Cursor::new(bad_magic)),
+        Err(ParseError::BadMagic { found: 0x0002 })
+    ));
+
+    let resource_map = ChunkHeader {
+        typ: ResourceType::XmlResourceMap,
+        header_size: 8,
+        size: 4,
+    };
+    assert!(matches!(
+        parse_resource_map(&mut std::io::Cursor::new([]), &resource_map),
+        Err(ParseError::SizeMismatch("resource map"))
+    ));
+}
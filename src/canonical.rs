@@ -0,0 +1,575 @@
+use std::fmt::Write;
+
+use crate::xml::{split_prefix, Element, Node, XmlDocument, ANDROID_NAMESPACE};
+
+///Version tag of the format written by [XmlDocument::canonical_string].
+///Bumped whenever the format changes in any way.
+const CANONICAL_STRING_VERSION: u32 = 1;
+
+///Prefixes that canonical XML always uses for well-known namespaces.
+const WELL_KNOWN_PREFIXES: &[(&str, &str)] = &[
+    (ANDROID_NAMESPACE, "android"),
+    ("http://schemas.android.com/apk/res-auto", "app"),
+    ("http://schemas.android.com/tools", "tools"),
+];
+
+///A stable, platform independent 128-bit FNV-1a hasher.
+struct Fnv128(u128);
+
+impl Fnv128 {
+    const OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+    const PRIME: u128 = 0x0000000001000000000000000000013b;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u128::from(b);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    ///Writes `s` prefixed by its length, so that consecutive strings cannot
+    ///run into each other.
+    fn write_str(&mut self, s: &str) {
+        self.write(&(s.len() as u64).to_le_bytes());
+        self.write(s.as_bytes());
+    }
+}
+
+impl XmlDocument {
+    ///Returns a digest of the document that only depends on its content.
+    ///
+    ///Namespaces are identified by their URI rather than their prefix,
+    ///attributes are taken in sorted order, and line numbers as well as
+    ///whitespace-only text are ignored. Two documents with the same hash
+    ///therefore describe the same manifest, even if they were packaged
+    ///differently. The digest is stable across platforms and releases of
+    ///this crate.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let data = std::fs::read(manifest_file).unwrap();
+    ///let a = parse(&mut std::io::Cursor::new(&data)).unwrap();
+    ///let b = parse(&mut std::io::Cursor::new(&data)).unwrap();
+    ///assert_eq!(a.canonical_hash(), b.canonical_hash());
+    ///```
+    pub fn canonical_hash(&self) -> u128 {
+        let mut hasher = Fnv128::new();
+        if let Some(root) = self.get_root() {
+            self.hash_node(root, &mut hasher);
+        }
+        hasher.0
+    }
+
+    fn hash_node(&self, node: &Node, hasher: &mut Fnv128) {
+        match node {
+            Node::Element(e) => self.hash_element(e, hasher),
+            Node::Cdata(c) => {
+                let text = c.get_data().trim();
+                if !text.is_empty() {
+                    hasher.write(b"T");
+                    hasher.write_str(text);
+                }
+            }
+        }
+    }
+
+    fn hash_element(&self, e: &Element, hasher: &mut Fnv128) {
+        hasher.write(b"E");
+        hasher.write_str(self.namespace_of(&e.namespace, e.get_tag()));
+        hasher.write_str(&e.name);
+
+        let attributes = self.canonical_attributes(e);
+        hasher.write(&(attributes.len() as u64).to_le_bytes());
+        for (namespace, name, value) in attributes {
+            hasher.write_str(namespace);
+            hasher.write_str(name);
+            hasher.write_str(value);
+        }
+
+        for child in e.get_children() {
+            self.hash_node(child, hasher);
+        }
+        hasher.write(b"/");
+    }
+
+    ///Writes the document as canonical XML, a form that only depends on the
+    ///content of the document, for hashing or comparing against allowlists.
+    ///The form is fixed and will not change between releases of this crate:
+    ///
+    ///* There is no XML declaration, comments are left out, and no
+    ///  whitespace is added between elements. Text is trimmed, and text
+    ///  that is only whitespace is dropped.
+    ///* Namespaces get fixed prefixes: `android`, `app` and `tools` for the
+    ///  well-known namespaces, and `ns0`, `ns1` and so on for others in the
+    ///  order they are first used. Every namespace that is used is declared
+    ///  on the root element, sorted by prefix.
+    ///* Attributes are sorted with those without a namespace first, then by
+    ///  namespace URI and name.
+    ///* Empty elements are written with an end tag, as `<a></a>`.
+    ///* `&`, `<` and `>` are escaped in text, and `&`, `<` and `"` in
+    ///  attribute values, as are tabs and line breaks in attribute values,
+    ///  carriage returns and other control characters, as `&#xD;`.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut root = Element::new("manifest");
+    ///root.set_attribute("a:versionCode", "1");
+    ///root.set_attribute("package", "com.example");
+    ///let mut xml = XmlDocument::new(Node::Element(root));
+    ///xml.declare_namespace("a", "http://schemas.android.com/apk/res/android");
+    ///assert_eq!(
+    ///    xml.to_canonical_xml(),
+    ///    "<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\" \
+    ///     package=\"com.example\" android:versionCode=\"1\"></manifest>"
+    ///);
+    ///```
+    pub fn to_canonical_xml(&self) -> String {
+        let root = match self.get_root() {
+            Some(Node::Element(root)) => root,
+            Some(Node::Cdata(c)) => return escape_canonical(c.get_data().trim(), false),
+            None => return String::new(),
+        };
+
+        let mut prefixes: Vec<(&str, String)> = Vec::new();
+        self.collect_namespaces(root, &mut prefixes);
+        let declarations: String = sorted_declarations(&prefixes)
+            .into_iter()
+            .map(|declaration| format!(" {}", declaration))
+            .collect();
+
+        let mut output = String::new();
+        self.write_canonical(&mut output, root, &prefixes, &declarations);
+        output
+    }
+
+    ///Writes the document in a line-based form meant for snapshot tests,
+    ///such as those of APK build pipelines, where a changed manifest should
+    ///show up as a readable diff.
+    ///
+    ///The output follows the rules of [XmlDocument::to_canonical_xml] for
+    ///namespaces, attribute order, escaping and which text is kept, but
+    ///puts every element, attribute and text on a line of its own, indented
+    ///by nesting depth. Empty elements are written as `<a/>`, and line
+    ///breaks in text are escaped so text always stays on one line.
+    ///
+    ///The first line is a version tag, `<!-- axmldecoder canonical v1 -->`.
+    ///The output for a given version tag is guaranteed not to change
+    ///between releases of this crate: any change to the format comes with
+    ///a new tag, so a snapshot that changes only because the format did is
+    ///easy to tell apart from one where the document changed.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut root = Element::new("manifest");
+    ///root.set_attribute("a:versionCode", "1");
+    ///root.set_attribute("package", "com.example");
+    ///root.get_children_mut()
+    ///    .push(Node::Element(Element::new("application")));
+    ///let mut xml = XmlDocument::new(Node::Element(root));
+    ///xml.declare_namespace("a", "http://schemas.android.com/apk/res/android");
+    ///assert_eq!(
+    ///    xml.canonical_string(),
+    ///    "<!-- axmldecoder canonical v1 -->
+    ///<manifest
+    ///    xmlns:android=\"http://schemas.android.com/apk/res/android\"
+    ///    package=\"com.example\"
+    ///    android:versionCode=\"1\">
+    ///  <application/>
+    ///</manifest>
+    ///"
+    ///);
+    ///```
+    pub fn canonical_string(&self) -> String {
+        let mut output = format!(
+            "<!-- axmldecoder canonical v{} -->\n",
+            CANONICAL_STRING_VERSION
+        );
+        match self.get_root() {
+            Some(Node::Element(root)) => {
+                let mut prefixes: Vec<(&str, String)> = Vec::new();
+                self.collect_namespaces(root, &mut prefixes);
+                let declarations = sorted_declarations(&prefixes);
+                self.write_canonical_lines(&mut output, root, 0, &prefixes, &declarations);
+            }
+            Some(Node::Cdata(c)) => {
+                let text = c.get_data().trim();
+                if !text.is_empty() {
+                    output.push_str(&escape_canonical(text, true));
+                    output.push('\n');
+                }
+            }
+            None => (),
+        }
+        output
+    }
+
+    fn write_canonical_lines(
+        &self,
+        output: &mut String,
+        e: &Element,
+        depth: usize,
+        prefixes: &[(&str, String)],
+        declarations: &[String],
+    ) {
+        let indent = "  ".repeat(depth);
+        let tag = qualified_canonical(
+            prefixes,
+            self.namespace_of(&e.namespace, e.get_tag()),
+            &e.name,
+        );
+        output.push_str(&indent);
+        output.push('<');
+        output.push_str(&tag);
+        let attributes =
+            self.canonical_attributes(e)
+                .into_iter()
+                .map(|(namespace, name, value)| {
+                    format!(
+                        "{}=\"{}\"",
+                        qualified_canonical(prefixes, namespace, name),
+                        escape_canonical(value, true)
+                    )
+                });
+        for attribute in declarations.iter().cloned().chain(attributes) {
+            let _ = write!(output, "\n{}    {}", indent, attribute);
+        }
+
+        let children: Vec<_> = significant_children(e).collect();
+        if children.is_empty() {
+            output.push_str("/>\n");
+            return;
+        }
+        output.push_str(">\n");
+        for child in children {
+            match child {
+                Node::Element(child) => {
+                    self.write_canonical_lines(output, child, depth + 1, prefixes, &[])
+                }
+                Node::Cdata(c) => {
+                    let _ = writeln!(
+                        output,
+                        "{}  {}",
+                        indent,
+                        escape_canonical(c.get_data().trim(), true)
+                    );
+                }
+            }
+        }
+        let _ = writeln!(output, "{}</{}>", indent, tag);
+    }
+
+    fn collect_namespaces<'a>(&'a self, e: &'a Element, prefixes: &mut Vec<(&'a str, String)>) {
+        let names = std::iter::once((&e.namespace, e.get_tag())).chain(
+            e.typed_attributes
+                .iter()
+                .map(|a| (&a.namespace, a.qualified_name.as_str())),
+        );
+        for (namespace, name) in names {
+            let uri = self.namespace_of(namespace, name);
+            if uri.is_empty() || prefixes.iter().any(|(u, _)| *u == uri) {
+                continue;
+            }
+            let prefix = match WELL_KNOWN_PREFIXES.iter().find(|(u, _)| *u == uri) {
+                Some((_, prefix)) => prefix.to_string(),
+                None => {
+                    let others = prefixes
+                        .iter()
+                        .filter(|(u, _)| !WELL_KNOWN_PREFIXES.iter().any(|(w, _)| w == u))
+                        .count();
+                    format!("ns{}", others)
+                }
+            };
+            prefixes.push((uri, prefix));
+        }
+        for child in e.get_children() {
+            if let Node::Element(child) = child {
+                self.collect_namespaces(child, prefixes);
+            }
+        }
+    }
+
+    fn write_canonical(
+        &self,
+        output: &mut String,
+        e: &Element,
+        prefixes: &[(&str, String)],
+        declarations: &str,
+    ) {
+        let qualified =
+            |namespace: &str, name: &str| qualified_canonical(prefixes, namespace, name);
+
+        let tag = qualified(self.namespace_of(&e.namespace, e.get_tag()), &e.name);
+        output.push('<');
+        output.push_str(&tag);
+        output.push_str(declarations);
+        for (namespace, name, value) in self.canonical_attributes(e) {
+            let _ = write!(
+                output,
+                " {}=\"{}\"",
+                qualified(namespace, name),
+                escape_canonical(value, true)
+            );
+        }
+        output.push('>');
+        for child in significant_children(e) {
+            match child {
+                Node::Element(child) => self.write_canonical(output, child, prefixes, ""),
+                Node::Cdata(c) => output.push_str(&escape_canonical(c.get_data().trim(), false)),
+            }
+        }
+        let _ = write!(output, "</{}>", tag);
+    }
+
+    ///Returns whether the document describes the same content as `other`,
+    ///by the same rules as [XmlDocument::canonical_hash]: namespaces are
+    ///compared by URI rather than prefix, attribute order, line numbers and
+    ///comments are ignored, and so is whitespace-only text.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let document = |attributes: &[(&str, &str)]| {
+    ///    let mut root = Element::new("manifest");
+    ///    for (name, value) in attributes {
+    ///        root.set_attribute(name, value);
+    ///    }
+    ///    XmlDocument::new(Node::Element(root))
+    ///};
+    ///let a = document(&[("package", "com.example"), ("android:versionCode", "1")]);
+    ///let b = document(&[("android:versionCode", "1"), ("package", "com.example")]);
+    ///assert!(a.semantically_equals(&b));
+    ///```
+    pub fn semantically_equals(&self, other: &XmlDocument) -> bool {
+        match (self.get_root(), other.get_root()) {
+            (Some(a), Some(b)) => self.nodes_equal(a, other, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn nodes_equal(&self, a: &Node, other: &XmlDocument, b: &Node) -> bool {
+        match (a, b) {
+            (Node::Element(a), Node::Element(b)) => {
+                let a_children: Vec<_> = significant_children(a).collect();
+                let b_children: Vec<_> = significant_children(b).collect();
+
+                a.name == b.name
+                    && self.namespace_of(&a.namespace, a.get_tag())
+                        == other.namespace_of(&b.namespace, b.get_tag())
+                    && self.canonical_attributes(a) == other.canonical_attributes(b)
+                    && a_children.len() == b_children.len()
+                    && a_children
+                        .iter()
+                        .zip(&b_children)
+                        .all(|(a, b)| self.nodes_equal(a, other, b))
+            }
+            (Node::Cdata(a), Node::Cdata(b)) => a.get_data().trim() == b.get_data().trim(),
+            _ => false,
+        }
+    }
+
+    ///Returns the attributes of `e` as sorted `(namespace, name, value)`
+    ///triples.
+    fn canonical_attributes<'a>(&'a self, e: &'a Element) -> Vec<(&'a str, &'a str, &'a str)> {
+        let mut attributes: Vec<_> = e
+            .typed_attributes
+            .iter()
+            .map(|attr| {
+                (
+                    self.namespace_of(&attr.namespace, &attr.qualified_name),
+                    attr.name.as_str(),
+                    e.get_attribute(&attr.qualified_name).unwrap_or(&attr.value),
+                )
+            })
+            .collect();
+        attributes.sort_unstable();
+        attributes.dedup();
+        attributes
+    }
+
+    ///Returns the namespace URI of a name, or an empty string if it has
+    ///none.
+    pub(crate) fn namespace_of<'a>(
+        &'a self,
+        namespace: &'a Option<String>,
+        qualified_name: &str,
+    ) -> &'a str {
+        if let Some(namespace) = namespace {
+            return namespace;
+        }
+
+        split_prefix(qualified_name)
+            .0
+            .and_then(|prefix| self.namespaces.iter().find(|(p, _)| p == prefix))
+            .map_or("", |(_, uri)| uri.as_str())
+    }
+}
+
+///Returns the `xmlns` attributes declaring the canonical `prefixes`, given
+///as `(uri, prefix)` pairs, sorted by prefix.
+fn sorted_declarations(prefixes: &[(&str, String)]) -> Vec<String> {
+    let mut declarations: Vec<_> = prefixes.iter().map(|(uri, p)| (p, *uri)).collect();
+    declarations.sort_unstable();
+    declarations
+        .into_iter()
+        .map(|(prefix, uri)| format!("xmlns:{}=\"{}\"", prefix, escape_canonical(uri, true)))
+        .collect()
+}
+
+///Returns `name` with the canonical prefix of `namespace`, if it has one.
+fn qualified_canonical(prefixes: &[(&str, String)], namespace: &str, name: &str) -> String {
+    match prefixes.iter().find(|(uri, _)| *uri == namespace) {
+        Some((_, prefix)) => format!("{}:{}", prefix, name),
+        None => name.to_string(),
+    }
+}
+
+///Escapes text or an attribute value for canonical XML.
+fn escape_canonical(s: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' if !attribute => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\t' | '\n' if !attribute => escaped.push(c),
+            c if c.is_control() => {
+                let _ = write!(escaped, "&#x{:X};", u32::from(c));
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+///Returns the children of `e` except whitespace-only text.
+fn significant_children(e: &Element) -> impl Iterator<Item = &Node> {
+    e.get_children()
+        .iter()
+        .filter(|c| !matches!(c, Node::Cdata(c) if c.get_data().trim().is_empty()))
+}
+
+#[test]
+fn test_canonical_hash() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut std::io::Cursor::new(&data)).unwrap();
+
+    // Re-encoding reorders attributes and renumbers lines.
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    crate::encode(&xml, &mut encoded).unwrap();
+    encoded.set_position(0);
+    let mut reencoded = crate::parse(&mut encoded).unwrap();
+    assert_eq!(xml.canonical_hash(), reencoded.canonical_hash());
+
+    match reencoded.get_root_mut() {
+        Some(Node::Element(root)) => root.set_attribute("android:versionCode", "2"),
+        _ => panic!("missing root element"),
+    }
+    assert_ne!(xml.canonical_hash(), reencoded.canonical_hash());
+}
+
+#[test]
+fn test_semantically_equals() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut std::io::Cursor::new(&data)).unwrap();
+
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    crate::encode(&xml, &mut encoded).unwrap();
+    encoded.set_position(0);
+    let mut reencoded = crate::parse(&mut encoded).unwrap();
+    assert!(xml.semantically_equals(&reencoded));
+
+    // The same attribute under another prefix.
+    let prefixed = |prefix: &str| {
+        let mut root = Element::new("manifest");
+        root.set_attribute(&format!("{}:versionCode", prefix), "1");
+        let mut xml = XmlDocument::new(Node::Element(root));
+        xml.declare_namespace(prefix, crate::xml::ANDROID_NAMESPACE);
+        xml
+    };
+    assert!(prefixed("android").semantically_equals(&prefixed("a")));
+
+    match reencoded.get_root_mut() {
+        Some(Node::Element(root)) => root.get_children_mut().pop(),
+        _ => panic!("missing root element"),
+    };
+    assert!(!xml.semantically_equals(&reencoded));
+}
+
+#[test]
+fn test_to_canonical_xml() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut std::io::Cursor::new(&data)).unwrap();
+    let canonical = xml.to_canonical_xml();
+    assert!(canonical.starts_with(
+        "<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\" package="
+    ));
+
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    crate::encode(&xml, &mut encoded).unwrap();
+    encoded.set_position(0);
+    let reencoded = crate::parse(&mut encoded).unwrap();
+    assert_eq!(reencoded.to_canonical_xml(), canonical);
+
+    let mut child = Element::new("x:child");
+    child.set_attribute("x:b", "tab\there");
+    child.set_attribute("a", "<\"&");
+    child
+        .get_children_mut()
+        .push(Node::Cdata(crate::Cdata::new("  a < b\r ")));
+    let mut root = Element::new("root");
+    root.get_children_mut()
+        .push(Node::Cdata(crate::Cdata::new("\n  ")));
+    root.get_children_mut().push(Node::Element(child));
+    let mut xml = XmlDocument::new(Node::Element(root));
+    xml.declare_namespace("x", "urn:example");
+    assert_eq!(
+        xml.to_canonical_xml(),
+        "<root xmlns:ns0=\"urn:example\"><ns0:child a=\"&lt;&quot;&amp;\" \
+         ns0:b=\"tab&#x9;here\">a &lt; b</ns0:child></root>"
+    );
+}
+
+#[test]
+fn test_canonical_string() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut std::io::Cursor::new(&data)).unwrap();
+    let canonical = xml.canonical_string();
+    assert!(
+        canonical.starts_with("<!-- axmldecoder canonical v1 -->\n<manifest\n    xmlns:android=")
+    );
+    assert!(canonical.ends_with("</manifest>\n"));
+
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    crate::encode(&xml, &mut encoded).unwrap();
+    encoded.set_position(0);
+    let reencoded = crate::parse(&mut encoded).unwrap();
+    assert_eq!(reencoded.canonical_string(), canonical);
+
+    let mut child = Element::new("x:child");
+    child.set_attribute("x:b", "tab\there");
+    child
+        .get_children_mut()
+        .push(Node::Cdata(crate::Cdata::new("  a < b\nc ")));
+    let mut root = Element::new("root");
+    root.get_children_mut().push(Node::Element(child));
+    let mut xml = XmlDocument::new(Node::Element(root));
+    xml.declare_namespace("x", "urn:example");
+    assert_eq!(
+        xml.canonical_string(),
+        "<!-- axmldecoder canonical v1 -->
+<root
+    xmlns:ns0=\"urn:example\">
+  <ns0:child
+      ns0:b=\"tab&#x9;here\">
+    a &lt; b&#xA;c
+  </ns0:child>
+</root>
+"
+    );
+}
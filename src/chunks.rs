@@ -0,0 +1,105 @@
+use std::convert::TryInto;
+use std::io::Read;
+
+use crate::binaryxml::{ResourceType, CHUNK_HEADER_SIZE};
+use crate::ParseError;
+
+///A chunk as it appears in the input, without any interpretation beyond
+///its header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct RawChunk {
+    ///The chunk type, such as `0x0102` for a start element.
+    pub typ: u16,
+    ///Offset of the chunk from the start of the input.
+    pub offset: u64,
+    ///The header size declared by the chunk.
+    pub header_size: u16,
+    ///The total size declared by the chunk.
+    pub size: u32,
+    ///The raw header, including the type and sizes.
+    pub header: Vec<u8>,
+    ///The raw bytes following the header.
+    pub bytes: Vec<u8>,
+}
+
+///Lists every chunk in a binary XML file in the order they appear.
+///
+///Nothing past the chunk headers is interpreted, so this also lists chunks
+///that [parse](crate::parse) would reject or skip, as well as anything
+///appended after the end of the document. The top-level XML chunk is listed
+///with its header and no bytes, followed by the chunks it contains. Chunks
+///declaring more bytes than are left in the input are cut short.
+///
+///```rust
+///use axmldecoder::read_chunks;
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///for chunk in read_chunks(&mut f)? {
+///    println!("{:#06x} at {} ({} bytes)", chunk.typ, chunk.offset, chunk.size);
+///}
+///# Ok::<(), ParseError>(())
+///```
+pub fn read_chunks<F: Read>(input: &mut F) -> Result<Vec<RawChunk>, ParseError> {
+    let mut data = Vec::new();
+    input.read_to_end(&mut data).map_err(ParseError::IoError)?;
+
+    let mut chunks = Vec::new();
+    let mut offset = 0;
+    while data.len() - offset >= usize::from(CHUNK_HEADER_SIZE) {
+        let typ = u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap());
+        let header_size = u16::from_le_bytes(data[offset + 2..offset + 4].try_into().unwrap());
+        let size = u32::from_le_bytes(data[offset + 4..offset + 8].try_into().unwrap());
+
+        // A chunk smaller than its own header would never advance.
+        if size < u32::from(CHUNK_HEADER_SIZE) {
+            return Err(ParseError::InvalidChunkHeaderSize {
+                offset: offset as u64,
+                header_size,
+                size,
+            });
+        }
+
+        let end = data.len().min(offset.saturating_add(size as usize));
+        let header_end = end.min(offset + usize::from(header_size.max(CHUNK_HEADER_SIZE)));
+        // The children of the document chunk follow its header.
+        let body_end = if typ == u16::from(ResourceType::Xml) {
+            header_end
+        } else {
+            end
+        };
+
+        chunks.push(RawChunk {
+            typ,
+            offset: offset as u64,
+            header_size,
+            size,
+            header: data[offset..header_end].to_vec(),
+            bytes: data[header_end..body_end].to_vec(),
+        });
+        offset = body_end;
+    }
+
+    Ok(chunks)
+}
+
+#[test]
+fn test_read_chunks() {
+    let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    data.extend_from_slice(&[0xef, 0xbe, 0x08, 0x00, 12, 0, 0, 0, 1, 2, 3, 4]);
+
+    let chunks = read_chunks(&mut std::io::Cursor::new(&data)).unwrap();
+    assert_eq!(chunks[0].typ, 0x0003);
+    assert!(chunks[0].bytes.is_empty());
+    assert_eq!(chunks[1].typ, 0x0001);
+    assert_eq!(chunks[1].offset, 8);
+
+    let junk = chunks.last().unwrap();
+    assert_eq!(junk.typ, 0xbeef);
+    assert_eq!(junk.offset as usize, data.len() - 12);
+    assert_eq!(junk.bytes, [1, 2, 3, 4]);
+
+    let total: usize = chunks.iter().map(|c| c.header.len() + c.bytes.len()).sum();
+    assert_eq!(total, data.len());
+}
@@ -0,0 +1,266 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::path::child_paths;
+use crate::xml::Attribute;
+use crate::{Element, Node, XmlDocument};
+
+///Attributes Android ignores in apps targeting the given API level or
+///higher, as `(tag, attribute, api)`.
+const IGNORED_ATTRIBUTES: &[(&str, &str, u32)] =
+    &[("application", "requestLegacyExternalStorage", 30)];
+
+///Elements Android only reads in apps targeting the given API level or
+///higher, as `(tag, api)`.
+const MINIMUM_SDK_ELEMENTS: &[(&str, u32)] = &[("queries", 30)];
+
+///Permissions that grant nothing to apps targeting the given API level or
+///higher, as `(permission, api, replacements)`.
+const OBSOLETE_PERMISSIONS: &[(&str, u32, &[&str])] = &[
+    ("android.permission.GET_TASKS", 21, &[]),
+    ("android.permission.WRITE_EXTERNAL_STORAGE", 30, &[]),
+    (
+        "android.permission.BLUETOOTH",
+        31,
+        &["android.permission.BLUETOOTH_CONNECT"],
+    ),
+    (
+        "android.permission.BLUETOOTH_ADMIN",
+        31,
+        &[
+            "android.permission.BLUETOOTH_SCAN",
+            "android.permission.BLUETOOTH_ADVERTISE",
+            "android.permission.BLUETOOTH_CONNECT",
+        ],
+    ),
+    (
+        "android.permission.READ_EXTERNAL_STORAGE",
+        33,
+        &[
+            "android.permission.READ_MEDIA_IMAGES",
+            "android.permission.READ_MEDIA_VIDEO",
+            "android.permission.READ_MEDIA_AUDIO",
+        ],
+    ),
+];
+
+///An entry found by [XmlDocument::sdk_compatibility] to have no effect at
+///the target API level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatFinding {
+    pub kind: CompatKind,
+    ///Path of the element, such as `manifest/uses-permission[2]`.
+    pub path: String,
+    ///Line number of the element, or 0 if unknown.
+    pub line_number: u32,
+}
+
+impl fmt::Display for CompatFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}): {}",
+            self.path, self.line_number, self.kind
+        )
+    }
+}
+
+///The kinds of entries reported by [XmlDocument::sdk_compatibility].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CompatKind {
+    #[error("{attribute} is ignored when targeting API {since} or higher")]
+    IgnoredAttribute { attribute: String, since: u32 },
+
+    #[error("{tag} is only read when targeting API {since} or higher")]
+    UnsupportedElement { tag: String, since: u32 },
+
+    #[error("{name} grants nothing when targeting API {since} or higher{}", replaced_by(.replacements))]
+    ObsoletePermission {
+        name: String,
+        since: u32,
+        ///The permissions that took over what `name` granted, if any.
+        replacements: Vec<String>,
+    },
+}
+
+fn replaced_by(replacements: &[String]) -> String {
+    if replacements.is_empty() {
+        String::new()
+    } else {
+        format!(", request {} instead", replacements.join(", "))
+    }
+}
+
+impl XmlDocument {
+    ///Reports the entries of a manifest that have no effect in an app
+    ///targeting API level `target_sdk`:
+    ///
+    /// - attributes Android stopped reading, such as
+    ///   `android:requestLegacyExternalStorage` from API 30,
+    /// - elements added in a later release, such as `queries` before API 30,
+    /// - permissions that were removed or split into others, such as
+    ///   `READ_EXTERNAL_STORAGE` from API 33. Permissions with an
+    ///   `android:maxSdkVersion` below the API level they stopped working
+    ///   at are only requested on older releases, and are not reported.
+    ///
+    ///Documents whose root is not a `manifest` element produce no findings.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let target = xml.manifest().and_then(|m| m.target_sdk_version()).unwrap_or(1);
+    ///for finding in xml.sdk_compatibility(target) {
+    ///    println!("{}", finding);
+    ///}
+    ///```
+    pub fn sdk_compatibility(&self, target_sdk: u32) -> Vec<CompatFinding> {
+        let mut findings = Vec::new();
+        if let Some(Node::Element(root)) = self.get_root() {
+            if root.get_tag() == "manifest" {
+                check_element(root, "manifest", target_sdk, &mut findings);
+            }
+        }
+        findings
+    }
+}
+
+fn check_element(
+    element: &Element,
+    path: &str,
+    target_sdk: u32,
+    findings: &mut Vec<CompatFinding>,
+) {
+    let mut report = |kind| {
+        findings.push(CompatFinding {
+            kind,
+            path: path.to_string(),
+            line_number: element.line_number,
+        })
+    };
+
+    let tag = element.get_tag();
+    for &(_, name, since) in IGNORED_ATTRIBUTES.iter().filter(|(t, ..)| *t == tag) {
+        if target_sdk >= since {
+            if let Some(attr) = element.android_attribute(name) {
+                report(CompatKind::IgnoredAttribute {
+                    attribute: attr.qualified_name.clone(),
+                    since,
+                });
+            }
+        }
+    }
+
+    if let Some(&(_, since)) = MINIMUM_SDK_ELEMENTS.iter().find(|(t, _)| *t == tag) {
+        if target_sdk < since {
+            report(CompatKind::UnsupportedElement {
+                tag: tag.to_string(),
+                since,
+            });
+            // Its children are ignored along with it.
+            return;
+        }
+    }
+
+    if tag == "uses-permission" || tag == "uses-permission-sdk-23" {
+        let name = element.android_value("name").unwrap_or_default();
+        let max_sdk = element
+            .android_attribute("maxSdkVersion")
+            .and_then(Attribute::integer_value);
+        if let Some(&(_, since, replacements)) = OBSOLETE_PERMISSIONS
+            .iter()
+            .find(|(permission, ..)| *permission == name)
+        {
+            if target_sdk >= since && max_sdk.map_or(true, |max| max >= since) {
+                report(CompatKind::ObsoletePermission {
+                    name: name.to_string(),
+                    since,
+                    replacements: replacements.iter().map(|r| r.to_string()).collect(),
+                });
+            }
+        }
+    }
+
+    for (child_path, child) in child_paths(element, path) {
+        check_element(child, &child_path, target_sdk, findings);
+    }
+}
+
+#[test]
+fn test_sdk_compatibility() {
+    use crate::test_support::{element, manifest_document};
+
+    let xml = manifest_document(vec![
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.READ_EXTERNAL_STORAGE")],
+            vec![],
+        ),
+        element(
+            "uses-permission",
+            &[
+                ("android:name", "android.permission.BLUETOOTH"),
+                ("android:maxSdkVersion", "30"),
+            ],
+            vec![],
+        ),
+        element(
+            "queries",
+            &[],
+            vec![element(
+                "package",
+                &[("android:name", "com.example.other")],
+                vec![],
+            )],
+        ),
+        element(
+            "application",
+            &[("android:requestLegacyExternalStorage", "true")],
+            vec![],
+        ),
+    ]);
+
+    let findings = |target| -> Vec<_> {
+        xml.sdk_compatibility(target)
+            .into_iter()
+            .map(|f| (f.kind, f.path))
+            .collect()
+    };
+    assert_eq!(
+        findings(29),
+        [(
+            CompatKind::UnsupportedElement {
+                tag: "queries".to_string(),
+                since: 30
+            },
+            "manifest/queries".to_string()
+        )]
+    );
+    assert_eq!(findings(30).len(), 1);
+    assert_eq!(
+        findings(34),
+        [
+            (
+                CompatKind::ObsoletePermission {
+                    name: "android.permission.READ_EXTERNAL_STORAGE".to_string(),
+                    since: 33,
+                    replacements: vec![
+                        "android.permission.READ_MEDIA_IMAGES".to_string(),
+                        "android.permission.READ_MEDIA_VIDEO".to_string(),
+                        "android.permission.READ_MEDIA_AUDIO".to_string(),
+                    ]
+                },
+                "manifest/uses-permission[1]".to_string()
+            ),
+            (
+                CompatKind::IgnoredAttribute {
+                    attribute: "android:requestLegacyExternalStorage".to_string(),
+                    since: 30
+                },
+                "manifest/application".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,187 @@
+///Options controlling how the parser reacts to malformed input.
+///
+///The [Default] configuration is strict: anything unexpected aborts parsing
+///with a [ParseError](crate::ParseError). Validators will usually want to
+///keep it that way, while recovery tools can start from
+///[ParseConfig::hardened] and tighten individual behaviors as needed.
+///
+///Neither configuration limits the size of a document. Input that may have
+///been crafted to exhaust memory should be read with limits set, each of
+///which fails parsing with
+///[ParseError::LimitExceeded](crate::ParseError::LimitExceeded) as soon as
+///it is exceeded.
+///
+///```rust
+///use axmldecoder::{parse_with_config, ParseConfig};
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let config = ParseConfig {
+///    max_depth: Some(64),
+///    max_elements: Some(100_000),
+///    max_attributes: Some(256),
+///    max_string_pool_size: Some(16 << 20),
+///    ..ParseConfig::hardened()
+///};
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let (xml, warnings) = parse_with_config(&mut f, &config)?;
+///# Ok::<(), ParseError>(())
+///```
+#[derive(Debug, Clone)]
+pub struct ParseConfig {
+    ///Skip chunks of unknown or unexpected types instead of failing. End
+    ///markers and resource table chunks are skipped either way.
+    pub skip_unknown_chunks: bool,
+
+    ///Decode invalid strings lossily instead of failing.
+    pub substitute_invalid_strings: bool,
+
+    ///Fail on end elements that do not match the innermost open element and
+    ///on text outside of any element. Otherwise an end element for an outer
+    ///element also closes the ones left open inside it, any other end
+    ///element closes the innermost open element and stray chunks are
+    ///ignored, with a warning.
+    pub enforce_tag_matching: bool,
+
+    ///Drop namespaces, attributes and text whose strings cannot be resolved
+    ///instead of failing.
+    pub drop_unresolved: bool,
+
+    ///Tolerate known AXML obfuscation tricks the way the Android runtime
+    ///does: distrust chunk header sizes, clamp inflated attribute counts,
+    ///keep the first prefix declared for a namespace, name framework
+    ///attributes by their resource id and skip string pools that cannot be
+    ///read in favor of a later one.
+    pub tolerate_obfuscation: bool,
+
+    ///Maximum nesting depth of elements, the root being at depth 1.
+    pub max_depth: Option<usize>,
+
+    ///Maximum number of elements in the document.
+    pub max_elements: Option<usize>,
+
+    ///Maximum number of attributes of a single element.
+    pub max_attributes: Option<usize>,
+
+    ///Maximum size in bytes of the string pool chunk.
+    pub max_string_pool_size: Option<usize>,
+
+    ///How floats, dimensions and fractions are written in attribute values
+    ///and text.
+    pub float_format: FloatFormat,
+
+    ///Join text split over consecutive Cdata chunks, as `aapt` does with
+    ///long text, into a single text node. Text stored as a typed value is
+    ///always kept apart.
+    pub merge_cdata: bool,
+
+    ///Which value [Element::get_attribute](crate::Element::get_attribute)
+    ///keeps for an attribute an element has more than once. Every occurrence
+    ///stays available through
+    ///[Element::typed_attributes](crate::Element::typed_attributes).
+    pub duplicate_attributes: DuplicateAttributes,
+}
+
+///How floating point values are formatted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    ///The shortest form that reads back as the same value, always with a
+    ///decimal point, such as `16.0dip` or `0.1`.
+    #[default]
+    Decimal,
+    ///The `%g` form `aapt` prints: six significant digits without trailing
+    ///zeros, switching to scientific notation for exponents below -4 or
+    ///above 5, such as `16dip` or `1.23457e+06`. Useful for diffing against
+    ///the output of Android's tools, but it may lose precision.
+    Aapt,
+}
+
+///Which occurrence of a repeated attribute wins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateAttributes {
+    ///The first occurrence, which is the one Android uses.
+    #[default]
+    First,
+    ///The last occurrence, which is what many other tools show.
+    Last,
+    ///Fails with
+    ///[ParseError::DuplicateAttribute](crate::ParseError::DuplicateAttribute).
+    Error,
+}
+
+impl ParseConfig {
+    ///The strict configuration, identical to [ParseConfig::default].
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    ///The configuration used by [parse_hardened](crate::parse_hardened),
+    ///tolerating everything the Android runtime tolerates.
+    pub fn hardened() -> Self {
+        Self {
+            skip_unknown_chunks: true,
+            substitute_invalid_strings: true,
+            enforce_tag_matching: false,
+            drop_unresolved: true,
+            tolerate_obfuscation: true,
+            max_depth: None,
+            max_elements: None,
+            max_attributes: None,
+            max_string_pool_size: None,
+            float_format: FloatFormat::Decimal,
+            merge_cdata: true,
+            duplicate_attributes: DuplicateAttributes::First,
+        }
+    }
+}
+
+///Options controlling how documents are encoded.
+///
+///The [Default] configuration writes what `aapt2` writes for a manifest.
+///
+///```rust
+///use axmldecoder::{encode_with_config, parse, EncodeConfig};
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///let config = EncodeConfig {
+///    utf8_strings: true,
+///    ..EncodeConfig::default()
+///};
+///let mut output = std::io::Cursor::new(Vec::new());
+///encode_with_config(&xml, &mut output, &config).unwrap();
+///```
+#[derive(Debug, Clone, Default)]
+pub struct EncodeConfig {
+    ///Encode the string pool as UTF-8 rather than UTF-16. UTF-8 pools are
+    ///smaller for mostly ASCII documents, but only readable on Android 2.2
+    ///and later. Documents with a string longer than 32767 bytes are
+    ///written as UTF-16 anyway, as UTF-8 pools cannot store its length.
+    pub utf8_strings: bool,
+
+    ///Parse the encoded document again and compare it with the original
+    ///before writing it, failing with
+    ///[EncodeError::Verification](crate::EncodeError::Verification) at the
+    ///first difference. This catches documents the encoder cannot represent,
+    ///such as attributes with an undeclared prefix, before they end up in
+    ///an APK.
+    pub verify: bool,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self {
+            skip_unknown_chunks: false,
+            substitute_invalid_strings: false,
+            enforce_tag_matching: true,
+            drop_unresolved: false,
+            tolerate_obfuscation: false,
+            max_depth: None,
+            max_elements: None,
+            max_attributes: None,
+            max_string_pool_size: None,
+            float_format: FloatFormat::Decimal,
+            merge_cdata: true,
+            duplicate_attributes: DuplicateAttributes::First,
+        }
+    }
+}
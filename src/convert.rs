@@ -0,0 +1,66 @@
+use std::convert::TryFrom;
+use std::io::Cursor;
+
+use crate::encoder::Encoder;
+use crate::{
+    parse, BinaryXmlDocument, EncodeConfig, EncodeError, ParseConfig, ParseError, XmlDocument,
+};
+
+///Parses a binary XML document with the default [ParseConfig], like
+///[parse](crate::parse).
+///
+///```rust
+///use axmldecoder::XmlDocument;
+///use std::convert::TryFrom;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let data = std::fs::read(manifest_file).unwrap();
+///let xml = XmlDocument::try_from(&data[..]).unwrap();
+///```
+impl TryFrom<&[u8]> for XmlDocument {
+    type Error = ParseError;
+
+    fn try_from(data: &[u8]) -> Result<Self, Self::Error> {
+        parse(&mut Cursor::new(data))
+    }
+}
+
+///Builds the element tree of a binary document with the default
+///[ParseConfig]. Warnings are discarded.
+impl TryFrom<BinaryXmlDocument> for XmlDocument {
+    type Error = ParseError;
+
+    fn try_from(document: BinaryXmlDocument) -> Result<Self, Self::Error> {
+        let (xml, _) = XmlDocument::from_binary(
+            document.elements,
+            document.string_pool,
+            document.resource_map,
+            &ParseConfig::default(),
+        )?;
+        Ok(xml)
+    }
+}
+
+///Encodes a document with the default [EncodeConfig], like
+///[encode](crate::encode).
+impl TryFrom<&XmlDocument> for BinaryXmlDocument {
+    type Error = EncodeError;
+
+    fn try_from(document: &XmlDocument) -> Result<Self, Self::Error> {
+        Encoder::encode(document, &EncodeConfig::default())
+    }
+}
+
+#[test]
+fn test_conversions() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = XmlDocument::try_from(&data[..]).unwrap();
+
+    let binaryxml = BinaryXmlDocument::try_from(&xml).unwrap();
+    let converted = XmlDocument::try_from(binaryxml).unwrap();
+    assert!(converted.semantically_equals(&xml));
+
+    assert!(matches!(
+        XmlDocument::try_from(&b"not a document"[..]),
+        Err(ParseError::BadMagic { found: 0x6f6e })
+    ));
+}
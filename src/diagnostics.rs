@@ -0,0 +1,73 @@
+use thiserror::Error;
+
+use crate::ParseError;
+
+///A non-fatal problem that was tolerated while parsing a document.
+///
+///Warnings are mostly produced by the tolerant parsing modes, where they
+///signal that the returned document is a best-effort interpretation of the
+///input. Even strict parsing skips a few things that Android ignores, such
+///as resource table chunks and additional string pools.
+#[derive(Error, Debug)]
+pub enum Warning {
+    #[error("skipped chunk of type {typ:#06x} at offset {offset}")]
+    SkippedChunk { offset: u64, typ: u16 },
+
+    #[error("skipped additional StringPool chunk at offset {offset}")]
+    DuplicateStringPool { offset: u64 },
+
+    #[error("dropped StringPool chunk at offset {offset}: {cause}")]
+    DroppedStringPool { offset: u64, cause: ParseError },
+
+    #[error("element at offset {offset} declares {declared} attributes but only {actual} fit")]
+    ClampedAttributeCount {
+        offset: u64,
+        declared: u16,
+        actual: u16,
+    },
+
+    #[error("StringPool entry {0} could not be decoded and was substituted")]
+    SubstitutedString(u32),
+
+    #[error("ignored end element without a matching start element")]
+    UnmatchedEndElement,
+
+    #[error("end element {found} on line {line} did not match open element {expected}")]
+    MismatchedEndElement {
+        expected: String,
+        found: String,
+        line: u32,
+    },
+
+    #[error("ignored Cdata outside of any element")]
+    CdataOutsideElement,
+
+    #[error("element {tag} follows the root element")]
+    StrayRoot { tag: String },
+
+    #[error("closed element {tag} that has no end element")]
+    UnclosedElement { tag: String },
+
+    #[error("dropped namespace declaration: {0}")]
+    DroppedNamespace(ParseError),
+
+    #[error("ignored repeated declaration of namespace {uri} with prefix {prefix}")]
+    DuplicateNamespace { uri: String, prefix: String },
+
+    #[error("dropped attribute of element {element}: {cause}")]
+    DroppedAttribute { element: String, cause: ParseError },
+
+    #[error("element {element} has attribute {attribute} more than once")]
+    DuplicateAttribute { element: String, attribute: String },
+
+    #[error("dropped Cdata: {0}")]
+    DroppedCdata(ParseError),
+}
+
+///Records a tolerated problem, also reporting it as a `tracing` event when
+///the `tracing` feature is enabled.
+pub(crate) fn push_warning(warnings: &mut Vec<Warning>, warning: Warning) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(%warning, "recovered from malformed input");
+    warnings.push(warning);
+}
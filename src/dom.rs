@@ -0,0 +1,91 @@
+//!Conversion into [minidom] trees, available with the `minidom` feature.
+
+use crate::xml::{Element, Node, XmlDocument};
+
+impl XmlDocument {
+    ///Converts the document into a [minidom::Element], or `None` if the
+    ///document has no root element. Namespace declarations are placed on the
+    ///root element as `xmlns` attributes, so prefixed attribute names such as
+    ///`android:name` can be looked up as they are written.
+    ///
+    ///```rust
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = axmldecoder::parse(&mut f).unwrap();
+    ///let manifest = xml.to_minidom().unwrap();
+    ///assert_eq!(manifest.name(), "manifest");
+    ///assert!(manifest.attr("package").is_some());
+    ///```
+    pub fn to_minidom(&self) -> Option<minidom::Element> {
+        let root = match self.get_root() {
+            Some(Node::Element(e)) => e,
+            _ => return None,
+        };
+
+        let mut element = root.to_minidom();
+        set_namespace_declarations(&mut element, &self.namespaces);
+        Some(element)
+    }
+}
+
+impl Element {
+    ///Converts the element and its descendants into a [minidom::Element].
+    pub fn to_minidom(&self) -> minidom::Element {
+        let tag = self.get_tag();
+        // minidom panics on names with more than one colon, which obfuscated
+        // documents are free to use, so those are kept unprefixed.
+        let mut element = if tag.matches(':').count() > 1 {
+            minidom::Element::bare(tag)
+        } else {
+            let builder = minidom::Element::builder(tag);
+            match &self.namespace {
+                Some(namespace) => builder.ns(namespace.as_str()).build(),
+                None => builder.build(),
+            }
+        };
+
+        set_namespace_declarations(&mut element, &self.namespace_declarations);
+        for (name, value) in self.attributes() {
+            element.set_attr(name, value);
+        }
+
+        for child in self.get_children() {
+            match child {
+                Node::Element(e) => {
+                    element.append_child(e.to_minidom());
+                }
+                Node::Cdata(c) => element.append_text_node(c.get_data()),
+            }
+        }
+
+        element
+    }
+}
+
+fn set_namespace_declarations(element: &mut minidom::Element, namespaces: &[(String, String)]) {
+    for (prefix, uri) in namespaces {
+        let name = if prefix.is_empty() {
+            "xmlns".to_string()
+        } else {
+            format!("xmlns:{}", prefix)
+        };
+        element.set_attr(name, uri.as_str());
+    }
+}
+
+#[test]
+fn test_to_minidom() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    let manifest = xml.to_minidom().unwrap();
+
+    assert_eq!(
+        manifest.attr("xmlns:android"),
+        Some("http://schemas.android.com/apk/res/android")
+    );
+    let application = manifest
+        .children()
+        .find(|e| e.name() == "application")
+        .unwrap();
+    assert!(application.attr("android:label").is_some());
+}
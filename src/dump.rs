@@ -0,0 +1,126 @@
+use std::fmt::Write;
+
+use crate::resource_value::ResourceValueType;
+use crate::xml::{Element, Node, XmlDocument};
+
+impl XmlDocument {
+    ///Renders the document in the format of `aapt2 dump xmltree`, with one
+    ///`N:`, `E:`, `A:` or `T:` line per namespace, element, attribute or
+    ///text node.
+    ///
+    ///Attributes are shown with their namespace URI, their resource id and
+    ///their typed value, followed by the raw string if one was stored.
+    ///Comparing the output with that of `aapt2` is a quick way to find where
+    ///this crate interprets a file differently.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///print!("{}", xml.dump_tree());
+    ///```
+    pub fn dump_tree(&self) -> String {
+        let mut output = String::new();
+        if let Some(root) = self.get_root() {
+            // Declarations made before the root are shown on the root.
+            let line_number = match root {
+                Node::Element(e) => e.line_number,
+                Node::Cdata(_) => 0,
+            };
+            for (depth, (prefix, uri)) in self.namespaces.iter().enumerate() {
+                push_line(
+                    &mut output,
+                    depth,
+                    format_args!("N: {}={} (line={})", prefix, uri, line_number),
+                );
+            }
+            self.dump_node(&mut output, root, self.namespaces.len());
+        }
+        output
+    }
+
+    fn dump_node(&self, output: &mut String, node: &Node, depth: usize) {
+        match node {
+            Node::Element(e) => self.dump_element(output, e, depth),
+            Node::Cdata(c) => push_line(output, depth, format_args!("T: '{}'", c.get_data())),
+        }
+    }
+
+    fn dump_element(&self, output: &mut String, e: &Element, depth: usize) {
+        for (prefix, uri) in &e.namespace_declarations {
+            push_line(
+                output,
+                depth,
+                format_args!("N: {}={} (line={})", prefix, uri, e.line_number),
+            );
+        }
+        let depth = depth + e.namespace_declarations.len();
+
+        let mut name = String::new();
+        let namespace = e.namespace.as_deref().unwrap_or_default();
+        if !namespace.is_empty() {
+            name.push_str(namespace);
+            name.push(':');
+        }
+        name.push_str(&e.name);
+        push_line(
+            output,
+            depth,
+            format_args!("E: {} (line={})", name, e.line_number),
+        );
+
+        for attr in &e.typed_attributes {
+            let mut line = String::from("A: ");
+            let namespace = self.namespace_of(&attr.namespace, &attr.qualified_name);
+            if !namespace.is_empty() {
+                line.push_str(namespace);
+                line.push(':');
+            }
+            line.push_str(&attr.name);
+            if let Some(id) = attr.resource_id {
+                let _ = write!(line, "(0x{:08x})", id);
+            }
+            if attr.typed_value.data_type == ResourceValueType::String {
+                let _ = write!(line, "=\"{}\"", attr.value);
+            } else {
+                let _ = write!(line, "={}", attr.value);
+            }
+            if let Some(raw) = attr.raw_value.as_deref().filter(|r| !r.is_empty()) {
+                let _ = write!(line, " (Raw: \"{}\")", raw);
+            }
+            push_line(output, depth + 1, format_args!("{}", line));
+        }
+
+        // aapt2 indents children twice, once for the attributes and once more
+        // for the children themselves.
+        for child in e.get_children() {
+            self.dump_node(output, child, depth + 2);
+        }
+    }
+}
+
+fn push_line(output: &mut String, depth: usize, line: std::fmt::Arguments) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+    let _ = output.write_fmt(line);
+    output.push('\n');
+}
+
+#[test]
+fn test_dump_tree() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    let dump = xml.dump_tree();
+
+    let mut lines = dump.lines();
+    assert_eq!(
+        lines.next(),
+        Some("N: android=http://schemas.android.com/apk/res/android (line=2)")
+    );
+    assert_eq!(lines.next(), Some("  E: manifest (line=2)"));
+    assert!(dump
+        .contains("\n    A: http://schemas.android.com/apk/res/android:versionCode(0x0101021b)="));
+    assert!(dump.contains("\n      E: application (line=6)\n"));
+}
@@ -0,0 +1,238 @@
+use thiserror::Error;
+
+use crate::path::{ElementPath, Selector};
+use crate::xml::{Element, Node, XmlDocument};
+
+///Why [XmlDocument::insert] could not add a node.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    #[error("no element matches {0}")]
+    NoMatch(String),
+    #[error("index {index} is past the {len} children of {path}")]
+    IndexOutOfBounds {
+        path: String,
+        index: usize,
+        len: usize,
+    },
+}
+
+impl XmlDocument {
+    ///Removes every element `selector` selects, with its subtree, and
+    ///returns them in document order.
+    ///
+    ///The selector is either an [ElementPath](crate::ElementPath) or a
+    ///predicate:
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, ElementPath};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut xml = parse(&mut std::fs::File::open(manifest_file).unwrap()).unwrap();
+    ///
+    ///let camera: ElementPath = "manifest/uses-permission[@android:name='android.permission.CAMERA']"
+    ///    .parse()
+    ///    .unwrap();
+    ///xml.remove_matching(camera);
+    ///xml.remove_matching(|e: &axmldecoder::Element| e.get_tag() == "receiver");
+    ///assert!(xml.find_first(|e| e.get_tag() == "receiver").is_none());
+    ///```
+    pub fn remove_matching<'a, S: Into<Selector<'a>>>(&mut self, selector: S) -> Vec<Element> {
+        let paths = match selector.into() {
+            Selector::Path(path) => path.locate(self),
+            Selector::Predicate(mut predicate) => {
+                let mut paths = Vec::new();
+                select(self.roots(), &mut predicate, &mut Vec::new(), &mut paths);
+                paths
+            }
+        };
+
+        // Removing from the back keeps the indices of earlier matches valid.
+        let mut removed: Vec<Element> = paths
+            .iter()
+            .rev()
+            .filter_map(|path| self.remove_at(path))
+            .collect();
+        removed.reverse();
+        removed
+    }
+
+    ///Inserts `node` at position `index` among the children of the first
+    ///element `parent` selects, shifting the following children back.
+    ///
+    ///Names in `parent` are matched by namespace, so `android:name` finds
+    ///the attribute whatever prefix the document uses for it. Prefixes in
+    ///`node` are kept as they are; an element taken from another document
+    ///should go through [XmlDocument::adopt] first.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, Element, ElementPath, Node};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut xml = parse(&mut std::fs::File::open(manifest_file).unwrap()).unwrap();
+    ///
+    ///let mut provider = Element::new("provider");
+    ///provider.set_attribute("android:name", "androidx.startup.InitializationProvider");
+    ///provider.set_attribute("android:authorities", "com.example.startup");
+    ///let application: ElementPath = "manifest/application".parse().unwrap();
+    ///xml.insert(&application, 0, Node::Element(provider)).unwrap();
+    ///assert!(xml.find_first(|e| e.get_tag() == "provider").is_some());
+    ///```
+    pub fn insert(
+        &mut self,
+        parent: &ElementPath,
+        index: usize,
+        node: Node,
+    ) -> Result<(), EditError> {
+        let path = parent
+            .locate(self)
+            .into_iter()
+            .next()
+            .ok_or_else(|| EditError::NoMatch(parent.to_string()))?;
+        let children = self
+            .element_at_mut(&path)
+            .ok_or_else(|| EditError::NoMatch(parent.to_string()))?
+            .get_children_mut();
+        if index > children.len() {
+            return Err(EditError::IndexOutOfBounds {
+                path: parent.to_string(),
+                index,
+                len: children.len(),
+            });
+        }
+        children.insert(index, node);
+        Ok(())
+    }
+
+    ///Returns the element at `path`, given as by [XmlDocument::path_of].
+    pub(crate) fn element_at_mut(&mut self, path: &[usize]) -> Option<&mut Element> {
+        let (first, rest) = path.split_first()?;
+        let mut element = match self.roots_mut().get_mut(*first)? {
+            Node::Element(e) => e,
+            Node::Cdata(_) => return None,
+        };
+        for &i in rest {
+            element = match element.get_children_mut().get_mut(i)? {
+                Node::Element(e) => e,
+                Node::Cdata(_) => return None,
+            };
+        }
+        Some(element)
+    }
+
+    ///Removes the element at `path`, given as by [XmlDocument::path_of].
+    fn remove_at(&mut self, path: &[usize]) -> Option<Element> {
+        let (last, ancestors) = path.split_last()?;
+        let mut siblings = self.roots_mut();
+        for &i in ancestors {
+            siblings = match siblings.get_mut(i)? {
+                Node::Element(e) => e.get_children_mut(),
+                Node::Cdata(_) => return None,
+            };
+        }
+        match siblings.get(*last)? {
+            Node::Element(_) => match siblings.remove(*last) {
+                Node::Element(e) => Some(e),
+                Node::Cdata(_) => None,
+            },
+            Node::Cdata(_) => None,
+        }
+    }
+}
+
+///Collects the paths of the elements `predicate` selects, without looking
+///below a selected element.
+fn select(
+    nodes: &[Node],
+    predicate: &mut dyn FnMut(&Element) -> bool,
+    path: &mut Vec<usize>,
+    found: &mut Vec<Vec<usize>>,
+) {
+    for (i, node) in nodes.iter().enumerate() {
+        if let Node::Element(e) = node {
+            path.push(i);
+            if predicate(e) {
+                found.push(path.clone());
+            } else {
+                select(e.get_children(), predicate, path, found);
+            }
+            path.pop();
+        }
+    }
+}
+
+#[test]
+fn test_remove_matching() {
+    use crate::ElementPath;
+
+    let mut manifest = Element::new("manifest");
+    for name in ["android.permission.CAMERA", "android.permission.INTERNET"].iter() {
+        let mut permission = Element::new("uses-permission");
+        permission.set_attribute("android:name", name);
+        manifest.get_children_mut().push(Node::Element(permission));
+    }
+    let mut application = Element::new("application");
+    application
+        .get_children_mut()
+        .push(Node::Element(Element::new("receiver")));
+    manifest.get_children_mut().push(Node::Element(application));
+    let mut xml = XmlDocument::new(Node::Element(manifest));
+    xml.declare_namespace("android", crate::ANDROID_NAMESPACE);
+
+    let path: ElementPath = "manifest/uses-permission[@android:name='android.permission.CAMERA']"
+        .parse()
+        .unwrap();
+    let removed = xml.remove_matching(path.clone());
+    assert_eq!(removed.len(), 1);
+    assert_eq!(
+        removed[0].get_attribute("android:name"),
+        Some("android.permission.CAMERA")
+    );
+    assert!(xml.remove_matching(path).is_empty());
+
+    let removed = xml.remove_matching(|e: &Element| e.get_tag() != "manifest");
+    let tags: Vec<_> = removed.iter().map(|e| e.get_tag()).collect();
+    assert_eq!(tags, ["uses-permission", "application"]);
+    assert!(xml.find_first(|e| e.get_tag() == "receiver").is_none());
+}
+
+#[test]
+fn test_insert() {
+    use std::convert::TryFrom;
+
+    let mut activity = Element::new("activity");
+    activity.set_attribute("a:name", ".Main");
+    let mut application = Element::new("application");
+    application
+        .get_children_mut()
+        .push(Node::Element(Element::new("activity")));
+    application.get_children_mut().push(Node::Element(activity));
+    let mut manifest = Element::new("manifest");
+    manifest.get_children_mut().push(Node::Element(application));
+    let mut source = XmlDocument::new(Node::Element(manifest));
+    source.declare_namespace("a", crate::ANDROID_NAMESPACE);
+    // Encoding and parsing again binds the prefix to the namespace.
+    let binaryxml = crate::BinaryXmlDocument::try_from(&source).unwrap();
+    let mut xml = XmlDocument::try_from(binaryxml).unwrap();
+
+    let main: ElementPath = "manifest/application/activity[@android:name='.Main']"
+        .parse()
+        .unwrap();
+    xml.insert(&main, 0, Node::Element(Element::new("intent-filter")))
+        .unwrap();
+    let activity = xml
+        .find_first(|e| e.get_tag() == "activity" && !e.get_children().is_empty())
+        .unwrap();
+    assert_eq!(activity.get_attribute("a:name"), Some(".Main"));
+
+    assert_eq!(
+        xml.insert(&main, 2, Node::Element(Element::new("meta-data"))),
+        Err(EditError::IndexOutOfBounds {
+            path: main.to_string(),
+            index: 2,
+            len: 1
+        })
+    );
+    let missing: ElementPath = "manifest/application/service".parse().unwrap();
+    assert_eq!(
+        xml.insert(&missing, 0, Node::Element(Element::new("meta-data"))),
+        Err(EditError::NoMatch(missing.to_string()))
+    );
+}
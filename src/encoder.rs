@@ -0,0 +1,369 @@
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::rc::Rc;
+
+use crate::binaryxml::{
+    BinaryXmlDocument, ChunkHeader, ResourceType, XmlAttrExt, XmlAttribute, XmlCdata, XmlElement,
+    XmlEndElement, XmlEndNameSpace, XmlNodeHeader, XmlStartElement, XmlStartNameSpace,
+};
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::stringpool::ResStringPool;
+use crate::xml::{
+    get_resource_id, split_prefix, Attribute, Element, Node, XmlDocument, ANDROID_NAMESPACE,
+};
+use crate::{EncodeConfig, EncodeError};
+
+const NO_STRING: u32 = u32::MAX;
+
+///Lowers an [XmlDocument] back into the chunks of a binary XML document.
+///
+///The maps are only used for lookups and never iterated, so that the output
+///does not depend on their order.
+#[derive(Default)]
+pub(crate) struct Encoder {
+    strings: Vec<Rc<String>>,
+    string_indices: HashMap<String, u32>,
+    resource_map: Vec<u32>,
+    resource_indices: HashMap<(u32, String), u32>,
+    elements: Vec<XmlElement>,
+    ///Namespace URIs by prefix, used to resolve names that were set without
+    ///one.
+    prefixes: HashMap<String, String>,
+}
+
+impl Encoder {
+    pub(crate) fn encode(
+        document: &XmlDocument,
+        config: &EncodeConfig,
+    ) -> Result<BinaryXmlDocument, EncodeError> {
+        let mut encoder = Self {
+            prefixes: document
+                .namespaces
+                .iter()
+                .map(|(prefix, uri)| (prefix.clone(), uri.clone()))
+                .collect(),
+            ..Self::default()
+        };
+
+        if let Some(Node::Element(root)) = document.get_root() {
+            encoder.collect_prefixes(root);
+        }
+
+        // Android looks up the resource id of an attribute name by using its
+        // string index as an index into the resource map, so these names have
+        // to come first in the pool. They are sorted by id like aapt2 does,
+        // which also keeps the pool independent of the attribute order.
+        let mut resource_names = BTreeSet::new();
+        if let Some(Node::Element(root)) = document.get_root() {
+            encoder.collect_resource_ids(root, &mut resource_names);
+        }
+        for (id, name) in resource_names {
+            let index = encoder.strings.len() as u32;
+            encoder.resource_map.push(id);
+            encoder.strings.push(Rc::new(name.clone()));
+            encoder.resource_indices.insert((id, name), index);
+        }
+
+        let namespaces: Vec<(u32, u32)> = document
+            .namespaces
+            .iter()
+            .map(|(prefix, uri)| (encoder.intern(prefix), encoder.intern(uri)))
+            .collect();
+
+        for &(prefix, uri) in &namespaces {
+            encoder
+                .elements
+                .push(XmlElement::XmlStartNameSpace(XmlStartNameSpace {
+                    header: node_header(ResourceType::XmlStartNameSpace, 24, 0),
+                    prefix,
+                    uri,
+                }));
+        }
+
+        if let Some(root) = document.get_root() {
+            encoder.encode_node(root)?;
+        }
+
+        for &(prefix, uri) in namespaces.iter().rev() {
+            encoder
+                .elements
+                .push(XmlElement::XmlEndNameSpace(XmlEndNameSpace {
+                    header: node_header(ResourceType::XmlEndNameSpace, 24, 0),
+                    prefix,
+                    uri,
+                }));
+        }
+
+        let mut string_pool = ResStringPool::from_strings(encoder.strings);
+        string_pool.set_utf8(config.utf8_strings);
+        Ok(BinaryXmlDocument::new(
+            encoder.elements,
+            string_pool,
+            encoder.resource_map,
+        ))
+    }
+
+    ///Adds the namespaces declared on nested elements to the prefixes used to
+    ///resolve names.
+    fn collect_prefixes(&mut self, element: &Element) {
+        for (prefix, uri) in &element.namespace_declarations {
+            self.prefixes
+                .entry(prefix.clone())
+                .or_insert_with(|| uri.clone());
+        }
+
+        for child in element.get_children() {
+            if let Node::Element(e) = child {
+                self.collect_prefixes(e);
+            }
+        }
+    }
+
+    fn collect_resource_ids(&self, element: &Element, names: &mut BTreeSet<(u32, String)>) {
+        for attr in &element.typed_attributes {
+            if let (_, Some(id)) = self.resolve_attribute(attr) {
+                // Malformed documents may attach the same id to several names,
+                // each of which needs its own entry.
+                names.insert((id, attr.name.clone()));
+            }
+        }
+
+        for child in element.get_children() {
+            if let Node::Element(e) = child {
+                self.collect_resource_ids(e, names);
+            }
+        }
+    }
+
+    fn resolve_namespace(
+        &self,
+        namespace: &Option<String>,
+        qualified_name: &str,
+    ) -> Option<String> {
+        namespace.clone().or_else(|| {
+            let prefix = split_prefix(qualified_name).0?;
+            self.prefixes.get(prefix).cloned()
+        })
+    }
+
+    ///Returns the namespace URI and resource id of `attr`. Attributes that
+    ///were added after parsing only know their prefix, and get the id of the
+    ///framework attribute of the same name if they are in the android
+    ///namespace.
+    fn resolve_attribute(&self, attr: &Attribute) -> (Option<String>, Option<u32>) {
+        let namespace = self.resolve_namespace(&attr.namespace, &attr.qualified_name);
+        let resource_id = attr.resource_id.or_else(|| match namespace.as_deref() {
+            Some(ANDROID_NAMESPACE) => get_resource_id(&attr.name),
+            _ => None,
+        });
+
+        (namespace, resource_id)
+    }
+
+    ///Returns the pool index of `s`, adding it to the pool if needed. Names
+    ///reserved for resource ids are never reused, as that would attach a
+    ///resource id to whatever references them.
+    fn intern(&mut self, s: &str) -> u32 {
+        if let Some(&i) = self.string_indices.get(s) {
+            return i;
+        }
+
+        let i = self.strings.len() as u32;
+        self.strings.push(Rc::new(s.to_string()));
+        self.string_indices.insert(s.to_string(), i);
+        i
+    }
+
+    fn intern_optional(&mut self, s: Option<&str>) -> u32 {
+        s.map_or(NO_STRING, |s| self.intern(s))
+    }
+
+    fn encode_node(&mut self, node: &Node) -> Result<(), EncodeError> {
+        match node {
+            Node::Element(e) => self.encode_element(e),
+            Node::Cdata(c) => {
+                let data = self.intern(c.get_data());
+                let typed_data = c.get_typed_value().cloned().unwrap_or(ResourceValue {
+                    size: 8,
+                    res: 0,
+                    data_type: ResourceValueType::String,
+                    data,
+                });
+                self.elements.push(XmlElement::XmlCdata(XmlCdata {
+                    header: node_header(ResourceType::XmlCdata, 28, 0),
+                    data,
+                    typed_data,
+                }));
+                Ok(())
+            }
+        }
+    }
+
+    fn encode_element(&mut self, element: &Element) -> Result<(), EncodeError> {
+        let namespace = self
+            .resolve_namespace(&element.namespace, element.get_tag())
+            .or_else(|| match split_prefix(element.get_tag()).0 {
+                // Unlike attributes, unprefixed elements are in the default
+                // namespace.
+                None => self.prefixes.get("").cloned(),
+                Some(_) => None,
+            });
+        let ns = self.intern_optional(namespace.as_deref());
+        let name = self.intern(&element.name);
+
+        // Android expects attributes with a resource id first, ordered by id,
+        // as it looks them up with a single forward scan.
+        let mut typed_attributes: Vec<_> = element
+            .typed_attributes
+            .iter()
+            .map(|attr| (attr, self.resolve_attribute(attr)))
+            .collect();
+        typed_attributes.sort_by_key(|(_, (_, id))| (id.is_none(), *id));
+
+        let mut attributes = Vec::with_capacity(typed_attributes.len());
+        for (attr, (namespace, resource_id)) in typed_attributes {
+            let attr_name = match resource_id {
+                Some(id) => self.resource_indices[&(id, attr.name.clone())],
+                None => self.intern(&attr.name),
+            };
+
+            let mut typed_value = attr.typed_value.clone();
+            let raw_value = if typed_value.data_type == ResourceValueType::String {
+                typed_value.data = self.intern(&attr.value);
+                typed_value.data
+            } else {
+                self.intern_optional(attr.raw_value.as_deref())
+            };
+
+            attributes.push(XmlAttribute {
+                ns: self.intern_optional(namespace.as_deref()),
+                name: attr_name,
+                raw_value,
+                typed_value,
+            });
+        }
+
+        let attribute_count = u16::try_from(attributes.len())
+            .map_err(|_| EncodeError::TooManyAttributes(element.get_tag().to_string()))?;
+
+        let namespaces: Vec<(u32, u32)> = element
+            .namespace_declarations
+            .iter()
+            .map(|(prefix, uri)| (self.intern(prefix), self.intern(uri)))
+            .collect();
+        for &(prefix, uri) in &namespaces {
+            self.elements
+                .push(XmlElement::XmlStartNameSpace(XmlStartNameSpace {
+                    header: node_header(ResourceType::XmlStartNameSpace, 24, element.line_number),
+                    prefix,
+                    uri,
+                }));
+        }
+
+        self.elements
+            .push(XmlElement::XmlStartElement(XmlStartElement {
+                header: node_header(ResourceType::XmlStartElement, 0, element.line_number),
+                attr_ext: XmlAttrExt {
+                    ns,
+                    name,
+                    attribute_start: 20,
+                    attribute_size: 20,
+                    attribute_count,
+                    id_index: 0,
+                    class_index: 0,
+                    style_index: 0,
+                },
+                attributes,
+            }));
+
+        for child in element.get_children() {
+            self.encode_node(child)?;
+        }
+
+        self.elements.push(XmlElement::XmlEndElement(XmlEndElement {
+            header: node_header(ResourceType::XmlEndElement, 24, element.line_number),
+            ns,
+            name,
+        }));
+
+        for &(prefix, uri) in namespaces.iter().rev() {
+            self.elements
+                .push(XmlElement::XmlEndNameSpace(XmlEndNameSpace {
+                    header: node_header(ResourceType::XmlEndNameSpace, 24, element.line_number),
+                    prefix,
+                    uri,
+                }));
+        }
+
+        Ok(())
+    }
+}
+
+impl XmlDocument {
+    ///Returns the number of bytes [encode_with_config](crate::encode_with_config)
+    ///would write for the document, without keeping the encoded bytes around.
+    ///
+    ///```rust
+    ///use axmldecoder::{encode_to_vec, parse, EncodeConfig};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let size = xml.encoded_size(&EncodeConfig::default()).unwrap();
+    ///assert_eq!(size, encode_to_vec(&xml).unwrap().len());
+    ///```
+    pub fn encoded_size(&self, config: &EncodeConfig) -> Result<usize, EncodeError> {
+        let mut counter = SizeCounter::default();
+        Encoder::encode(self, config)?
+            .write_to(&mut counter)
+            .map_err(EncodeError::IoError)?;
+        Ok(counter.len as usize)
+    }
+}
+
+///A writer that discards its input and only tracks how far it got, for
+///measuring output that the writers patch up by seeking back.
+#[derive(Default)]
+struct SizeCounter {
+    position: u64,
+    len: u64,
+}
+
+impl Write for SizeCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.position += buf.len() as u64;
+        self.len = self.len.max(self.position);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SizeCounter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "seek before start"))?;
+        Ok(self.position)
+    }
+}
+
+///Builds the header of a node chunk. A `size` of 0 is filled in by the
+///writer.
+fn node_header(typ: ResourceType, size: u32, line_no: u32) -> XmlNodeHeader {
+    XmlNodeHeader {
+        chunk_header: ChunkHeader {
+            typ,
+            header_size: 16,
+            size,
+        },
+        line_no,
+        comment: NO_STRING,
+    }
+}
@@ -0,0 +1,267 @@
+use crate::binaryxml::{
+    BinaryXmlDocument, ChunkHeader, ResourceType, XmlAttrExt, XmlAttribute, XmlCdata, XmlElement,
+    XmlEndElement, XmlEndNameSpace, XmlNodeHeader, XmlStartElement, XmlStartNameSpace,
+};
+use crate::chunks::RawChunk;
+use crate::resource_value::ResourceValue;
+
+///String pool indices are stored as `u32::MAX` when there is no string.
+const NO_STRING: u32 = u32::MAX;
+
+///A node chunk of a binary XML document, as it is stored.
+///
+///Names and values are string pool indices, to be looked up in
+///[BinaryXmlDocument::string_pool], with `None` where the document refers
+///to no string. Chunk sizes and the layout of attributes are left out, as
+///they are recomputed when the document is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlEvent {
+    StartNamespace(NamespaceEvent),
+    EndNamespace(NamespaceEvent),
+    StartElement(StartElementEvent),
+    EndElement(EndElementEvent),
+    Text(TextEvent),
+    ///A chunk that was skipped while reading, kept as it was read.
+    Unknown(RawChunk),
+}
+
+///The start or end of a namespace declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceEvent {
+    pub line_number: u32,
+    pub comment: Option<u32>,
+    ///The prefix, `None` for the default namespace.
+    pub prefix: Option<u32>,
+    pub uri: u32,
+}
+
+///The start of an element with its attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartElementEvent {
+    pub line_number: u32,
+    pub comment: Option<u32>,
+    pub namespace: Option<u32>,
+    pub name: u32,
+    pub attributes: Vec<AttributeEvent>,
+    ///One-based index into `attributes` of the `id` attribute, or 0.
+    pub id_index: u16,
+    ///One-based index into `attributes` of the `class` attribute, or 0.
+    pub class_index: u16,
+    ///One-based index into `attributes` of the `style` attribute, or 0.
+    pub style_index: u16,
+}
+
+///An attribute of a [StartElementEvent].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeEvent {
+    pub namespace: Option<u32>,
+    ///The name. Framework attributes are identified by the resource id at
+    ///the same index of [BinaryXmlDocument::resource_map] instead.
+    pub name: u32,
+    ///The value as written in the source, if it was kept.
+    pub raw_value: Option<u32>,
+    pub value: ResourceValue,
+}
+
+///The end of an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndElementEvent {
+    pub line_number: u32,
+    pub comment: Option<u32>,
+    pub namespace: Option<u32>,
+    pub name: u32,
+}
+
+///Text between elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEvent {
+    pub line_number: u32,
+    pub comment: Option<u32>,
+    ///The text, `None` if it is only stored as a typed value.
+    pub data: Option<u32>,
+    pub value: ResourceValue,
+}
+
+impl BinaryXmlDocument {
+    ///Returns the node chunks of the document in order.
+    ///
+    ///Together with [BinaryXmlDocument::set_events], this allows transforms
+    ///that work below the level of [XmlDocument](crate::XmlDocument), such
+    ///as removing comments while keeping everything else as it is:
+    ///
+    ///```rust
+    ///use axmldecoder::{BinaryXmlDocument, XmlEvent};
+    ///# use axmldecoder::ParseError;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let mut document = BinaryXmlDocument::read_from(&mut f)?;
+    ///let events: Vec<XmlEvent> = document
+    ///    .events()
+    ///    .map(|mut event| {
+    ///        if let XmlEvent::StartElement(e) = &mut event {
+    ///            e.comment = None;
+    ///        }
+    ///        event
+    ///    })
+    ///    .collect();
+    ///document.set_events(events);
+    ///# Ok::<(), ParseError>(())
+    ///```
+    pub fn events(&self) -> impl Iterator<Item = XmlEvent> + '_ {
+        self.elements.iter().map(XmlEvent::from_element)
+    }
+
+    ///Replaces the node chunks of the document. The string pool and
+    ///resource map are kept, so the events must only refer to strings the
+    ///pool has.
+    pub fn set_events<I: IntoIterator<Item = XmlEvent>>(&mut self, events: I) {
+        self.elements = events.into_iter().map(XmlEvent::into_element).collect();
+    }
+}
+
+impl XmlEvent {
+    pub(crate) fn from_element(element: &XmlElement) -> Self {
+        match element {
+            XmlElement::XmlStartNameSpace(e) => XmlEvent::StartNamespace(NamespaceEvent {
+                line_number: e.header.line_no,
+                comment: string(e.header.comment),
+                prefix: string(e.prefix),
+                uri: e.uri,
+            }),
+            XmlElement::XmlEndNameSpace(e) => XmlEvent::EndNamespace(NamespaceEvent {
+                line_number: e.header.line_no,
+                comment: string(e.header.comment),
+                prefix: string(e.prefix),
+                uri: e.uri,
+            }),
+            XmlElement::XmlStartElement(e) => XmlEvent::StartElement(StartElementEvent {
+                line_number: e.header.line_no,
+                comment: string(e.header.comment),
+                namespace: string(e.attr_ext.ns),
+                name: e.attr_ext.name,
+                attributes: e
+                    .attributes
+                    .iter()
+                    .map(|attr| AttributeEvent {
+                        namespace: string(attr.ns),
+                        name: attr.name,
+                        raw_value: string(attr.raw_value),
+                        value: attr.typed_value.clone(),
+                    })
+                    .collect(),
+                id_index: e.attr_ext.id_index,
+                class_index: e.attr_ext.class_index,
+                style_index: e.attr_ext.style_index,
+            }),
+            XmlElement::XmlEndElement(e) => XmlEvent::EndElement(EndElementEvent {
+                line_number: e.header.line_no,
+                comment: string(e.header.comment),
+                namespace: string(e.ns),
+                name: e.name,
+            }),
+            XmlElement::XmlCdata(e) => XmlEvent::Text(TextEvent {
+                line_number: e.header.line_no,
+                comment: string(e.header.comment),
+                data: string(e.data),
+                value: e.typed_data.clone(),
+            }),
+            XmlElement::Raw(chunk) => XmlEvent::Unknown(chunk.clone()),
+        }
+    }
+
+    pub(crate) fn into_element(self) -> XmlElement {
+        match self {
+            XmlEvent::StartNamespace(e) => XmlElement::XmlStartNameSpace(XmlStartNameSpace {
+                header: header(ResourceType::XmlStartNameSpace, e.line_number, e.comment),
+                prefix: e.prefix.unwrap_or(NO_STRING),
+                uri: e.uri,
+            }),
+            XmlEvent::EndNamespace(e) => XmlElement::XmlEndNameSpace(XmlEndNameSpace {
+                header: header(ResourceType::XmlEndNameSpace, e.line_number, e.comment),
+                prefix: e.prefix.unwrap_or(NO_STRING),
+                uri: e.uri,
+            }),
+            XmlEvent::StartElement(e) => XmlElement::XmlStartElement(XmlStartElement {
+                header: header(ResourceType::XmlStartElement, e.line_number, e.comment),
+                // The layout and count of the attributes are set when writing.
+                attr_ext: XmlAttrExt {
+                    ns: e.namespace.unwrap_or(NO_STRING),
+                    name: e.name,
+                    attribute_start: 0,
+                    attribute_size: 0,
+                    attribute_count: 0,
+                    id_index: e.id_index,
+                    class_index: e.class_index,
+                    style_index: e.style_index,
+                },
+                attributes: e
+                    .attributes
+                    .into_iter()
+                    .map(|attr| XmlAttribute {
+                        ns: attr.namespace.unwrap_or(NO_STRING),
+                        name: attr.name,
+                        raw_value: attr.raw_value.unwrap_or(NO_STRING),
+                        typed_value: attr.value,
+                    })
+                    .collect(),
+            }),
+            XmlEvent::EndElement(e) => XmlElement::XmlEndElement(XmlEndElement {
+                header: header(ResourceType::XmlEndElement, e.line_number, e.comment),
+                ns: e.namespace.unwrap_or(NO_STRING),
+                name: e.name,
+            }),
+            XmlEvent::Text(e) => XmlElement::XmlCdata(XmlCdata {
+                header: header(ResourceType::XmlCdata, e.line_number, e.comment),
+                data: e.data.unwrap_or(NO_STRING),
+                typed_data: e.value,
+            }),
+            XmlEvent::Unknown(chunk) => XmlElement::Raw(chunk),
+        }
+    }
+}
+
+fn string(index: u32) -> Option<u32> {
+    Some(index).filter(|&i| i != NO_STRING)
+}
+
+///Builds the header of a node chunk. The size is filled in by the writer.
+fn header(typ: ResourceType, line_no: u32, comment: Option<u32>) -> XmlNodeHeader {
+    XmlNodeHeader {
+        chunk_header: ChunkHeader {
+            typ,
+            header_size: 16,
+            size: 0,
+        },
+        line_no,
+        comment: comment.unwrap_or(NO_STRING),
+    }
+}
+
+#[test]
+fn test_events_roundtrip() {
+    use std::io::Cursor;
+
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let mut document = BinaryXmlDocument::read_from(&mut Cursor::new(&data)).unwrap();
+    let events: Vec<XmlEvent> = document.events().collect();
+    assert!(matches!(events[0], XmlEvent::StartNamespace(_)));
+
+    let mut original = Cursor::new(Vec::new());
+    document.write_to(&mut original).unwrap();
+    document.set_events(events.clone());
+    assert_eq!(document.events().collect::<Vec<_>>(), events);
+    let mut output = Cursor::new(Vec::new());
+    document.write_to(&mut output).unwrap();
+    assert_eq!(output.into_inner(), original.into_inner());
+
+    // Dropping the text leaves a document that still parses.
+    document.set_events(
+        events
+            .into_iter()
+            .filter(|event| !matches!(event, XmlEvent::Text(_))),
+    );
+    let mut output = Cursor::new(Vec::new());
+    document.write_to(&mut output).unwrap();
+    output.set_position(0);
+    assert!(crate::parse(&mut output).is_ok());
+}
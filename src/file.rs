@@ -0,0 +1,105 @@
+use std::fs::File;
+use std::io::BufReader;
+#[cfg(feature = "mmap")]
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::binaryxml::InputExtent;
+use crate::{encode_to_vec, parse_document, EncodeError, ParseConfig, ParseError, XmlDocument};
+
+///Files at least this large are memory mapped rather than read, when the
+///`mmap` feature is enabled. Below it, mapping costs more than it saves.
+#[cfg(feature = "mmap")]
+const MMAP_THRESHOLD: u64 = 1 << 20;
+
+///Parses the Android binary XML file at `path`. Errors are wrapped in
+///[ParseError::File] so that they name the file.
+///
+///Small files are read through a buffer. With the `mmap` feature, large
+///files are memory mapped instead.
+///
+///```rust
+///use axmldecoder::parse_file;
+///# use axmldecoder::ParseError;
+///let xml = parse_file("examples/AndroidManifest.xml")?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<XmlDocument, ParseError> {
+    let path = path.as_ref();
+    read_file(path).map_err(|e| ParseError::File {
+        path: path.to_path_buf(),
+        source: Box::new(e),
+    })
+}
+
+fn read_file(path: &Path) -> Result<XmlDocument, ParseError> {
+    let file = File::open(path).map_err(ParseError::IoError)?;
+    // Chunks are checked against the size of the file before they are read.
+    let size = file.metadata().map_err(ParseError::IoError)?.len();
+    let extent = InputExtent {
+        start: 0,
+        len: Some(size),
+    };
+    let config = ParseConfig::default();
+
+    #[cfg(feature = "mmap")]
+    {
+        if size >= MMAP_THRESHOLD {
+            // SAFETY: the map is only read while parsing. Truncating the
+            // file from another process in the meantime is not supported.
+            let map = unsafe { memmap2::Mmap::map(&file) }.map_err(ParseError::IoError)?;
+            return Ok(parse_document(&mut Cursor::new(&map[..]), extent, &config)?.0);
+        }
+    }
+
+    Ok(parse_document(&mut BufReader::new(file), extent, &config)?.0)
+}
+
+///Encodes a [XmlDocument] into the file at `path`, replacing it if it
+///exists. Returns the number of bytes written.
+///
+///```rust,no_run
+///use axmldecoder::{encode_file, parse_file};
+///# use std::error::Error;
+///let xml = parse_file("AndroidManifest.xml")?;
+///encode_file(&xml, "AndroidManifest.out.xml")?;
+///# Ok::<(), Box<dyn Error>>(())
+///```
+pub fn encode_file<P: AsRef<Path>>(document: &XmlDocument, path: P) -> Result<usize, EncodeError> {
+    let path = path.as_ref();
+    // The document is encoded in full first so that a failed encoding does
+    // not leave a truncated file behind.
+    let data = encode_to_vec(document)?;
+    std::fs::write(path, &data).map_err(|e| EncodeError::File {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    Ok(data.len())
+}
+
+#[test]
+fn test_parse_and_encode_file() {
+    let xml = parse_file("examples/AndroidManifest.xml").unwrap();
+
+    // Concurrent test runs must not share the file.
+    let path = std::env::temp_dir().join(format!(
+        "axmldecoder-test-encode-file-{}.xml",
+        std::process::id()
+    ));
+    let size = encode_file(&xml, &path).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), size as u64);
+    let reparsed = parse_file(&path).unwrap();
+    assert_eq!(
+        encode_to_vec(&reparsed).unwrap(),
+        std::fs::read(&path).unwrap()
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    let missing = Path::new("examples/missing.xml");
+    match parse_file(missing) {
+        Err(e @ ParseError::File { .. }) => {
+            assert!(e.to_string().starts_with("examples/missing.xml: "))
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+}
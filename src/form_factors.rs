@@ -0,0 +1,115 @@
+use crate::manifest::{child_elements, Manifest};
+
+const FEATURE_WATCH: &str = "android.hardware.type.watch";
+const FEATURE_LEANBACK: &str = "android.software.leanback";
+const FEATURE_TELEVISION: &str = "android.hardware.type.television";
+const FEATURE_AUTOMOTIVE: &str = "android.hardware.type.automotive";
+const META_DATA_WEAR_STANDALONE: &str = "com.google.android.wearable.standalone";
+const META_DATA_CAR_APPLICATION: &str = "com.google.android.gms.car.application";
+
+///A kind of device other than phones and tablets that an app targets, as
+///returned by [Manifest::form_factors].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum FormFactor {
+    ///Wear OS watches.
+    Wear,
+    ///Android TV and Google TV.
+    Tv,
+    ///Android Auto, projected from a phone, and Android Automotive OS.
+    Auto,
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the form factors the app declares support for, in the order
+    ///Wear, TV, Auto. Phones and tablets are not listed, as every app runs
+    ///on them unless it requires a feature they lack.
+    ///
+    /// - Wear: a `uses-feature` for `android.hardware.type.watch`, or the
+    ///   `com.google.android.wearable.standalone` meta-data.
+    /// - TV: a `uses-feature` for `android.software.leanback` or
+    ///   `android.hardware.type.television`, or a launchable activity in the
+    ///   `LEANBACK_LAUNCHER` category.
+    /// - Auto: the `com.google.android.gms.car.application` meta-data, or a
+    ///   `uses-feature` for `android.hardware.type.automotive`.
+    ///
+    ///Features count whether they are required or not, since an optional
+    ///feature still marks the app as built for that kind of device.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, FormFactor};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let form_factors = xml.manifest().unwrap().form_factors();
+    ///assert!(!form_factors.contains(&FormFactor::Tv));
+    ///```
+    pub fn form_factors(&self) -> Vec<FormFactor> {
+        let features: Vec<&str> = child_elements(self.root())
+            .filter(|e| e.get_tag() == "uses-feature")
+            .filter_map(|e| e.android_value("name"))
+            .collect();
+        let meta_data: Vec<&str> = self
+            .application()
+            .into_iter()
+            .flat_map(child_elements)
+            .filter(|e| e.get_tag() == "meta-data")
+            .filter_map(|e| e.android_value("name"))
+            .collect();
+
+        let mut form_factors = Vec::new();
+        if features.contains(&FEATURE_WATCH) || meta_data.contains(&META_DATA_WEAR_STANDALONE) {
+            form_factors.push(FormFactor::Wear);
+        }
+        if features.contains(&FEATURE_LEANBACK)
+            || features.contains(&FEATURE_TELEVISION)
+            || self.launchable_activities().iter().any(|a| a.leanback)
+        {
+            form_factors.push(FormFactor::Tv);
+        }
+        if meta_data.contains(&META_DATA_CAR_APPLICATION) || features.contains(&FEATURE_AUTOMOTIVE)
+        {
+            form_factors.push(FormFactor::Auto);
+        }
+        form_factors
+    }
+}
+
+#[test]
+fn test_form_factors() {
+    use crate::launcher::CATEGORY_LEANBACK_LAUNCHER;
+    use crate::xml::{Element, Node};
+
+    fn named(tag: &str, name: &str) -> Node {
+        let mut e = Element::new(tag);
+        e.set_attribute("android:name", name);
+        Node::Element(e)
+    }
+
+    let xml = Manifest::minimal("com.example", 21, 34);
+    assert_eq!(xml.manifest().unwrap().form_factors(), []);
+
+    let mut xml = Manifest::minimal("com.example", 21, 34);
+    let filter = "manifest/application/activity/intent-filter"
+        .parse()
+        .unwrap();
+    xml.insert(&filter, 0, named("category", CATEGORY_LEANBACK_LAUNCHER))
+        .unwrap();
+    let mut feature = Element::new("uses-feature");
+    feature.set_attribute("android:name", FEATURE_WATCH);
+    feature.set_attribute("android:required", "false");
+    xml.insert(&"manifest".parse().unwrap(), 0, Node::Element(feature))
+        .unwrap();
+    let application = "manifest/application".parse().unwrap();
+    xml.insert(
+        &application,
+        0,
+        named("meta-data", META_DATA_CAR_APPLICATION),
+    )
+    .unwrap();
+
+    assert_eq!(
+        xml.manifest().unwrap().form_factors(),
+        [FormFactor::Wear, FormFactor::Tv, FormFactor::Auto]
+    );
+}
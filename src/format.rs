@@ -0,0 +1,835 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+use crate::resolver::ResourceResolver;
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::xml::{split_prefix, Attribute, Cdata, Element, Node, XmlDocument};
+
+///Options controlling how a document is rendered as textual XML.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    ///Number of spaces each nesting level is indented by.
+    pub indent: usize,
+    ///Which characters of attribute values and text are escaped.
+    pub escaping: Escaping,
+    ///How whitespace in text nodes is treated.
+    pub whitespace: Whitespace,
+    ///The order attributes are written in.
+    pub attribute_order: AttributeOrder,
+    ///How characters that XML 1.0 does not allow are written.
+    pub invalid_characters: InvalidCharacters,
+    ///Whether comments stored in the binary XML are written as XML comments
+    ///before the element they are attached to.
+    pub include_comments: bool,
+    ///The XML declaration written before the root element.
+    pub xml_declaration: XmlDeclaration,
+    ///Resolves resource ids so that references are written by name, such
+    ///as `@string/app_name`. References the resolver does not know keep
+    ///their numeric form.
+    pub resolver: Option<Arc<dyn ResourceResolver>>,
+    ///Write framework attributes that are enums or flags by name, such as
+    ///`android:launchMode="singleTask"` instead of `"2"` or
+    ///`android:configChanges="keyboard|orientation"` instead of `"144"`.
+    #[cfg(feature = "symbols")]
+    pub symbolic_values: bool,
+    ///How values this crate cannot decode, such as dimensions in an unknown
+    ///unit, are written.
+    pub unknown_values: UnknownValues,
+    ///Whether elements without children are written as `<a/>` rather than
+    ///`<a></a>`.
+    pub self_closing: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            escaping: Escaping::Minimal,
+            whitespace: Whitespace::Trim,
+            attribute_order: AttributeOrder::Document,
+            invalid_characters: InvalidCharacters::Escape,
+            include_comments: false,
+            xml_declaration: XmlDeclaration::Standard,
+            resolver: None,
+            #[cfg(feature = "symbols")]
+            symbolic_values: false,
+            unknown_values: UnknownValues::Hex,
+            self_closing: true,
+        }
+    }
+}
+
+impl FormatOptions {
+    ///Options matching how apktool writes a decoded `AndroidManifest.xml`:
+    ///four spaces of indentation, attributes in resource id order and its
+    ///particular XML declaration. Set [FormatOptions::resolver] to also
+    ///write references by name the way apktool does.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, FormatOptions};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let text = xml.to_xml_string(&FormatOptions::apktool());
+    ///assert!(text.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"no\"?><manifest "));
+    ///```
+    pub fn apktool() -> Self {
+        Self {
+            indent: 4,
+            attribute_order: AttributeOrder::ResourceId,
+            xml_declaration: XmlDeclaration::Apktool,
+            ..Self::default()
+        }
+    }
+}
+
+///How characters in attribute values and text are escaped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escaping {
+    ///Escapes `&`, `<` and `>`, double quotes in attribute values, and
+    ///control characters as numeric character references. Tabs and line
+    ///breaks are only escaped in attribute values, where parsers would
+    ///otherwise normalize them to spaces.
+    Minimal,
+    ///Additionally escapes both kinds of quotes everywhere, and tabs, line
+    ///breaks, `DEL` and the C1 control characters as numeric character
+    ///references.
+    Aggressive,
+    ///Escapes like [Escaping::Minimal], and additionally writes every
+    ///character outside of ASCII as a numeric character reference, for
+    ///consumers that only handle ASCII.
+    Ascii,
+}
+
+///How characters that XML 1.0 does not allow, such as `NUL` and most other
+///C0 control characters, are written. Obfuscated manifests use them to trip
+///up tools, while Android itself does not mind them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidCharacters {
+    ///Writes them as numeric character references, which keeps the value
+    ///intact but is only accepted by XML 1.1 parsers.
+    Escape,
+    ///Leaves them out.
+    Strip,
+    ///Writes U+FFFD REPLACEMENT CHARACTER in their place.
+    Replace,
+}
+
+///The XML declaration written at the start of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XmlDeclaration {
+    ///`<?xml version="1.0" encoding="utf-8"?>` on a line of its own.
+    Standard,
+    ///`<?xml version="1.0" encoding="utf-8" standalone="no"?>` immediately
+    ///followed by the root element, as written by apktool.
+    Apktool,
+    ///No declaration at all.
+    Omit,
+}
+
+///How whitespace in text nodes is treated. Text in manifests is usually
+///just formatting, while other resources may depend on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Whitespace {
+    ///Writes text exactly as stored. Elements containing text are written
+    ///on a single line, so that indentation does not alter their content.
+    Preserve,
+    ///Trims whitespace surrounding text and drops text that is only
+    ///whitespace.
+    Trim,
+    ///Drops text that is only whitespace and writes everything else as
+    ///stored.
+    Drop,
+}
+
+///The order attributes are written in. Sorting makes the output of
+///documents that only differ in attribute order identical, which keeps
+///diffs small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOrder {
+    ///The order the attributes were stored or set in.
+    Document,
+    ///Attributes without a namespace prefix first, then sorted by prefix
+    ///and name.
+    Name,
+    ///Attributes with a resource id first in increasing id order, which is
+    ///how Android stores them, then the others sorted by name.
+    ResourceId,
+}
+
+///How values this crate cannot decode are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownValues {
+    ///Writes the raw data in hex, as `@0x0000010f`.
+    Hex,
+    ///Writes an empty value.
+    Empty,
+    ///Fails [XmlDocument::to_writer] with an error of kind
+    ///[io::ErrorKind::InvalidData]. [XmlDocument::to_xml_string] cannot fail
+    ///and writes them as [UnknownValues::Hex] instead.
+    Error,
+}
+
+impl XmlDocument {
+    ///Renders the document as textual XML. Namespace declarations are placed
+    ///on the root element.
+    ///
+    ///The output is well-formed XML, so it can be handed to parsers such as
+    ///`roxmltree` to reuse their querying and validation tooling:
+    ///
+    ///```rust
+    ///# use axmldecoder::{parse, FormatOptions};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///# let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///# let xml = parse(&mut f).unwrap();
+    ///let text = xml.to_xml_string(&FormatOptions::default());
+    ///let document = roxmltree::Document::parse(&text).unwrap();
+    ///assert_eq!(document.root_element().tag_name().name(), "manifest");
+    ///```
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, FormatOptions};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///println!("{}", xml.to_xml_string(&FormatOptions::default()));
+    ///```
+    pub fn to_xml_string(&self, options: &FormatOptions) -> String {
+        let mut output = String::new();
+        // Writing into a String cannot fail, and nothing else does without
+        // UnknownValues::Error.
+        let _ = self.write_xml(&mut output, &infallible(options));
+        output
+    }
+
+    ///Streams the document as textual XML into `output`, producing the same
+    ///text as [XmlDocument::to_xml_string] without holding all of it in
+    ///memory.
+    ///
+    ///The output is written in many small pieces, so unbuffered writers such
+    ///as files should be wrapped in a [std::io::BufWriter].
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, FormatOptions};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let mut stdout = std::io::stdout().lock();
+    ///xml.to_writer(&mut stdout, &FormatOptions::default()).unwrap();
+    ///```
+    pub fn to_writer<W: io::Write>(
+        &self,
+        output: &mut W,
+        options: &FormatOptions,
+    ) -> io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: output,
+            error: None,
+        };
+        match self.write_xml(&mut adapter, options) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter.error.unwrap_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "document contains a value of an unsupported type",
+                )
+            })),
+        }
+    }
+
+    fn write_xml<W: fmt::Write>(&self, output: &mut W, options: &FormatOptions) -> fmt::Result {
+        output.write_str(match options.xml_declaration {
+            XmlDeclaration::Standard => "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n",
+            XmlDeclaration::Apktool => {
+                "<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"no\"?>"
+            }
+            XmlDeclaration::Omit => "",
+        })?;
+        if let Some(root) = self.get_root() {
+            write_node(output, root, &self.namespaces, Some(0), options)?;
+        }
+        Ok(())
+    }
+}
+
+impl Element {
+    ///Renders the element and its descendants as textual XML.
+    pub fn to_xml_string(&self, options: &FormatOptions) -> String {
+        let mut output = String::new();
+        let _ = write_element(&mut output, self, &[], Some(0), &infallible(options));
+        output
+    }
+}
+
+///Renders the document as textual XML with the default [FormatOptions], like
+///[XmlDocument::to_xml_string].
+///
+///```rust
+///use axmldecoder::parse;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///println!("{}", xml);
+///```
+impl fmt::Display for XmlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_xml(f, &FormatOptions::default())
+    }
+}
+
+///Renders the element and its descendants as textual XML with the default
+///[FormatOptions], like [Element::to_xml_string].
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_element(f, self, &[], Some(0), &FormatOptions::default())
+    }
+}
+
+///Returns `options` with [UnknownValues::Error] replaced, for writers that
+///cannot report errors.
+fn infallible(options: &FormatOptions) -> Cow<'_, FormatOptions> {
+    match options.unknown_values {
+        UnknownValues::Error => Cow::Owned(FormatOptions {
+            unknown_values: UnknownValues::Hex,
+            ..options.clone()
+        }),
+        _ => Cow::Borrowed(options),
+    }
+}
+
+///Forwards formatted output to an [io::Write], keeping the error that
+///[fmt::Write] has no way to carry.
+struct IoAdapter<'a, W> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+fn write_node<W: fmt::Write>(
+    output: &mut W,
+    node: &Node,
+    namespaces: &[(String, String)],
+    level: Option<usize>,
+    options: &FormatOptions,
+) -> fmt::Result {
+    match node {
+        Node::Element(e) => write_element(output, e, namespaces, level, options),
+        Node::Cdata(c) => {
+            if let Some(text) = visible_text(c, options.whitespace) {
+                let text = resolve_reference(text, c.get_typed_value(), options);
+                let text = unknown_value(text, c.get_typed_value(), options)?;
+                push_indent(output, level, options)?;
+                let text = replace_invalid(&text, options.invalid_characters);
+                output.write_str(&escape(&text, false, options.escaping))?;
+                push_line_break(output, level)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+///Writes an element at the given indentation `level`, or inline without any
+///added whitespace if `level` is `None`.
+fn write_element<W: fmt::Write>(
+    output: &mut W,
+    e: &Element,
+    namespaces: &[(String, String)],
+    level: Option<usize>,
+    options: &FormatOptions,
+) -> fmt::Result {
+    if options.include_comments {
+        for comment in &e.comments {
+            push_indent(output, level, options)?;
+            write_comment(output, comment)?;
+            push_line_break(output, level)?;
+        }
+    }
+
+    push_indent(output, level, options)?;
+    output.write_char('<')?;
+    output.write_str(e.get_tag())?;
+
+    for (prefix, uri) in namespaces.iter().chain(&e.namespace_declarations) {
+        output.write_str(" xmlns")?;
+        if !prefix.is_empty() {
+            output.write_char(':')?;
+            output.write_str(prefix)?;
+        }
+        push_attribute_value(output, uri, options)?;
+    }
+
+    let typed: HashMap<&str, &Attribute> = e
+        .typed_attributes
+        .iter()
+        .map(|attr| (attr.qualified_name.as_str(), attr))
+        .collect();
+    let mut attributes: Vec<_> = e.attributes().collect();
+    match options.attribute_order {
+        AttributeOrder::Document => (),
+        AttributeOrder::Name => attributes.sort_by_key(|(name, _)| split_prefix(name)),
+        AttributeOrder::ResourceId => attributes.sort_by_key(|(name, _)| {
+            let id = typed[name].resource_id;
+            (id.is_none(), id, split_prefix(name))
+        }),
+    }
+    for (name, value) in attributes {
+        output.write_char(' ')?;
+        output.write_str(name)?;
+        let value = resolve_reference(value, Some(&typed[name].typed_value), options);
+        let value = unknown_value(value, Some(&typed[name].typed_value), options)?;
+        #[cfg(feature = "symbols")]
+        let value = match crate::symbols::symbolic_value(typed[name]) {
+            Some(symbol) if options.symbolic_values => Cow::Owned(symbol),
+            _ => value,
+        };
+        push_attribute_value(output, &value, options)?;
+    }
+
+    let children: Vec<&Node> = e
+        .get_children()
+        .iter()
+        .filter(|child| match child {
+            Node::Element(_) => true,
+            Node::Cdata(c) => visible_text(c, options.whitespace).is_some(),
+        })
+        .collect();
+    if children.is_empty() {
+        if options.self_closing {
+            output.write_str("/>")?;
+        } else {
+            output.write_str("></")?;
+            output.write_str(e.get_tag())?;
+            output.write_char('>')?;
+        }
+        return push_line_break(output, level);
+    }
+
+    let mixed = options.whitespace == Whitespace::Preserve
+        && children.iter().any(|child| matches!(child, Node::Cdata(_)));
+    let child_level = level.filter(|_| !mixed).map(|level| level + 1);
+
+    output.write_char('>')?;
+    push_line_break(output, child_level)?;
+    for child in children {
+        write_node(output, child, &[], child_level, options)?;
+    }
+    push_indent(output, child_level.and(level), options)?;
+    output.write_str("</")?;
+    output.write_str(e.get_tag())?;
+    output.write_char('>')?;
+    push_line_break(output, level)
+}
+
+///Writes an XML comment. Comments cannot contain `--`, so any such
+///sequence is broken up by a space.
+fn write_comment<W: fmt::Write>(output: &mut W, comment: &str) -> fmt::Result {
+    let mut comment = comment.to_string();
+    while comment.contains("--") {
+        comment = comment.replace("--", "- -");
+    }
+    write!(output, "<!-- {} -->", comment)
+}
+
+///Writes references by name when the resolver of `options` knows them.
+fn resolve_reference<'a>(
+    value: &'a str,
+    typed_value: Option<&ResourceValue>,
+    options: &FormatOptions,
+) -> Cow<'a, str> {
+    let (resolver, typed_value) = match (&options.resolver, typed_value) {
+        (Some(resolver), Some(typed_value)) if typed_value.data != 0 => (resolver, typed_value),
+        _ => return Cow::Borrowed(value),
+    };
+    let prefix = match typed_value.data_type {
+        ResourceValueType::Reference => '@',
+        ResourceValueType::Attribute => '?',
+        _ => return Cow::Borrowed(value),
+    };
+    match resolver.resolve(typed_value.data) {
+        Some(name) => Cow::Owned(format!("{}{}", prefix, name)),
+        None => Cow::Borrowed(value),
+    }
+}
+
+///Applies [FormatOptions::unknown_values] to values this crate cannot
+///decode.
+fn unknown_value<'a>(
+    value: Cow<'a, str>,
+    typed_value: Option<&ResourceValue>,
+    options: &FormatOptions,
+) -> Result<Cow<'a, str>, fmt::Error> {
+    match typed_value {
+        Some(typed_value) if !typed_value.is_supported() => match options.unknown_values {
+            UnknownValues::Hex => Ok(value),
+            UnknownValues::Empty => Ok(Cow::Borrowed("")),
+            UnknownValues::Error => Err(fmt::Error),
+        },
+        _ => Ok(value),
+    }
+}
+
+///Returns the text to write for a text node, if any.
+fn visible_text(c: &Cdata, whitespace: Whitespace) -> Option<&str> {
+    let data = c.get_data();
+    let blank = data.trim().is_empty();
+    match whitespace {
+        Whitespace::Preserve => Some(data),
+        Whitespace::Trim if !blank => Some(data.trim()),
+        Whitespace::Drop if !blank => Some(data),
+        _ => None,
+    }
+}
+
+fn push_indent<W: fmt::Write>(
+    output: &mut W,
+    level: Option<usize>,
+    options: &FormatOptions,
+) -> fmt::Result {
+    match level {
+        Some(level) => write!(output, "{:1$}", "", level * options.indent),
+        None => Ok(()),
+    }
+}
+
+fn push_line_break<W: fmt::Write>(output: &mut W, level: Option<usize>) -> fmt::Result {
+    match level {
+        Some(_) => output.write_char('\n'),
+        None => Ok(()),
+    }
+}
+
+fn push_attribute_value<W: fmt::Write>(
+    output: &mut W,
+    value: &str,
+    options: &FormatOptions,
+) -> fmt::Result {
+    output.write_str("=\"")?;
+    let value = replace_invalid(value, options.invalid_characters);
+    output.write_str(&escape(&value, true, options.escaping))?;
+    output.write_char('"')
+}
+
+///Strips or replaces the characters that XML 1.0 does not allow, as asked
+///for by `invalid_characters`. Escaping them is left to [escape].
+fn replace_invalid(s: &str, invalid_characters: InvalidCharacters) -> Cow<'_, str> {
+    let is_invalid = |c: char| match c {
+        '\t' | '\n' | '\r' => false,
+        '\0'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => true,
+        _ => false,
+    };
+    match invalid_characters {
+        InvalidCharacters::Escape => Cow::Borrowed(s),
+        _ if !s.contains(is_invalid) => Cow::Borrowed(s),
+        InvalidCharacters::Strip => Cow::Owned(s.replace(is_invalid, "")),
+        InvalidCharacters::Replace => Cow::Owned(s.replace(is_invalid, "\u{fffd}")),
+    }
+}
+
+///Escapes the characters that cannot appear literally in attribute values
+///or text, as well as those `escaping` asks for.
+fn escape(s: &str, attribute: bool, escaping: Escaping) -> Cow<'_, str> {
+    let aggressive = escaping == Escaping::Aggressive;
+    let ascii = escaping == Escaping::Ascii;
+    let needs_reference = |c: char| match c {
+        '\t' | '\n' | '\r' => attribute || aggressive,
+        '\u{7f}'..='\u{9f}' => aggressive || ascii,
+        c => c.is_control() || (ascii && !c.is_ascii()),
+    };
+    let needs_escape = |c: char| match c {
+        '&' | '<' | '>' => true,
+        '"' => attribute || aggressive,
+        '\'' => aggressive,
+        c => needs_reference(c),
+    };
+
+    if !s.contains(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if needs_escape(c) => escaped.push_str("&quot;"),
+            '\'' if needs_escape(c) => escaped.push_str("&apos;"),
+            c if needs_reference(c) => escaped.push_str(&format!("&#x{:x};", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[test]
+fn test_escape() {
+    use Escaping::*;
+
+    assert_eq!(escape("plain", true, Minimal), "plain");
+    assert_eq!(
+        escape("<a href=\"x\">&</a>", true, Minimal),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+    );
+    assert_eq!(escape("\"it's\"\n", false, Minimal), "\"it's\"\n");
+    assert_eq!(escape("a\tb\n", true, Minimal), "a&#x9;b&#xa;");
+    assert_eq!(escape("nul\0", false, Minimal), "nul&#x0;");
+    assert_eq!(
+        escape("\"it's\"\n\u{85}", false, Aggressive),
+        "&quot;it&apos;s&quot;&#xa;&#x85;"
+    );
+    assert_eq!(
+        escape("caf\u{e9} \u{1f600}\u{7f} \"a\"\n", false, Ascii),
+        "caf&#xe9; &#x1f600;&#x7f; \"a\"\n"
+    );
+}
+
+#[test]
+fn test_replace_invalid() {
+    use InvalidCharacters::*;
+
+    assert_eq!(replace_invalid("a\0b\t\u{1a}", Escape), "a\0b\t\u{1a}");
+    assert_eq!(replace_invalid("a\0b\t\u{1a}", Strip), "ab\t");
+    assert_eq!(
+        replace_invalid("a\0b\t\u{1a}", Replace),
+        "a\u{fffd}b\t\u{fffd}"
+    );
+}
+
+#[test]
+fn test_roxmltree_compatible() {
+    let options = FormatOptions {
+        invalid_characters: InvalidCharacters::Strip,
+        ..FormatOptions::default()
+    };
+    for entry in std::fs::read_dir("examples").unwrap() {
+        let path = entry.unwrap().path();
+        let mut f = std::fs::File::open(&path).unwrap();
+        let xml = match crate::parse_hardened(&mut f) {
+            Ok(xml) => xml,
+            Err(_) => continue,
+        };
+
+        let text = xml.to_xml_string(&options);
+        if let Err(e) = roxmltree::Document::parse(&text) {
+            panic!("{}: {}", path.display(), e);
+        }
+    }
+}
+
+#[test]
+fn test_whitespace() {
+    let mut string = Element::new("string");
+    string
+        .get_children_mut()
+        .push(Node::Cdata(Cdata::new(" a  b ")));
+    let mut resources = Element::new("resources");
+    resources
+        .get_children_mut()
+        .push(Node::Cdata(Cdata::new("\n  ")));
+    resources.get_children_mut().push(Node::Element(string));
+
+    let render = |whitespace| {
+        let options = FormatOptions {
+            whitespace,
+            ..FormatOptions::default()
+        };
+        resources.to_xml_string(&options)
+    };
+    assert_eq!(
+        render(Whitespace::Preserve),
+        "<resources>\n  <string> a  b </string></resources>\n"
+    );
+    assert_eq!(
+        render(Whitespace::Trim),
+        "<resources>\n  <string>\n    a  b\n  </string>\n</resources>\n"
+    );
+    assert_eq!(
+        render(Whitespace::Drop),
+        "<resources>\n  <string>\n     a  b \n  </string>\n</resources>\n"
+    );
+}
+
+#[test]
+fn test_attribute_order() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    let manifest = match xml.get_root() {
+        Some(Node::Element(e)) => e,
+        _ => panic!("missing root element"),
+    };
+
+    let render = |attribute_order| {
+        let options = FormatOptions {
+            attribute_order,
+            ..FormatOptions::default()
+        };
+        let text = manifest.to_xml_string(&options);
+        text[..text.find('>').unwrap()].to_string()
+    };
+    assert_eq!(
+        render(AttributeOrder::Document),
+        "<manifest android:versionCode=\"1\" android:versionName=\"1.0\" package=\"org.t0t0.androguard.TC\""
+    );
+    assert_eq!(
+        render(AttributeOrder::Name),
+        "<manifest package=\"org.t0t0.androguard.TC\" android:versionCode=\"1\" android:versionName=\"1.0\""
+    );
+    assert_eq!(
+        render(AttributeOrder::ResourceId),
+        render(AttributeOrder::Document)
+    );
+}
+
+#[test]
+fn test_comments() {
+    let mut activity = Element::new("activity");
+    activity.comments.push("Main entry point -->".to_string());
+    let mut application = Element::new("application");
+    application.get_children_mut().push(Node::Element(activity));
+
+    let options = FormatOptions {
+        include_comments: true,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        application.to_xml_string(&options),
+        "<application>\n  <!-- Main entry point - -> -->\n  <activity/>\n</application>\n"
+    );
+    assert_eq!(
+        application.to_xml_string(&FormatOptions::default()),
+        "<application>\n  <activity/>\n</application>\n"
+    );
+}
+
+#[test]
+fn test_apktool() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    let options = FormatOptions {
+        resolver: Some(Arc::new(|id| match id {
+            0x7f040000 => Some("string/app_name".to_string()),
+            _ => None,
+        })),
+        ..FormatOptions::apktool()
+    };
+
+    let text = xml.to_xml_string(&options);
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next(),
+        Some("<?xml version=\"1.0\" encoding=\"utf-8\" standalone=\"no\"?><manifest xmlns:android=\"http://schemas.android.com/apk/res/android\" android:versionCode=\"1\" android:versionName=\"1.0\" package=\"org.t0t0.androguard.TC\">")
+    );
+    assert_eq!(
+        lines.next(),
+        Some("    <application android:label=\"@string/app_name\" android:icon=\"@0x7f020000\" android:debuggable=\"true\">")
+    );
+}
+
+#[test]
+fn test_to_writer() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    let options = FormatOptions::default();
+
+    let mut output = Vec::new();
+    xml.to_writer(&mut output, &options).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        xml.to_xml_string(&options)
+    );
+
+    // Errors from the writer are passed on unchanged.
+    let mut full = [0u8; 16];
+    let error = xml.to_writer(&mut &mut full[..], &options).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_unknown_values() {
+    let mut e = Element::new("view");
+    // A dimension in unit 15, which does not exist.
+    let value = ResourceValue::new(0x05, 0x0000_010f).unwrap();
+    e.set_typed_attribute(None, "width", value);
+    let xml = XmlDocument::new(Node::Element(e));
+
+    let render = |unknown_values| {
+        let options = FormatOptions {
+            unknown_values,
+            xml_declaration: XmlDeclaration::Omit,
+            ..FormatOptions::default()
+        };
+        let mut output = Vec::new();
+        xml.to_writer(&mut output, &options)
+            .map(|()| String::from_utf8(output).unwrap())
+    };
+    assert_eq!(
+        render(UnknownValues::Hex).unwrap(),
+        "<view width=\"@0x0000010f\"/>\n"
+    );
+    assert_eq!(
+        render(UnknownValues::Empty).unwrap(),
+        "<view width=\"\"/>\n"
+    );
+    assert_eq!(
+        render(UnknownValues::Error).unwrap_err().kind(),
+        io::ErrorKind::InvalidData
+    );
+
+    let options = FormatOptions {
+        unknown_values: UnknownValues::Error,
+        ..FormatOptions::default()
+    };
+    assert!(xml.to_xml_string(&options).contains("@0x0000010f"));
+}
+
+#[test]
+fn test_self_closing() {
+    let mut permission = Element::new("uses-permission");
+    permission.set_attribute("android:name", "android.permission.INTERNET");
+    let mut manifest = Element::new("manifest");
+    manifest.get_children_mut().push(Node::Element(permission));
+    let xml = XmlDocument::new(Node::Element(manifest));
+
+    let mut options = FormatOptions {
+        xml_declaration: XmlDeclaration::Omit,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        xml.to_xml_string(&options),
+        "<manifest>\n  <uses-permission android:name=\"android.permission.INTERNET\"/>\n</manifest>\n"
+    );
+    options.self_closing = false;
+    assert_eq!(
+        xml.to_xml_string(&options),
+        "<manifest>\n  <uses-permission android:name=\"android.permission.INTERNET\"></uses-permission>\n</manifest>\n"
+    );
+}
+
+#[test]
+fn test_display() {
+    let mut permission = Element::new("uses-permission");
+    permission.set_attribute("android:name", "android.permission.INTERNET");
+    assert_eq!(
+        permission.to_string(),
+        permission.to_xml_string(&FormatOptions::default())
+    );
+
+    let mut manifest = Element::new("manifest");
+    manifest.get_children_mut().push(Node::Element(permission));
+    let xml = XmlDocument::new(Node::Element(manifest));
+    assert_eq!(
+        format!("{}", xml),
+        xml.to_xml_string(&FormatOptions::default())
+    );
+}
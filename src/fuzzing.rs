@@ -0,0 +1,64 @@
+//!Structured fuzzing support, available with the `fuzzing` feature.
+//!
+//!With the feature enabled, [BinaryXmlDocument] and the chunks it is made of
+//!implement [arbitrary::Arbitrary], so a fuzzer can generate documents
+//!chunk by chunk instead of mutating raw bytes. [roundtrip] drives the whole
+//!pipeline from such input, and is meant to be called from a
+//![cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) target:
+//!
+//!```rust,ignore
+//!#![no_main]
+//!use libfuzzer_sys::fuzz_target;
+//!
+//!fuzz_target!(|data: &[u8]| axmldecoder::fuzzing::roundtrip(data));
+//!```
+
+use arbitrary::{Arbitrary, Unstructured};
+use std::io::Cursor;
+
+use crate::{BinaryXmlDocument, FormatOptions, ParseConfig};
+
+///Generates a [BinaryXmlDocument] from `data`, writes it out and parses the
+///result with both the strict and the hardened configuration.
+///
+///Documents that parse strictly are encoded again, and the encoded bytes
+///must parse back into the same document. Any failure of that round trip
+///panics, as does any panic inside the parser or encoder.
+pub fn roundtrip(data: &[u8]) {
+    let document = match BinaryXmlDocument::arbitrary_take_rest(Unstructured::new(data)) {
+        Ok(document) => document,
+        Err(_) => return,
+    };
+
+    let mut bytes = Cursor::new(Vec::new());
+    if document.write_to(&mut bytes).is_err() {
+        return;
+    }
+    let bytes = bytes.into_inner();
+
+    let _ = crate::parse_with_config(&mut Cursor::new(&bytes), &ParseConfig::hardened());
+    let (parsed, _) =
+        match crate::parse_with_config(&mut Cursor::new(&bytes), &ParseConfig::strict()) {
+            Ok(parsed) => parsed,
+            Err(_) => return,
+        };
+
+    let mut encoded = Cursor::new(Vec::new());
+    crate::encode(&parsed, &mut encoded).expect("failed to encode a parsed document");
+    let reparsed = crate::parse(&mut Cursor::new(encoded.get_ref()))
+        .expect("failed to parse an encoded document");
+
+    let options = FormatOptions::default();
+    assert_eq!(
+        parsed.to_xml_string(&options),
+        reparsed.to_xml_string(&options)
+    );
+}
+
+#[test]
+fn test_roundtrip() {
+    for entry in std::fs::read_dir("examples").unwrap() {
+        roundtrip(&std::fs::read(entry.unwrap().path()).unwrap());
+    }
+    roundtrip(&[0; 256]);
+}
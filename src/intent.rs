@@ -0,0 +1,66 @@
+use crate::manifest::child_elements;
+use crate::xml::Element;
+
+///The actions, categories and data of an `intent-filter` element, or of an
+///`intent` element inside `queries`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IntentFilter {
+    ///Names of the `action` children.
+    pub actions: Vec<String>,
+    ///Names of the `category` children.
+    pub categories: Vec<String>,
+    ///The `data` children.
+    pub data: Vec<IntentData>,
+}
+
+///The attributes of a `data` element, each `None` if not given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct IntentData {
+    pub scheme: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<String>,
+    pub path: Option<String>,
+    pub path_prefix: Option<String>,
+    pub path_pattern: Option<String>,
+    pub mime_type: Option<String>,
+}
+
+impl IntentFilter {
+    ///Reads the `action`, `category` and `data` children of `element`.
+    pub fn from_element(element: &Element) -> Self {
+        let mut filter = Self::default();
+        for child in child_elements(element) {
+            let name = child.android_value("name").map(str::to_string);
+            match child.get_tag() {
+                "action" => filter.actions.extend(name),
+                "category" => filter.categories.extend(name),
+                "data" => {
+                    let value = |name| child.android_value(name).map(str::to_string);
+                    filter.data.push(IntentData {
+                        scheme: value("scheme"),
+                        host: value("host"),
+                        port: value("port"),
+                        path: value("path"),
+                        path_prefix: value("pathPrefix"),
+                        path_pattern: value("pathPattern"),
+                        mime_type: value("mimeType"),
+                    });
+                }
+                _ => (),
+            }
+        }
+        filter
+    }
+
+    ///Returns whether the filter lists `action`.
+    pub fn has_action(&self, action: &str) -> bool {
+        self.actions.iter().any(|a| a == action)
+    }
+
+    ///Returns whether the filter lists `category`.
+    pub fn has_category(&self, category: &str) -> bool {
+        self.categories.iter().any(|c| c == category)
+    }
+}
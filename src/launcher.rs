@@ -0,0 +1,210 @@
+use crate::aliases::intent_filters;
+use crate::intent::IntentFilter;
+use crate::manifest::{child_elements, Manifest};
+use crate::xml::Element;
+
+pub(crate) const ACTION_MAIN: &str = "android.intent.action.MAIN";
+pub(crate) const CATEGORY_LAUNCHER: &str = "android.intent.category.LAUNCHER";
+pub(crate) const CATEGORY_LEANBACK_LAUNCHER: &str = "android.intent.category.LEANBACK_LAUNCHER";
+
+///An activity or activity alias that a launcher shows, as returned by
+///[Manifest::launchable_activities].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LaunchableActivity {
+    ///The fully qualified name of the activity or alias.
+    pub name: String,
+    ///The fully qualified name of the activity that is started, which is
+    ///`name` unless this is an alias.
+    pub activity: String,
+    ///The label, taken from the alias, the activity or the application,
+    ///whichever sets one first.
+    pub label: Option<String>,
+    ///The icon, looked up the same way as `label`.
+    pub icon: Option<String>,
+    ///Whether it is shown by phone and tablet launchers, through the
+    ///`LAUNCHER` category.
+    pub launcher: bool,
+    ///Whether it is shown by TV launchers, through the `LEANBACK_LAUNCHER`
+    ///category.
+    pub leanback: bool,
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the activities and aliases with an intent filter for the
+    ///`MAIN` action and the `LAUNCHER` or `LEANBACK_LAUNCHER` category, in
+    ///document order.
+    ///
+    ///Components that are disabled, or whose application is, are left out,
+    ///as are aliases whose target activity is missing or disabled. An alias
+    ///is launchable through its own intent filters or those of its target.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let launchable = xml.manifest().unwrap().launchable_activities();
+    ///assert_eq!(launchable[0].name, "org.t0t0.androguard.TC.TCActivity");
+    ///assert!(launchable[0].launcher);
+    ///```
+    pub fn launchable_activities(&self) -> Vec<LaunchableActivity> {
+        let application = match self.application() {
+            Some(application) if application.android_bool("enabled") != Some(false) => application,
+            _ => return Vec::new(),
+        };
+        let aliases = self.activity_aliases();
+        let mut aliases = aliases.iter();
+
+        let mut launchable = Vec::new();
+        for e in child_elements(application) {
+            let (name, activity, filters, target) = match e.get_tag() {
+                "activity" if e.android_bool("enabled") != Some(false) => {
+                    let name = self.resolve_class_name(e.android_value("name").unwrap_or_default());
+                    (name.clone(), name, intent_filters(e), None)
+                }
+                "activity-alias" => {
+                    // activity_aliases lists the aliases in the same order.
+                    let resolved = match aliases.next().and_then(|a| self.resolve_alias(a)) {
+                        Some(resolved) if resolved.enabled => resolved,
+                        _ => continue,
+                    };
+                    let target = self.find_component(&resolved.activity).map(|(e, _)| e);
+                    (
+                        resolved.alias,
+                        resolved.activity,
+                        resolved.intent_filters,
+                        target,
+                    )
+                }
+                _ => continue,
+            };
+
+            let launcher = has_launcher_filter(&filters, CATEGORY_LAUNCHER);
+            let leanback = has_launcher_filter(&filters, CATEGORY_LEANBACK_LAUNCHER);
+            if !launcher && !leanback {
+                continue;
+            }
+            let inherited = |attribute: &str| {
+                [Some(e), target, Some(application)]
+                    .iter()
+                    .flatten()
+                    .find_map(|e: &&Element| e.android_value(attribute))
+                    .map(str::to_string)
+            };
+            launchable.push(LaunchableActivity {
+                name,
+                activity,
+                label: inherited("label"),
+                icon: inherited("icon"),
+                launcher,
+                leanback,
+            });
+        }
+        launchable
+    }
+}
+
+///Whether one of `filters` matches the `MAIN` action with `category`.
+pub(crate) fn has_launcher_filter(filters: &[IntentFilter], category: &str) -> bool {
+    filters
+        .iter()
+        .any(|f| f.has_action(ACTION_MAIN) && f.has_category(category))
+}
+
+#[test]
+fn test_launchable_activities() {
+    use crate::test_support::{element, manifest_document};
+    use crate::Node;
+
+    fn component(tag: &str, attributes: &[(&str, &str)], categories: &[&str]) -> Element {
+        let filters = if categories.is_empty() {
+            Vec::new()
+        } else {
+            let action = element("action", &[("android:name", ACTION_MAIN)], vec![]);
+            let categories = categories
+                .iter()
+                .map(|name| element("category", &[("android:name", name)], vec![]));
+            vec![element(
+                "intent-filter",
+                &[],
+                std::iter::once(action).chain(categories).collect(),
+            )]
+        };
+        element(tag, attributes, filters)
+    }
+
+    let application = element(
+        "application",
+        &[("android:label", "App")],
+        vec![
+            component(
+                "activity",
+                &[("android:name", ".Main"), ("android:icon", "@0x7f020000")],
+                &[CATEGORY_LAUNCHER, CATEGORY_LEANBACK_LAUNCHER],
+            ),
+            component(
+                "activity",
+                &[("android:name", ".Disabled"), ("android:enabled", "false")],
+                &[CATEGORY_LAUNCHER],
+            ),
+            component("activity", &[("android:name", ".Hidden")], &[]),
+            component(
+                "activity-alias",
+                &[
+                    ("android:name", ".Alias"),
+                    ("android:targetActivity", ".Hidden"),
+                    ("android:label", "Alias"),
+                ],
+                &[CATEGORY_LAUNCHER],
+            ),
+            component(
+                "activity-alias",
+                &[
+                    ("android:name", ".DisabledTarget"),
+                    ("android:targetActivity", ".Disabled"),
+                ],
+                &[],
+            ),
+            component(
+                "activity-alias",
+                &[
+                    ("android:name", ".Dangling"),
+                    ("android:targetActivity", ".Missing"),
+                ],
+                &[CATEGORY_LAUNCHER],
+            ),
+        ],
+    );
+    let mut xml = manifest_document(vec![application]);
+
+    let launchable = xml.manifest().unwrap().launchable_activities();
+    assert_eq!(
+        launchable,
+        [
+            LaunchableActivity {
+                name: "com.example.Main".to_string(),
+                activity: "com.example.Main".to_string(),
+                label: Some("App".to_string()),
+                icon: Some("@0x7f020000".to_string()),
+                launcher: true,
+                leanback: true,
+            },
+            LaunchableActivity {
+                name: "com.example.Alias".to_string(),
+                activity: "com.example.Hidden".to_string(),
+                label: Some("Alias".to_string()),
+                icon: None,
+                launcher: true,
+                leanback: false,
+            },
+        ]
+    );
+
+    if let Some(Node::Element(root)) = xml.get_root_mut() {
+        if let Node::Element(application) = &mut root.get_children_mut()[0] {
+            application.set_attribute("android:enabled", "false");
+        }
+    }
+    assert!(xml.manifest().unwrap().launchable_activities().is_empty());
+}
@@ -1,9 +1,9 @@
 //!Decoder for the binary XML format used by Android.
 //!
 //!This library implements the minimal amount of parsing required obtain
-//!useful information from a binary `AndroidManifest.xml`. It does not
-//!support parsing generic binary XML documents and does not have
-//!support for decoding resource identifiers. In return, the compiled
+//!useful information from a binary `AndroidManifest.xml` or any other
+//!compiled XML resource, such as layouts. It does not have support for
+//!resolving resource identifiers to names. In return, the compiled
 //!footprint of the library is _much_ lighter as it does not have to
 //!link in Android's `resources.arsc` file.
 //!
@@ -14,26 +14,176 @@
 //!
 //!Please file an issue with the relevant binary `AndroidManifest.xml` if
 //!if any issues are encountered.
+//!
+//!# Optional features
+//!
+//!* `serde`: implements `Serialize` for the parsed document.
+//!* `wasm`: exposes [wasm::parse_bytes] through `wasm-bindgen` for use
+//!  from JavaScript.
+//!* `python`: builds a Python extension module with `parse` and `encode`
+//!  functions. See `pyproject.toml` for building wheels with maturin.
+//!* `zip`: adds [parse_apk] to read the manifest straight out of an APK.
+//!* `proto`: adds [parse_proto] for the protobuf XML format of app
+//!  bundles, and [parse_aab] to read the manifest straight out of an `.aab`.
+//!  Implies `zip`.
+//!* `tokio`: adds [parse_async] for reading from a `tokio::io::AsyncRead`.
+//!* `minidom`: adds [XmlDocument::to_minidom] and [Element::to_minidom] for
+//!  converting a parsed document into a `minidom::Element` tree.
+//!* `fuzzing`: implements `arbitrary::Arbitrary` for [BinaryXmlDocument] and
+//!  adds the [fuzzing::roundtrip] entry point for structured fuzzing.
+//!* `tracing`: emits `tracing` spans and events for every parse, chunk read
+//!  and recovery from malformed input.
+//!* `rayon`: decodes large UTF-16 string pools on the `rayon` thread pool.
+//!* `mmap`: makes [parse_file] memory map large files instead of reading
+//!  them.
+//!* `symbols`: adds [FormatOptions::symbolic_values] for writing framework
+//!  enum and flag attributes such as `android:launchMode` by name,
+//!  [ResourceValue::symbolic] and the [symbols] module with the tables
+//!  behind them.
+//!* `permission-levels`: adds [platform_permission], a table of the
+//!  protection levels and API levels of platform permissions, and
+//!  [Manifest::dangerous_permissions].
+//!* `test-support`: adds the [test_support] module, with random document
+//!  generators and [test_support::assert_roundtrip] for property testing
+//!  code built on the encoder.
+//!* `text`: adds [parse_text] for textual XML and [parse_auto], which
+//!  accepts either form.
+//!* `cli`: builds the `axmldec` command-line tool, which prints, queries,
+//!  edits and encodes binary XML files.
 
+mod aliases;
+#[cfg(feature = "zip")]
+mod apk;
+mod badging;
 mod binaryxml;
+mod canonical;
+mod chunks;
+mod compat;
+mod config;
+mod convert;
+mod diagnostics;
+#[cfg(feature = "minidom")]
+mod dom;
+mod dump;
+mod edit;
+mod encoder;
+mod events;
+mod file;
+mod form_factors;
+mod format;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+mod intent;
+mod launcher;
+pub mod lint;
+mod manifest;
+mod memory;
+mod merge;
+mod metadata;
+mod node_id;
+mod path;
+mod permissions;
+#[cfg(feature = "permission-levels")]
+mod platform_permissions;
+#[cfg(feature = "proto")]
+mod proto;
+mod providers;
+#[cfg(feature = "python")]
+mod python;
+mod queries;
+mod resolver;
 mod resource_value;
+mod security;
+mod stats;
 mod stringpool;
+mod subtree;
+#[cfg(feature = "symbols")]
+pub mod symbols;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+pub mod testing;
+#[cfg(feature = "text")]
+mod text;
+mod validate;
+mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 mod xml;
 
 use byteorder::ByteOrder;
 use byteorder::LittleEndian;
 use byteorder::WriteBytesExt;
-use std::io::{Read, Seek, Write};
+use std::io::{Cursor, Read, Seek, Write};
 use thiserror::Error;
-use std::fs::File;
 
+use crate::binaryxml::{InputExtent, PartialBinaryXmlDocument};
+use crate::encoder::Encoder;
+
+pub use crate::aliases::{ActivityAlias, ResolvedAlias};
+#[cfg(feature = "zip")]
+pub use crate::apk::parse_apk;
 pub use crate::binaryxml::BinaryXmlDocument;
-pub use crate::xml::{Cdata, Element, Node, XmlDocument};
+pub use crate::chunks::{read_chunks, RawChunk};
+pub use crate::compat::{CompatFinding, CompatKind};
+pub use crate::config::{DuplicateAttributes, EncodeConfig, FloatFormat, ParseConfig};
+pub use crate::diagnostics::Warning;
+pub use crate::edit::EditError;
+pub use crate::events::{
+    AttributeEvent, EndElementEvent, NamespaceEvent, StartElementEvent, TextEvent, XmlEvent,
+};
+pub use crate::file::{encode_file, parse_file};
+pub use crate::form_factors::FormFactor;
+pub use crate::format::{
+    AttributeOrder, Escaping, FormatOptions, InvalidCharacters, UnknownValues, Whitespace,
+    XmlDeclaration,
+};
+pub use crate::intent::{IntentData, IntentFilter};
+pub use crate::launcher::LaunchableActivity;
+pub use crate::manifest::{format_gl_es_version, ComponentKind, Manifest};
+pub use crate::merge::{merge, MergeError, MergePolicy};
+pub use crate::metadata::{ChunkInfo, DocumentMetadata, ResourceMapEntry};
+pub use crate::node_id::NodeId;
+pub use crate::path::{ElementPath, PathError, Selector};
+pub use crate::permissions::{Permission, PermissionGroup, PermissionTree, ProtectionLevel};
+#[cfg(feature = "permission-levels")]
+pub use crate::platform_permissions::{platform_permission, PlatformPermission};
+#[cfg(feature = "proto")]
+pub use crate::proto::{parse_aab, parse_proto};
+pub use crate::providers::{PathPattern, PathPermission, Provider};
+pub use crate::queries::Queries;
+pub use crate::resolver::{CachedResolver, ResourceResolver};
+pub use crate::resource_value::{complex, ResourceValue, ResourceValueType};
+pub use crate::security::{analyze_security, ExposedComponent, SecurityReport, TaskHijackingRisk};
+pub use crate::stats::ParseStats;
+pub use crate::stringpool::{ResStringPool, StringPoolSpan};
+pub use crate::subtree::Subtree;
+#[cfg(feature = "text")]
+pub use crate::text::{parse_auto, parse_text};
+pub use crate::validate::{ValidationIssue, ValidationKind};
+pub use crate::xml::{
+    framework_attribute_id, framework_attribute_name, Cdata, Element, Node, TypedAttribute,
+    XmlDocument, ANDROID_NAMESPACE,
+};
 
+///Errors from reading a document. New variants may be added as more
+///kinds of malformed input are told apart.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ParseError {
-    #[error("invalid file")]
-    InvalidFile,
+    #[error("not a binary XML document, found chunk type {found:#06x} instead of 0x0003")]
+    BadMagic { found: u16 },
+
+    #[error("unexpected chunk type {found:#06x} at offset {offset}")]
+    UnexpectedChunkType { found: u16, offset: u64 },
+
+    #[error("{0} does not fit in its chunk")]
+    SizeMismatch(&'static str),
+
+    #[error("unsupported resource value type {0:#04x}")]
+    UnsupportedResourceValueType(u8),
+
+    #[error("document has no root element")]
+    NoRootElement,
 
     #[error("missing StringPool chunk")]
     MissingStringPoolChunk,
@@ -53,6 +203,43 @@ pub enum ParseError {
     #[error("Unknown resource string: {0}")]
     UnknownResourceString(u32),
 
+    #[error("chunk at offset {offset} has invalid header size {header_size} for size {size}")]
+    InvalidChunkHeaderSize {
+        offset: u64,
+        header_size: u16,
+        size: u32,
+    },
+
+    #[error("chunk at offset {offset} of size {size} exceeds the {remaining} remaining bytes")]
+    ChunkOutOfBounds {
+        offset: u64,
+        size: u32,
+        remaining: u64,
+    },
+
+    #[error("chunk at offset {offset} overruns its declared size {size}")]
+    ChunkOverrun { offset: u64, size: u32 },
+
+    #[error("end element without a matching start element")]
+    UnmatchedEndElement,
+
+    #[error("end element {found} on line {line} does not match {expected} opened on line {expected_line}")]
+    MismatchedEndElement {
+        expected: String,
+        expected_line: u32,
+        found: String,
+        line: u32,
+    },
+
+    #[error("Cdata outside of any element")]
+    CdataOutsideElement,
+
+    #[error("element {element} has attribute {attribute} more than once")]
+    DuplicateAttribute { element: String, attribute: String },
+
+    #[error("{limit} exceeds the configured maximum of {max}")]
+    LimitExceeded { limit: &'static str, max: usize },
+
     #[error(transparent)]
     Utf8StringParseError(std::string::FromUtf8Error),
 
@@ -61,6 +248,52 @@ pub enum ParseError {
 
     #[error(transparent)]
     IoError(std::io::Error),
+
+    #[error("reading chunk of type {chunk_type:#06x} at offset {offset}: {source}")]
+    ChunkIo {
+        chunk_type: u16,
+        offset: u64,
+        source: std::io::Error,
+    },
+
+    #[error("{}: {source}", .path.display())]
+    File {
+        path: std::path::PathBuf,
+        source: Box<ParseError>,
+    },
+
+    #[cfg(feature = "zip")]
+    #[error(transparent)]
+    ZipError(zip::result::ZipError),
+
+    #[cfg(feature = "proto")]
+    #[error(transparent)]
+    ProtoError(prost::DecodeError),
+
+    #[cfg(feature = "text")]
+    #[error(transparent)]
+    TextXmlError(roxmltree::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum EncodeError {
+    #[error("element {0} has more attributes than can be encoded")]
+    TooManyAttributes(String),
+
+    #[error(transparent)]
+    IoError(std::io::Error),
+
+    #[error("{}: {source}", .path.display())]
+    File {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("encoded document cannot be parsed: {0}")]
+    Unreadable(ParseError),
+
+    #[error("encoded document differs at {path}: {reason}")]
+    Verification { path: String, reason: String },
 }
 
 ///Parses an Android binary XML and returns a [XmlDocument] object.
@@ -73,34 +306,304 @@ pub enum ParseError {
 ///parse(&mut f)?;
 ///# Ok::<(), ParseError>(())
 ///```
-pub fn parse<F: Read + Seek>(input: &mut F) -> Result<XmlDocument, ParseError> {
-    let binaryxml = BinaryXmlDocument::read_from_file(input)?;
+///
+///The input is only ever read forward and never past the end of the
+///document, so it can be a stream such as standard input or an entry of a
+///zip archive. Each chunk is read into memory before it is decoded.
+pub fn parse<F: Read>(input: &mut F) -> Result<XmlDocument, ParseError> {
+    Ok(parse_with_config(input, &ParseConfig::default())?.0)
+}
 
-    //let mut out = File::create("test.xml").unwrap();
-    // binaryxml.write_to_file(&mut out).unwrap();
+///Parses an Android binary XML from an asynchronous reader.
+///
+///The parser itself is synchronous, so the whole document is read into
+///memory before it is parsed.
+///
+///```rust
+///use axmldecoder::parse_async;
+///# use axmldecoder::ParseError;
+///# async fn example() -> Result<(), ParseError> {
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let data = std::fs::read(manifest_file).unwrap();
+///let xml = parse_async(&mut data.as_slice()).await?;
+///# Ok(())
+///# }
+///```
+#[cfg(feature = "tokio")]
+pub async fn parse_async<R: tokio::io::AsyncRead + Unpin>(
+    input: &mut R,
+) -> Result<XmlDocument, ParseError> {
+    use tokio::io::AsyncReadExt;
+
+    let mut data = Vec::new();
+    input
+        .read_to_end(&mut data)
+        .await
+        .map_err(ParseError::IoError)?;
+
+    let extent = InputExtent {
+        start: 0,
+        len: Some(data.len() as u64),
+    };
+    let config = ParseConfig::default();
+    Ok(parse_document(&mut data.as_slice(), extent, &config)?.0)
+}
+
+///Returns whether `data` starts like an Android binary XML document, with
+///an XML chunk header, as opposed to textual XML.
+///
+///Only the header is looked at, so the document may still fail to parse.
+///
+///```rust
+///use axmldecoder::is_binary_xml;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///assert!(is_binary_xml(&std::fs::read(manifest_file).unwrap()));
+///assert!(!is_binary_xml(b"<?xml version=\"1.0\"?><manifest/>"));
+///```
+pub fn is_binary_xml(data: &[u8]) -> bool {
+    data.len() >= usize::from(binaryxml::CHUNK_HEADER_SIZE)
+        && LittleEndian::read_u16(&data[0..2]) == binaryxml::ResourceType::Xml as u16
+        && LittleEndian::read_u32(&data[4..8]) >= u32::from(binaryxml::CHUNK_HEADER_SIZE)
+}
+
+///Parses an Android binary XML in hardened mode.
+///
+///Hardened mode mirrors how the Android runtime treats known obfuscation
+///tricks used by "protected" APKs instead of rejecting the document:
+///
+///* inflated attribute counts are clamped to what fits in the chunk,
+///* attributes and text nodes with unresolvable string indices are dropped,
+///* repeated namespace declarations keep the first prefix for a URI,
+///* chunk `header_size` values are not trusted,
+///* framework attributes are named by their resource id rather than by
+///  their (possibly forged) name and namespace strings.
+///
+///```rust
+///use axmldecoder::parse_hardened;
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest_NamespaceInAttributeName.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///parse_hardened(&mut f)?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_hardened<F: Read>(input: &mut F) -> Result<XmlDocument, ParseError> {
+    Ok(parse_with_diagnostics(input)?.0)
+}
+
+///Parses an Android binary XML in hardened mode, also returning a [Warning]
+///for everything that had to be skipped, dropped or substituted.
+///
+///An empty list of warnings means the document was read exactly as stored.
+///
+///```rust
+///use axmldecoder::parse_with_diagnostics;
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let (xml, warnings) = parse_with_diagnostics(&mut f)?;
+///for warning in &warnings {
+///    eprintln!("warning: {}", warning);
+///}
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_with_diagnostics<F: Read>(
+    input: &mut F,
+) -> Result<(XmlDocument, Vec<Warning>), ParseError> {
+    parse_with_config(input, &ParseConfig::hardened())
+}
 
-    XmlDocument::new(
+///Parses an Android binary XML using the given [ParseConfig], returning the
+///document together with a [Warning] for everything the configuration
+///allowed to be skipped, dropped or substituted.
+///
+///```rust
+///use axmldecoder::{parse_with_config, ParseConfig};
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let config = ParseConfig {
+///    max_string_pool_size: Some(1 << 20),
+///    ..ParseConfig::strict()
+///};
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let (xml, warnings) = parse_with_config(&mut f, &config)?;
+///assert!(warnings.is_empty());
+///# Ok::<(), ParseError>(())
+///```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, err)
+)]
+pub fn parse_with_config<F: Read>(
+    input: &mut F,
+    config: &ParseConfig,
+) -> Result<(XmlDocument, Vec<Warning>), ParseError> {
+    let (document, warnings, _) = parse_document(input, InputExtent::default(), config)?;
+    Ok((document, warnings))
+}
+
+///Parses an Android binary XML using the given [ParseConfig], returning the
+///document together with [ParseStats] describing the structure of the file.
+///
+///The input is measured first, which takes seeking. This also lets chunks
+///that claim to extend past the end of the input be rejected before they are
+///read.
+///
+///```rust
+///use axmldecoder::{parse_with_stats, ParseConfig};
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let (xml, stats) = parse_with_stats(&mut f, &ParseConfig::hardened())?;
+///println!(
+///    "{} strings, {} attributes, {} trailing bytes",
+///    stats.string_count,
+///    stats.attribute_count,
+///    stats.file_size - stats.bytes_consumed
+///);
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_with_stats<F: Read + Seek>(
+    input: &mut F,
+    config: &ParseConfig,
+) -> Result<(XmlDocument, ParseStats), ParseError> {
+    let extent = InputExtent::measure(input)?;
+    let (document, _, stats) = parse_document(input, extent, config)?;
+    Ok((document, stats))
+}
+
+pub(crate) fn parse_document<F: Read>(
+    input: &mut F,
+    extent: InputExtent,
+    config: &ParseConfig,
+) -> Result<(XmlDocument, Vec<Warning>, ParseStats), ParseError> {
+    let (binaryxml, mut warnings, stats, metadata) =
+        BinaryXmlDocument::read_with_config(input, extent, config)?;
+
+    let (mut document, tree_warnings) = XmlDocument::from_binary(
         binaryxml.elements,
         binaryxml.string_pool,
         binaryxml.resource_map,
-    )
+        config,
+    )?;
+    warnings.extend(tree_warnings);
+    document.metadata = Some(metadata);
+
+    Ok((document, warnings, stats))
+}
+
+///Parses as much of an Android binary XML as possible.
+///
+///Unlike [parse], this never discards the tree built so far. If the input
+///is truncated or otherwise fails to parse, the partially built
+///[XmlDocument] is returned together with the error that stopped parsing.
+///Elements that were still open at that point are closed implicitly.
+///
+///```rust
+///use axmldecoder::parse_partial;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let data = std::fs::read(manifest_file).unwrap();
+///let mut truncated = std::io::Cursor::new(&data[..data.len() - 30]);
+///let (xml, error) = parse_partial(&mut truncated);
+///assert!(xml.get_root().is_some());
+///assert!(error.is_some());
+///```
+pub fn parse_partial<F: Read>(input: &mut F) -> (XmlDocument, Option<ParseError>) {
+    let config = ParseConfig::default();
+    let (binaryxml, read_error) =
+        PartialBinaryXmlDocument::read_from_file(input, InputExtent::default(), &config);
+    let (document, build_error) = XmlDocument::new_partial(binaryxml, &config);
+
+    (document, read_error.or(build_error))
+}
+
+///Encodes a [XmlDocument] into Android's binary XML format, returning the
+///number of bytes written.
+///
+///Attributes keep the binary type they were parsed with, so a parsed
+///document can be written back without Android noticing a difference.
+///
+///Encoding is deterministic: the same document always encodes to the same
+///bytes, on any platform. Strings are pooled in the order they are first
+///used and attributes keep their order apart from the sorting by resource
+///id Android requires, so re-signing pipelines can rely on reproducible
+///output.
+///
+///```rust
+///use axmldecoder::{encode, parse};
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///
+///let mut output = std::io::Cursor::new(Vec::new());
+///encode(&xml, &mut output).unwrap();
+///```
+pub fn encode<F: Write + Seek>(
+    document: &XmlDocument,
+    output: &mut F,
+) -> Result<usize, EncodeError> {
+    encode_with_config(document, output, &EncodeConfig::default())
 }
 
-fn read_u8<F: Read + Seek>(input: &mut F) -> Result<u8, ParseError> {
+///Encodes a [XmlDocument] into Android's binary XML format using the given
+///[EncodeConfig]. Returns the number of bytes written.
+pub fn encode_with_config<F: Write + Seek>(
+    document: &XmlDocument,
+    output: &mut F,
+    config: &EncodeConfig,
+) -> Result<usize, EncodeError> {
+    let binaryxml = Encoder::encode(document, config)?;
+    if !config.verify {
+        return binaryxml.write_to(output).map_err(EncodeError::IoError);
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    binaryxml
+        .write_to(&mut data)
+        .map_err(EncodeError::IoError)?;
+    data.set_position(0);
+    let reparsed = parse(&mut data).map_err(EncodeError::Unreadable)?;
+    if let Some((path, reason)) = verify::first_difference(document, &reparsed) {
+        return Err(EncodeError::Verification { path, reason });
+    }
+
+    let data = data.into_inner();
+    output.write_all(&data).map_err(EncodeError::IoError)?;
+    Ok(data.len())
+}
+
+///Encodes a [XmlDocument] into Android's binary XML format and returns the
+///encoded bytes.
+///
+///```rust
+///use axmldecoder::{encode_to_vec, parse};
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///
+///let bytes = encode_to_vec(&xml).unwrap();
+///assert_eq!(&bytes[..2], &[0x03, 0x00]);
+///```
+pub fn encode_to_vec(document: &XmlDocument) -> Result<Vec<u8>, EncodeError> {
+    let mut output = Cursor::new(Vec::new());
+    encode(document, &mut output)?;
+    Ok(output.into_inner())
+}
+
+fn read_u8<F: Read>(input: &mut F) -> Result<u8, ParseError> {
     let mut buf = [0; 1];
     input.read_exact(&mut buf).map_err(ParseError::IoError)?;
 
     Ok(buf[0])
 }
 
-fn read_u16<F: Read + Seek>(input: &mut F) -> Result<u16, ParseError> {
+fn read_u16<F: Read>(input: &mut F) -> Result<u16, ParseError> {
     let mut buf = [0; 2];
     input.read_exact(&mut buf).map_err(ParseError::IoError)?;
 
     Ok(LittleEndian::read_u16(&buf))
 }
 
-fn read_u32<F: Read + Seek>(input: &mut F) -> Result<u32, ParseError> {
+fn read_u32<F: Read>(input: &mut F) -> Result<u32, ParseError> {
     let mut buf = [0; 4];
     input.read_exact(&mut buf).map_err(ParseError::IoError)?;
 
@@ -128,7 +631,6 @@ mod tests {
     use std::fs::File;
     use std::path::PathBuf;
 
-    /*
     #[test]
     fn test_parse() {
         let mut examples = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -137,15 +639,933 @@ mod tests {
         for entry in std::fs::read_dir(examples).unwrap() {
             let entry = entry.unwrap();
             let mut f = File::open(entry.path()).unwrap();
-            parse(&mut f).expect(&format!("{} failed to parse", entry.path().display()));
+            parse(&mut f)
+                .unwrap_or_else(|e| panic!("{} failed to parse: {}", entry.path().display(), e));
         }
     }
-    */
 
+    #[test]
+    fn test_get_elements_by_tag_name() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let xml = parse(&mut f).unwrap();
+
+        assert_eq!(xml.get_elements_by_tag_name("manifest").len(), 1);
+        let filters = xml.get_elements_by_tag_name("intent-filter");
+        assert_eq!(filters.len(), 1);
+        let children: Vec<_> = filters[0]
+            .get_elements_by_tag_name("category")
+            .iter()
+            .map(|e| e.get_attribute("android:name").unwrap())
+            .collect();
+        assert_eq!(children, ["android.intent.category.LAUNCHER"]);
+        assert!(xml.get_elements_by_tag_name("service").is_empty());
+    }
+
+    #[test]
+    fn test_typed_attributes() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let xml = parse(&mut f).unwrap();
+        let application = xml.find_first(|e| e.get_tag() == "application").unwrap();
+
+        let icon = application
+            .typed_attributes()
+            .find(|a| a.qualified_name == "android:icon")
+            .unwrap();
+        assert_eq!(icon.namespace, Some(xml::ANDROID_NAMESPACE));
+        assert_eq!(icon.name, "icon");
+        assert_eq!(icon.value, &ResourceValue::reference(0x7f020000));
+        assert_eq!(icon.raw_value, None);
+        assert_eq!(icon.resource_id, Some(0x01010002));
+
+        let mut e = Element::new("meta-data");
+        e.set_attribute("android:value", "text");
+        let attr = e.typed_attributes().next().unwrap();
+        assert_eq!(attr.namespace, None);
+        assert_eq!(attr.raw_value, Some("text"));
+        assert_eq!(attr.resource_id, None);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let xml = parse(&mut f).unwrap();
+
+        let tags: Vec<_> = xml.find_all(|_| true).map(Element::get_tag).collect();
+        assert_eq!(
+            tags,
+            [
+                "manifest",
+                "application",
+                "activity",
+                "intent-filter",
+                "action",
+                "category"
+            ]
+        );
+
+        let mut visited = 0;
+        let first = xml.find_first(|e| {
+            visited += 1;
+            e.get_tag() == "activity"
+        });
+        assert_eq!(first.map(Element::get_tag), Some("activity"));
+        assert_eq!(visited, 3);
+        assert!(xml.find_first(|e| e.get_tag() == "service").is_none());
+    }
+
+    #[test]
+    fn test_parse_hardened() {
+        let manifest_file = "examples/AndroidManifest_NamespaceInAttributeName.xml";
+        let mut f = File::open(manifest_file).unwrap();
+        let xml = parse_hardened(&mut f).unwrap();
+
+        let root = match xml.get_root() {
+            Some(Node::Element(e)) => e,
+            _ => panic!("missing root element"),
+        };
+        let uses_sdk = match &root.get_children()[0] {
+            Node::Element(e) => e,
+            _ => panic!("expected uses-sdk element"),
+        };
+        assert!(uses_sdk.get_attribute("android:minSdkVersion").is_some());
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let (_, warnings) = parse_with_diagnostics(&mut f).unwrap();
+        assert!(warnings.is_empty());
+
+        let mut f = File::open("examples/AndroidManifestDoubleNamespace.xml").unwrap();
+        let (_, warnings) = parse_with_diagnostics(&mut f).unwrap();
+        assert!(matches!(
+            warnings.as_slice(),
+            [Warning::DuplicateNamespace { prefix, .. }] if prefix == "andorid"
+        ));
+    }
+
+    #[test]
+    fn test_parse_with_config_limits() {
+        let config = ParseConfig {
+            max_depth: Some(1),
+            ..ParseConfig::default()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        assert!(matches!(
+            parse_with_config(&mut f, &config),
+            Err(ParseError::LimitExceeded {
+                limit: "nesting depth",
+                max: 1
+            })
+        ));
+
+        let config = ParseConfig {
+            max_string_pool_size: Some(16),
+            ..ParseConfig::hardened()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        assert!(matches!(
+            parse_with_config(&mut f, &config),
+            Err(ParseError::LimitExceeded {
+                limit: "string pool size",
+                ..
+            })
+        ));
+
+        let config = ParseConfig {
+            max_elements: Some(2),
+            ..ParseConfig::hardened()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        assert!(matches!(
+            parse_with_config(&mut f, &config),
+            Err(ParseError::LimitExceeded {
+                limit: "element count",
+                max: 2
+            })
+        ));
+
+        let config = ParseConfig {
+            max_attributes: Some(1),
+            ..ParseConfig::default()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        assert!(matches!(
+            parse_with_config(&mut f, &config),
+            Err(ParseError::LimitExceeded {
+                limit: "attributes per element",
+                max: 1
+            })
+        ));
+
+        let config = ParseConfig {
+            max_depth: Some(8),
+            max_elements: Some(100),
+            max_attributes: Some(16),
+            ..ParseConfig::default()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        assert!(parse_with_config(&mut f, &config).is_ok());
+    }
+
+    #[test]
+    fn test_parse_mismatched_end_element() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let mut binaryxml = BinaryXmlDocument::read_from(&mut f).unwrap();
+        // Dropping the end of <action> leaves it open when <intent-filter>
+        // ends.
+        let events: Vec<XmlEvent> = binaryxml
+            .events()
+            .filter(|event| match event {
+                XmlEvent::EndElement(end) => {
+                    binaryxml.string_pool().get(end.name).unwrap().as_str() != "action"
+                }
+                _ => true,
+            })
+            .collect();
+        binaryxml.set_events(events);
+        let mut data = Cursor::new(Vec::new());
+        binaryxml.write_to(&mut data).unwrap();
+
+        data.set_position(0);
+        match parse(&mut data) {
+            Err(ParseError::MismatchedEndElement {
+                expected, found, ..
+            }) => {
+                assert_eq!(expected, "action");
+                assert_eq!(found, "intent-filter");
+            }
+            other => panic!("unexpected result {:?}", other.map(|_| ())),
+        }
+
+        data.set_position(0);
+        let (xml, warnings) = parse_with_diagnostics(&mut data).unwrap();
+        assert!(matches!(
+            warnings.as_slice(),
+            [Warning::MismatchedEndElement { expected, found, .. }]
+                if expected == "action" && found == "intent-filter"
+        ));
+        let filter = xml.find_first(|e| e.get_tag() == "intent-filter").unwrap();
+        let tags: Vec<_> = filter
+            .get_children()
+            .iter()
+            .filter_map(|node| match node {
+                Node::Element(e) => Some(e.get_tag()),
+                Node::Cdata(_) => None,
+            })
+            .collect();
+        assert_eq!(tags, ["action"]);
+    }
+
+    #[test]
+    fn test_parse_duplicate_attributes() {
+        let mut first = Element::new("activity");
+        first.set_attribute("label", "first");
+        let mut element = first.clone();
+        element.set_attribute("label", "last");
+        element
+            .typed_attributes
+            .insert(0, first.typed_attributes.remove(0));
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&XmlDocument::new(Node::Element(element)), &mut output).unwrap();
+        let data = output.into_inner();
+
+        let parse_with = |duplicate_attributes| {
+            let config = ParseConfig {
+                duplicate_attributes,
+                ..ParseConfig::default()
+            };
+            parse_with_config(&mut std::io::Cursor::new(&data), &config)
+        };
+        for (policy, expected) in [
+            (DuplicateAttributes::First, "first"),
+            (DuplicateAttributes::Last, "last"),
+        ] {
+            let (xml, warnings) = parse_with(policy).unwrap();
+            let root = match xml.get_root() {
+                Some(Node::Element(root)) => root,
+                _ => panic!("missing root element"),
+            };
+            assert_eq!(root.get_attribute("label"), Some(expected));
+            assert_eq!(root.attributes().collect::<Vec<_>>(), [("label", expected)]);
+            assert_eq!(root.typed_attributes().count(), 2);
+            assert!(matches!(warnings[..], [Warning::DuplicateAttribute { .. }]));
+        }
+        assert!(matches!(
+            parse_with(DuplicateAttributes::Error),
+            Err(ParseError::DuplicateAttribute { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_without_seek() {
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let size = data.len();
+        data.extend_from_slice(b"trailing");
+
+        // A byte slice can only be read, and is read no further than the
+        // end of the document.
+        let mut input = &data[..];
+        let xml = parse(&mut input).unwrap();
+        assert_eq!(input, b"trailing");
+        assert_eq!(xml.metadata().unwrap().file_size as usize, size);
+
+        let binaryxml = BinaryXmlDocument::read_from(&mut &data[..]).unwrap();
+        let mut output = Cursor::new(Vec::new());
+        binaryxml.write_to(&mut output).unwrap();
+        assert_eq!(output.into_inner(), &data[..size]);
+
+        let (xml, error) = parse_partial(&mut &data[..size - 30]);
+        assert!(xml.get_root().is_some());
+        assert!(error.is_some());
+    }
+
+    #[test]
+    fn test_parse_trailing_garbage() {
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let expected = parse(&mut std::io::Cursor::new(&data)).unwrap();
+
+        data.extend_from_slice(&[0x03, 0x00, 0x08, 0x00, 0xff, 0xff, 0xff, 0xff, 0xde, 0xad]);
+        let doc = parse(&mut std::io::Cursor::new(&data)).unwrap();
+        match (doc.get_root(), expected.get_root()) {
+            (Some(Node::Element(a)), Some(Node::Element(b))) => {
+                assert_eq!(a.get_tag(), b.get_tag());
+                assert_eq!(a.get_children().len(), b.get_children().len());
+            }
+            _ => panic!("missing root element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_last_chunk_and_table() {
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+        let first_element = chunks.iter().find(|c| c.typ == 0x0102).unwrap().offset as usize;
+
+        let mut inserted = vec![0x7f, 0x01, 0x08, 0x00, 8, 0, 0, 0];
+        inserted.extend_from_slice(&[0x02, 0x00, 0x0c, 0x00, 16, 0, 0, 0, 0, 0, 0, 0]);
+        inserted.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        data.splice(first_element..first_element, inserted.iter().copied());
+        let size = data.len() as u32;
+        data[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let (xml, warnings) =
+            parse_with_config(&mut Cursor::new(&data), &ParseConfig::strict()).unwrap();
+        assert!(matches!(xml.get_root(), Some(Node::Element(e)) if e.get_tag() == "manifest"));
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(
+            warnings[1],
+            Warning::SkippedChunk { typ: 0x0002, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_multiple_string_pools() {
+        let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+        let pool = &chunks[1];
+        let pool_end = (pool.offset + u64::from(pool.size)) as usize;
+        // A pool whose string data would start inside its own header.
+        let mut junk = vec![0x01, 0x00, 0x1c, 0x00, 32, 0, 0, 0, 1, 0, 0, 0];
+        junk.extend_from_slice(&[0; 20]);
+
+        let with_chunk = |offset: usize, chunk: &[u8]| {
+            let mut data = data.clone();
+            data.splice(offset..offset, chunk.iter().copied());
+            let size = data.len() as u32;
+            data[4..8].copy_from_slice(&size.to_le_bytes());
+            data
+        };
+        let tag = |xml: &XmlDocument| match xml.get_root() {
+            Some(Node::Element(e)) => e.get_tag().to_string(),
+            _ => panic!("missing root element"),
+        };
+
+        // A second pool is skipped, even in strict mode.
+        let decoy_after = with_chunk(pool_end, &junk);
+        let (xml, warnings) =
+            parse_with_config(&mut Cursor::new(&decoy_after), &ParseConfig::strict()).unwrap();
+        assert_eq!(tag(&xml), "manifest");
+        assert!(matches!(
+            warnings[..],
+            [Warning::DuplicateStringPool { offset }] if offset as usize == pool_end
+        ));
+
+        // A broken pool before the real one is only tolerated in hardened
+        // mode.
+        let decoy_before = with_chunk(pool.offset as usize, &junk);
+        assert!(parse(&mut Cursor::new(&decoy_before)).is_err());
+        let (xml, warnings) = parse_with_diagnostics(&mut Cursor::new(&decoy_before)).unwrap();
+        assert_eq!(tag(&xml), "manifest");
+        assert!(matches!(warnings[..], [Warning::DroppedStringPool { .. }]));
+    }
+
+    #[test]
+    fn test_parse_out_of_order_chunks() {
+        let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let expected = parse(&mut Cursor::new(&data)).unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+
+        // Android does not care where the string pool and the resource map
+        // are, as long as they are in the document.
+        let (tables, nodes): (Vec<_>, Vec<_>) = chunks[1..]
+            .iter()
+            .partition(|c| c.typ == 0x0001 || c.typ == 0x0180);
+        let mut reordered = chunks[0].header.clone();
+        for chunk in nodes.iter().chain(tables.iter().rev()) {
+            reordered.extend_from_slice(&chunk.header);
+            reordered.extend_from_slice(&chunk.bytes);
+        }
+
+        let xml = parse(&mut Cursor::new(&reordered)).unwrap();
+        let options = FormatOptions::default();
+        assert_eq!(
+            xml.to_xml_string(&options),
+            expected.to_xml_string(&options)
+        );
+    }
+
+    #[test]
+    fn test_binaryxml_preserves_chunks() {
+        for entry in std::fs::read_dir("examples").unwrap() {
+            let data = std::fs::read(entry.unwrap().path()).unwrap();
+            let binaryxml = match BinaryXmlDocument::read_from(&mut Cursor::new(&data)) {
+                Ok(binaryxml) => binaryxml,
+                Err(_) => continue,
+            };
+            let mut output = Cursor::new(Vec::new());
+            let size = binaryxml.write_to(&mut output).unwrap();
+            assert_eq!(output.into_inner(), data[..size]);
+        }
+
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+        let first_element = chunks.iter().find(|c| c.typ == 0x0102).unwrap().offset as usize;
+        let unknown = [0x42, 0x02, 0x08, 0x00, 12, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef];
+        data.splice(first_element..first_element, unknown.iter().copied());
+        let size = data.len() as u32;
+        data[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let config = ParseConfig::hardened();
+        let mut binaryxml =
+            BinaryXmlDocument::read_from_with_config(&mut Cursor::new(&data), &config).unwrap();
+        assert!(matches!(
+            &binaryxml.elements[1],
+            binaryxml::XmlElement::Raw(chunk) if chunk.bytes == [0xde, 0xad, 0xbe, 0xef]
+        ));
+
+        // Modified documents are encoded again, still with the unknown chunk
+        // in its place.
+        binaryxml.resource_map.push(0x0101_0000);
+        let mut output = Cursor::new(Vec::new());
+        binaryxml.write_to(&mut output).unwrap();
+        let output = output.into_inner();
+        assert_ne!(output, data);
+        let chunks = read_chunks(&mut Cursor::new(&output)).unwrap();
+        let position = chunks.iter().position(|c| c.typ == 0x0242).unwrap();
+        assert_eq!(chunks[position + 1].typ, 0x0102);
+        assert_eq!(chunks[position].bytes, [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_parse_multiple_roots() {
+        let mut manifest = Element::new("manifest");
+        manifest
+            .get_children_mut()
+            .push(Node::Element(Element::new("application")));
+        manifest
+            .get_children_mut()
+            .push(Node::Element(Element::new("uses-sdk")));
+        let mut output = Cursor::new(Vec::new());
+        encode(&XmlDocument::new(Node::Element(manifest)), &mut output).unwrap();
+        output.set_position(0);
+        let mut binaryxml = BinaryXmlDocument::read_from(&mut output).unwrap();
+
+        // Close the root before uses-sdk, which then never gets closed.
+        let ends: Vec<usize> = (0..binaryxml.elements.len())
+            .filter(|&i| {
+                matches!(
+                    binaryxml.elements[i],
+                    binaryxml::XmlElement::XmlEndElement(_)
+                )
+            })
+            .collect();
+        let root_end = binaryxml.elements.remove(ends[2]);
+        binaryxml.elements.remove(ends[1]);
+        binaryxml.elements.insert(ends[0] + 1, root_end);
+        let mut output = Cursor::new(Vec::new());
+        binaryxml.write_to(&mut output).unwrap();
+        output.set_position(0);
+
+        let (xml, warnings) = parse_with_config(&mut output, &ParseConfig::default()).unwrap();
+        let tags: Vec<_> = xml
+            .roots()
+            .iter()
+            .map(|node| match node {
+                Node::Element(e) => (e.get_tag(), e.get_children().len()),
+                Node::Cdata(_) => panic!("unexpected text"),
+            })
+            .collect();
+        assert_eq!(tags, [("manifest", 1), ("uses-sdk", 0)]);
+        assert!(matches!(
+            &warnings[..],
+            [Warning::UnclosedElement { tag }, Warning::StrayRoot { .. }] if tag == "uses-sdk"
+        ));
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let xml = parse(&mut f).unwrap();
+
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+        output.set_position(0);
+        let reparsed = parse(&mut output).unwrap();
+
+        fn assert_same(a: &Element, b: &Element) {
+            assert_eq!(a.get_tag(), b.get_tag());
+            assert!(a.attributes().eq(b.attributes()));
+            assert_eq!(a.get_children().len(), b.get_children().len());
+            for (a, b) in a.get_children().iter().zip(b.get_children()) {
+                match (a, b) {
+                    (Node::Element(a), Node::Element(b)) => assert_same(a, b),
+                    (Node::Cdata(a), Node::Cdata(b)) => assert_eq!(a.get_data(), b.get_data()),
+                    _ => panic!("node kinds differ"),
+                }
+            }
+        }
+
+        match (xml.get_root(), reparsed.get_root()) {
+            (Some(Node::Element(a)), Some(Node::Element(b))) => assert_same(a, b),
+            _ => panic!("missing root element"),
+        }
+    }
+
+    #[test]
+    fn test_encode_chunk_layout() {
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+        // Trailing bytes in a node chunk are not written back.
+        let namespace = chunks.iter().find(|c| c.typ == 0x0100).unwrap();
+        let end = (namespace.offset + u64::from(namespace.size)) as usize;
+        data.splice(end..end, [0xaa; 8].iter().copied());
+        let size = namespace.size + 8;
+        let offset = namespace.offset as usize;
+        data[offset + 4..offset + 8].copy_from_slice(&size.to_le_bytes());
+        let size = data.len() as u32;
+        data[4..8].copy_from_slice(&size.to_le_bytes());
+
+        let xml = parse(&mut Cursor::new(&data)).unwrap();
+        let encoded = encode_to_vec(&xml).unwrap();
+        for chunk in read_chunks(&mut Cursor::new(&encoded)).unwrap() {
+            assert_eq!(chunk.offset % 4, 0);
+            assert_eq!(chunk.size % 4, 0);
+            if (0x0100..=0x0104).contains(&chunk.typ) {
+                assert_eq!(chunk.header_size, 16);
+            }
+            if chunk.typ == 0x0100 || chunk.typ == 0x0101 {
+                assert_eq!(chunk.size, 24);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encode_verify() {
+        let config = EncodeConfig {
+            verify: true,
+            ..EncodeConfig::default()
+        };
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let mut xml = parse(&mut f).unwrap();
+        let mut output = Cursor::new(Vec::new());
+        let size = encode_with_config(&xml, &mut output, &config).unwrap();
+        assert_eq!(output.into_inner(), encode_to_vec(&xml).unwrap()[..size]);
+
+        // The prefix is not declared, so the name is written without it.
+        if let Some(Node::Element(root)) = xml.get_root_mut() {
+            root.set_attribute("tools:ignore", "all");
+        }
+        match encode_with_config(&xml, &mut Cursor::new(Vec::new()), &config) {
+            Err(EncodeError::Verification { path, reason }) => {
+                assert_eq!(path, "manifest");
+                assert_eq!(reason, "attribute tools:ignore is missing");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_encode_deterministic() {
+        let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let parsed = || parse(&mut Cursor::new(&data)).unwrap();
+        let bytes = encode_to_vec(&parsed()).unwrap();
+        assert_eq!(encode_to_vec(&parsed()).unwrap(), bytes);
+        let reparsed = parse(&mut Cursor::new(&bytes)).unwrap();
+        assert_eq!(encode_to_vec(&reparsed).unwrap(), bytes);
+
+        // Every element has its own attribute map, each iterating in a
+        // different order.
+        let built = || {
+            let mut root = Element::new("manifest");
+            root.declare_namespace("android", "http://schemas.android.com/apk/res/android");
+            for i in 0..32 {
+                root.set_attribute(&format!("attr{}", i), &i.to_string());
+            }
+            root.set_attribute("android:versionCode", "1");
+            encode_to_vec(&XmlDocument::new(Node::Element(root))).unwrap()
+        };
+        assert_eq!(built(), built());
+    }
+
+    #[test]
+    fn test_encoded_size() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let xml = parse(&mut f).unwrap();
+
+        for utf8_strings in [false, true] {
+            let config = EncodeConfig {
+                utf8_strings,
+                ..EncodeConfig::default()
+            };
+            let mut output = Cursor::new(Vec::new());
+            let size = encode_with_config(&xml, &mut output, &config).unwrap();
+            assert_eq!(xml.encoded_size(&config).unwrap(), size);
+            assert_eq!(output.into_inner().len(), size);
+        }
+    }
+
+    #[test]
+    fn test_set_attribute_encode() {
+        let mut f = File::open("examples/AndroidManifest.xml").unwrap();
+        let mut xml = parse(&mut f).unwrap();
+        match xml.get_root_mut() {
+            Some(Node::Element(root)) => {
+                root.set_attribute("android:versionCode", "42");
+                root.set_attribute("android:installLocation", "0");
+            }
+            _ => panic!("missing root element"),
+        }
+
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+        output.set_position(0);
+        let binaryxml = BinaryXmlDocument::read_from(&mut output).unwrap();
+        assert!(binaryxml.resource_map.contains(&0x10102b7));
+
+        output.set_position(0);
+        let reparsed = parse(&mut output).unwrap();
+        let root = match reparsed.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        assert_eq!(root.get_attribute("android:versionCode"), Some("42"));
+        assert_eq!(root.get_attribute("android:installLocation"), Some("0"));
+    }
+
+    #[test]
+    fn test_set_typed_attribute_encode() {
+        let mut uses_sdk = Element::new("uses-sdk");
+        uses_sdk.set_typed_attribute(None, "android:minSdkVersion", ResourceValue::integer(23));
+        let mut application = Element::new("application");
+        application.set_typed_attribute(
+            Some("http://schemas.android.com/apk/res/android"),
+            "android:debuggable",
+            ResourceValue::boolean(true),
+        );
+        application.set_typed_attribute(None, "android:icon", ResourceValue::reference(0x7f010001));
+        let mut manifest = Element::new("manifest");
+        manifest.get_children_mut().push(Node::Element(uses_sdk));
+        manifest.get_children_mut().push(Node::Element(application));
+        let mut xml = XmlDocument::new(Node::Element(manifest));
+        xml.declare_namespace("android", "http://schemas.android.com/apk/res/android");
+
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+        output.set_position(0);
+        let reparsed = parse(&mut output).unwrap();
+        let dump = reparsed.dump_tree();
+        assert!(dump.contains(":minSdkVersion(0x0101020c)=23\n"));
+        assert!(dump.contains(":debuggable(0x0101000f)=true\n"));
+        assert!(dump.contains(":icon(0x01010002)=@0x7f010001\n"));
+    }
+
+    #[test]
+    fn test_parse_without_resource_map() {
+        let mut screen = Element::new("PreferenceScreen");
+        screen.set_attribute("key", "general");
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&XmlDocument::new(Node::Element(screen)), &mut output).unwrap();
+
+        // Cut the empty resource map chunk following the string pool.
+        let mut data = output.into_inner();
+        let string_pool_size = LittleEndian::read_u32(&data[12..16]) as usize;
+        let resource_map = 8 + string_pool_size;
+        assert_eq!(LittleEndian::read_u16(&data[resource_map..]), 0x180);
+        data.drain(resource_map..resource_map + 8);
+        let size = data.len() as u32;
+        LittleEndian::write_u32(&mut data[4..8], size);
+
+        let xml = parse(&mut std::io::Cursor::new(data)).unwrap();
+        match xml.get_root() {
+            Some(Node::Element(root)) => assert_eq!(root.get_attribute("key"), Some("general")),
+            _ => panic!("missing root element"),
+        }
+    }
+
+    #[test]
+    fn test_parse_names_past_resource_map() {
+        let mut manifest = Element::new("manifest");
+        manifest.set_attribute("android:versionCode", "1");
+        manifest.set_attribute("android:versionName", "1.0");
+        let mut xml = XmlDocument::new(Node::Element(manifest));
+        xml.declare_namespace("android", "http://schemas.android.com/apk/res/android");
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+
+        // Drop the id of versionName, the second name in the pool, as tools
+        // writing manifests by hand do.
+        let mut data = output.into_inner();
+        let string_pool_size = LittleEndian::read_u32(&data[12..16]) as usize;
+        let resource_map = 8 + string_pool_size;
+        assert_eq!(LittleEndian::read_u32(&data[resource_map + 4..]), 16);
+        LittleEndian::write_u32(&mut data[resource_map + 4..], 12);
+        data.drain(resource_map + 12..resource_map + 16);
+        let size = data.len() as u32;
+        LittleEndian::write_u32(&mut data[4..8], size);
+
+        let xml = parse(&mut std::io::Cursor::new(data)).unwrap();
+        let root = match xml.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        let version_name = root
+            .typed_attributes()
+            .find(|a| a.name == "versionName")
+            .unwrap();
+        assert_eq!(version_name.qualified_name, "android:versionName");
+        assert_eq!(version_name.resource_id, None);
+        assert_eq!(root.get_attribute("android:versionCode"), Some("1"));
+    }
+
+    #[test]
+    fn test_layout_roundtrip() {
+        let mut layout = Element::new("LinearLayout");
+        layout.set_attribute("android:layout_width", "16.0dip");
+        layout.set_attribute("android:background", "#ffff0000");
+        layout.set_attribute("android:theme", "?0x7f010004");
+        layout.set_attribute("app:layout_behavior", "@0x7f0b0001");
+        let mut xml = XmlDocument::new(Node::Element(layout));
+        xml.declare_namespace("android", "http://schemas.android.com/apk/res/android");
+        xml.declare_namespace("app", "http://schemas.android.com/apk/res-auto");
+
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+        output.set_position(0);
+        let reparsed = parse(&mut output).unwrap();
+        let root = match reparsed.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        assert_eq!(root.get_attribute("android:layout_width"), Some("16.0dip"));
+        assert_eq!(root.get_attribute("android:background"), Some("#ffff0000"));
+        assert_eq!(root.get_attribute("android:theme"), Some("?0x7f010004"));
+        assert_eq!(
+            root.get_attribute("app:layout_behavior"),
+            Some("@0x7f0b0001")
+        );
+    }
+
+    #[test]
+    fn test_nested_namespace_declaration() {
+        let mut activity = Element::new("activity");
+        activity.declare_namespace("tools", "http://schemas.android.com/tools");
+        activity.set_attribute("tools:ignore", "MissingClass");
+        let mut manifest = Element::new("manifest");
+        manifest.get_children_mut().push(Node::Element(activity));
+        let mut xml = XmlDocument::new(Node::Element(manifest));
+        xml.declare_namespace("android", "http://schemas.android.com/apk/res/android");
+
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&xml, &mut output).unwrap();
+        output.set_position(0);
+        let reparsed = parse(&mut output).unwrap();
+        let manifest = match reparsed.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        assert!(manifest.get_namespace_declarations().is_empty());
+        match &manifest.get_children()[0] {
+            Node::Element(activity) => assert_eq!(
+                activity.get_namespace_declarations(),
+                [(
+                    "tools".to_string(),
+                    "http://schemas.android.com/tools".to_string()
+                )]
+            ),
+            _ => panic!("expected activity element"),
+        }
+
+        let text = reparsed.to_xml_string(&FormatOptions::default());
+        assert!(text
+            .contains("<manifest xmlns:android=\"http://schemas.android.com/apk/res/android\">"));
+        assert!(text.contains(
+            "<activity xmlns:tools=\"http://schemas.android.com/tools\" tools:ignore=\"MissingClass\"/>"
+        ));
+    }
+
+    #[test]
+    fn test_default_namespace() {
+        let mut root = Element::new("root");
+        root.get_children_mut()
+            .push(Node::Element(Element::new("child")));
+        let mut xml = XmlDocument::new(Node::Element(root));
+        xml.declare_namespace("", "http://example.com/ns");
+
+        // Declare the default namespace without a prefix string.
+        let mut binaryxml = Encoder::encode(&xml, &EncodeConfig::default()).unwrap();
+        for element in &mut binaryxml.elements {
+            if let binaryxml::XmlElement::XmlStartNameSpace(ns) = element {
+                ns.prefix = u32::MAX;
+            }
+        }
+        let mut data = std::io::Cursor::new(Vec::new());
+        binaryxml.write_to(&mut data).unwrap();
+        data.set_position(0);
+
+        let reparsed = parse(&mut data).unwrap();
+        let root = match reparsed.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        assert_eq!(root.get_tag(), "root");
+        assert_eq!(root.get_namespace(), Some("http://example.com/ns"));
+        assert!(reparsed
+            .to_xml_string(&FormatOptions::default())
+            .contains("<root xmlns=\"http://example.com/ns\">\n  <child/>"));
+    }
+
+    #[test]
+    fn test_typed_cdata() {
+        let mut integer = Element::new("integer");
+        integer.get_children_mut().push(Node::Cdata(Cdata::new("")));
+        let mut binaryxml = Encoder::encode(
+            &XmlDocument::new(Node::Element(integer)),
+            &EncodeConfig::default(),
+        )
+        .unwrap();
+
+        // Store the text as a decimal integer without any string.
+        for element in &mut binaryxml.elements {
+            if let binaryxml::XmlElement::XmlCdata(cdata) = element {
+                cdata.data = u32::MAX;
+                cdata.typed_data = resource_value::ResourceValue::from_str_as(
+                    resource_value::ResourceValueType::Dec,
+                    "42",
+                )
+                .unwrap();
+            }
+        }
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        binaryxml.write_to(&mut data).unwrap();
+        for _ in 0..2 {
+            data.set_position(0);
+            let xml = parse(&mut data).unwrap();
+            let cdata = match xml.get_root() {
+                Some(Node::Element(root)) => match &root.get_children()[0] {
+                    Node::Cdata(c) => c,
+                    _ => panic!("expected text"),
+                },
+                _ => panic!("missing root element"),
+            };
+            assert_eq!(cdata.get_data(), "42");
+            let typed_value = cdata.get_typed_value().unwrap();
+            assert_eq!((typed_value.raw_type(), typed_value.data()), (0x10, 42));
+
+            data = std::io::Cursor::new(Vec::new());
+            encode(&xml, &mut data).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_merge_cdata() {
+        let mut element = Element::new("string");
+        element
+            .get_children_mut()
+            .push(Node::Cdata(Cdata::new("split ")));
+        element
+            .get_children_mut()
+            .push(Node::Cdata(Cdata::new("text")));
+        let mut output = std::io::Cursor::new(Vec::new());
+        encode(&XmlDocument::new(Node::Element(element)), &mut output).unwrap();
+        let data = output.into_inner();
+
+        let children = |merge_cdata| {
+            let config = ParseConfig {
+                merge_cdata,
+                ..ParseConfig::default()
+            };
+            let (xml, _) = parse_with_config(&mut std::io::Cursor::new(&data), &config).unwrap();
+            match xml.get_root() {
+                Some(Node::Element(root)) => root
+                    .get_children()
+                    .iter()
+                    .map(|node| match node {
+                        Node::Cdata(c) => c.get_data().to_string(),
+                        Node::Element(e) => e.get_tag().to_string(),
+                    })
+                    .collect::<Vec<_>>(),
+                _ => panic!("missing root element"),
+            }
+        };
+        assert_eq!(children(true), ["split text"]);
+        assert_eq!(children(false), ["split ", "text"]);
+    }
+
+    #[test]
+    fn test_comments() {
+        let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let chunks = read_chunks(&mut Cursor::new(&data)).unwrap();
+        // Point the comments of the first namespace and element chunks at
+        // the namespace prefix and tag name strings.
+        for (typ, name_offset) in [(0x0100, 16), (0x0102, 20)] {
+            let chunk = chunks.iter().find(|c| c.typ == typ).unwrap();
+            let offset = chunk.offset as usize;
+            let (header, name) = (offset + 12, offset + name_offset);
+            data.copy_within(name..name + 4, header);
+        }
+
+        let xml = parse(&mut Cursor::new(&data)).unwrap();
+        let root = match xml.get_root() {
+            Some(Node::Element(root)) => root,
+            _ => panic!("missing root element"),
+        };
+        assert_eq!(root.get_comments(), ["android", "manifest"]);
+
+        let options = FormatOptions {
+            include_comments: true,
+            ..FormatOptions::default()
+        };
+        assert!(xml
+            .to_xml_string(&options)
+            .contains("\n<!-- android -->\n<!-- manifest -->\n<manifest "));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_parse_async() {
+        let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let xml = runtime.block_on(parse_async(&mut data.as_slice())).unwrap();
+        assert!(xml.get_root().is_some());
+    }
 
     #[test]
     fn test_u8_rw() {
-        let src = [42u8;1];
+        let src = [42u8; 1];
         let mut dst: Vec<u8> = vec![];
         let mut cursor = std::io::Cursor::new(src);
         let v = read_u8(&mut cursor).unwrap();
@@ -154,7 +1574,6 @@ mod tests {
         let n = write_u8(&mut cursor, v).unwrap();
         assert_eq!(n, 1);
         assert_eq!(&src, dst.as_slice());
-
     }
 
     #[test]
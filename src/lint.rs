@@ -0,0 +1,366 @@
+//!Checks for common mistakes in a parsed `AndroidManifest.xml`.
+//!
+//!```rust
+//!use axmldecoder::parse;
+//!# let manifest_file = "examples/AndroidManifest.xml";
+//!let mut f = std::fs::File::open(manifest_file).unwrap();
+//!let xml = parse(&mut f).unwrap();
+//!for finding in xml.lint() {
+//!    println!("{}", finding);
+//!}
+//!```
+
+use std::collections::HashSet;
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::path::child_paths;
+use crate::resource_value::ResourceValueType;
+use crate::xml::Attribute;
+use crate::{Element, Node, XmlDocument};
+
+///Tags of the application components that can be started by other apps.
+const COMPONENTS: &[&str] = &[
+    "activity",
+    "activity-alias",
+    "service",
+    "receiver",
+    "provider",
+];
+
+///Tags that request or declare a permission by name.
+const PERMISSION_TAGS: &[&str] = &["uses-permission", "uses-permission-sdk-23", "permission"];
+
+const BOOLEAN_ATTRIBUTES: &[&str] = &[
+    "allowBackup",
+    "debuggable",
+    "directBootAware",
+    "enabled",
+    "exported",
+    "extractNativeLibs",
+    "grantUriPermissions",
+    "hardwareAccelerated",
+    "largeHeap",
+    "required",
+    "testOnly",
+    "usesCleartextTraffic",
+];
+
+const INTEGER_ATTRIBUTES: &[&str] = &[
+    "compileSdkVersion",
+    "maxSdkVersion",
+    "minSdkVersion",
+    "targetSdkVersion",
+    "versionCode",
+];
+
+///The API level from which components with intent filters must declare
+///`android:exported`.
+pub(crate) const EXPORTED_REQUIRED_SDK: u32 = 31;
+
+///A problem found by [XmlDocument::lint].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub kind: LintKind,
+    ///Path of the offending element, such as
+    ///`manifest/application/activity[2]`.
+    pub path: String,
+    ///Line number of the offending element, or 0 if unknown.
+    pub line_number: u32,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}): {}",
+            self.path, self.line_number, self.kind
+        )
+    }
+}
+
+///The kinds of problems reported by [XmlDocument::lint].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    #[error("component has an intent filter but does not set android:exported, which is required when targeting API 31 or higher")]
+    MissingExported,
+
+    #[error("permission {0} is listed more than once")]
+    DuplicatePermission(String),
+
+    #[error("minSdkVersion {second} conflicts with the earlier minSdkVersion {first}")]
+    ConflictingMinSdk { first: String, second: String },
+
+    #[error("minSdkVersion {min} is higher than targetSdkVersion {target}")]
+    MinSdkAboveTarget { min: u32, target: u32 },
+
+    #[error("component class {class} is outside of package {package}")]
+    ForeignComponentClass { class: String, package: String },
+
+    #[error("attribute {attribute} should be a {expected}")]
+    InvalidAttributeType {
+        attribute: String,
+        expected: &'static str,
+    },
+}
+
+impl XmlDocument {
+    ///Checks the document for common manifest mistakes. Documents whose root
+    ///is not a `manifest` element produce no findings.
+    pub fn lint(&self) -> Vec<LintFinding> {
+        let root = match self.get_root() {
+            Some(Node::Element(root)) if root.get_tag() == "manifest" => root,
+            _ => return Vec::new(),
+        };
+
+        let mut linter = Linter {
+            package: root.get_attribute("package").map(str::to_string),
+            target_sdk: None,
+            permissions: HashSet::new(),
+            findings: Vec::new(),
+        };
+        linter.check_sdk(root);
+        linter.check_element(root, "manifest".to_string());
+        linter.findings
+    }
+}
+
+struct Linter {
+    package: Option<String>,
+    target_sdk: Option<u32>,
+    ///Permissions seen so far, along with the tag listing them.
+    permissions: HashSet<(String, String)>,
+    findings: Vec<LintFinding>,
+}
+
+impl Linter {
+    fn report(&mut self, kind: LintKind, path: &str, element: &Element) {
+        self.findings.push(LintFinding {
+            kind,
+            path: path.to_string(),
+            line_number: element.line_number,
+        });
+    }
+
+    fn check_sdk(&mut self, manifest: &Element) {
+        let mut min_sdk: Option<String> = None;
+        for (path, uses_sdk) in child_paths(manifest, "manifest") {
+            if uses_sdk.get_tag() != "uses-sdk" {
+                continue;
+            }
+
+            let min = uses_sdk
+                .android_attribute("minSdkVersion")
+                .map(|a| a.value.clone());
+            let target = uses_sdk
+                .android_attribute("targetSdkVersion")
+                .and_then(Attribute::integer_value);
+            if self.target_sdk.is_none() {
+                self.target_sdk = target;
+            }
+
+            match (&min_sdk, min) {
+                (Some(first), Some(second)) if *first != second => self.report(
+                    LintKind::ConflictingMinSdk {
+                        first: first.clone(),
+                        second,
+                    },
+                    &path,
+                    uses_sdk,
+                ),
+                (None, Some(min)) => min_sdk = Some(min),
+                _ => (),
+            }
+
+            let min = uses_sdk
+                .android_attribute("minSdkVersion")
+                .and_then(Attribute::integer_value);
+            if let (Some(min), Some(target)) = (min, target) {
+                if min > target {
+                    self.report(LintKind::MinSdkAboveTarget { min, target }, &path, uses_sdk);
+                }
+            }
+        }
+    }
+
+    fn check_element(&mut self, element: &Element, path: String) {
+        self.check_attribute_types(element, &path);
+
+        let tag = element.get_tag();
+        if COMPONENTS.contains(&tag) {
+            self.check_component(element, &path);
+        }
+
+        if PERMISSION_TAGS.contains(&tag) {
+            if let Some(name) = element.android_attribute("name") {
+                let key = (tag.to_string(), name.value.clone());
+                if !self.permissions.insert(key) {
+                    self.report(
+                        LintKind::DuplicatePermission(name.value.clone()),
+                        &path,
+                        element,
+                    );
+                }
+            }
+        }
+
+        for (child_path, child) in child_paths(element, &path) {
+            self.check_element(child, child_path);
+        }
+    }
+
+    fn check_component(&mut self, component: &Element, path: &str) {
+        let has_intent_filter = component
+            .get_children()
+            .iter()
+            .any(|c| matches!(c, Node::Element(e) if e.get_tag() == "intent-filter"));
+        if has_intent_filter
+            && component.android_attribute("exported").is_none()
+            && self.target_sdk.is_some_and(|t| t >= EXPORTED_REQUIRED_SDK)
+        {
+            self.report(LintKind::MissingExported, path, component);
+        }
+
+        // The name of an alias is not a class.
+        if component.get_tag() == "activity-alias" {
+            return;
+        }
+        let (package, class) = match (&self.package, component.android_attribute("name")) {
+            (Some(package), Some(class)) => (package.clone(), class.value.clone()),
+            _ => return,
+        };
+        let relative = class.starts_with('.') || !class.contains('.');
+        if !relative && !class.starts_with(&format!("{}.", package)) {
+            self.report(
+                LintKind::ForeignComponentClass { class, package },
+                path,
+                component,
+            );
+        }
+    }
+
+    fn check_attribute_types(&mut self, element: &Element, path: &str) {
+        for attr in &element.typed_attributes {
+            if !attr.is_android() {
+                continue;
+            }
+
+            let data_type = attr.typed_value.data_type;
+            // Values can always come from a resource or theme attribute.
+            if matches!(
+                data_type,
+                ResourceValueType::Reference | ResourceValueType::Attribute
+            ) {
+                continue;
+            }
+
+            let expected = if BOOLEAN_ATTRIBUTES.contains(&attr.name.as_str()) {
+                (data_type != ResourceValueType::Boolean).then_some("boolean")
+            } else if INTEGER_ATTRIBUTES.contains(&attr.name.as_str()) {
+                // SDK versions may also name a preview release, such as "Q".
+                let codename = data_type == ResourceValueType::String
+                    && attr.name.ends_with("SdkVersion")
+                    && attr.value.chars().all(|c| c.is_ascii_alphabetic());
+                let integer = matches!(data_type, ResourceValueType::Dec | ResourceValueType::Hex);
+                (!integer && !codename).then_some("integer")
+            } else {
+                None
+            };
+
+            if let Some(expected) = expected {
+                self.report(
+                    LintKind::InvalidAttributeType {
+                        attribute: attr.qualified_name.clone(),
+                        expected,
+                    },
+                    path,
+                    element,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lint() {
+    use crate::test_support::{element, manifest_document};
+
+    let application = element(
+        "application",
+        &[("android:debuggable", "yes")],
+        vec![
+            element(
+                "activity",
+                &[("android:name", ".MainActivity")],
+                vec![element("intent-filter", &[], vec![])],
+            ),
+            element(
+                "activity",
+                &[
+                    ("android:name", "org.other.Activity"),
+                    ("android:exported", "false"),
+                ],
+                vec![],
+            ),
+        ],
+    );
+    let xml = manifest_document(vec![
+        element(
+            "uses-sdk",
+            &[
+                ("android:minSdkVersion", "21"),
+                ("android:targetSdkVersion", "33"),
+            ],
+            vec![],
+        ),
+        element("uses-sdk", &[("android:minSdkVersion", "23")], vec![]),
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.INTERNET")],
+            vec![],
+        ),
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.INTERNET")],
+            vec![],
+        ),
+        application,
+    ]);
+
+    let findings: Vec<_> = xml.lint().into_iter().map(|f| (f.kind, f.path)).collect();
+    assert_eq!(
+        findings,
+        vec![
+            (
+                LintKind::ConflictingMinSdk {
+                    first: "21".to_string(),
+                    second: "23".to_string()
+                },
+                "manifest/uses-sdk[2]".to_string()
+            ),
+            (
+                LintKind::DuplicatePermission("android.permission.INTERNET".to_string()),
+                "manifest/uses-permission[2]".to_string()
+            ),
+            (
+                LintKind::InvalidAttributeType {
+                    attribute: "android:debuggable".to_string(),
+                    expected: "boolean"
+                },
+                "manifest/application".to_string()
+            ),
+            (
+                LintKind::MissingExported,
+                "manifest/application/activity[1]".to_string()
+            ),
+            (
+                LintKind::ForeignComponentClass {
+                    class: "org.other.Activity".to_string(),
+                    package: "com.example".to_string()
+                },
+                "manifest/application/activity[2]".to_string()
+            ),
+        ]
+    );
+}
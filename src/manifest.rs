@@ -0,0 +1,327 @@
+use crate::launcher::{ACTION_MAIN, CATEGORY_LAUNCHER};
+use crate::xml::{Attribute, Element, Node, XmlDocument};
+
+///A view of a document whose root is a `manifest` element, with helpers
+///for the elements and attributes specific to `AndroidManifest.xml`.
+///
+///```rust
+///use axmldecoder::parse;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///let manifest = xml.manifest().unwrap();
+///assert_eq!(manifest.package(), Some("org.t0t0.androguard.TC"));
+///```
+#[derive(Debug, Clone, Copy)]
+pub struct Manifest<'a> {
+    root: &'a Element,
+}
+
+///The kinds of application components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComponentKind {
+    Activity,
+    Service,
+    Receiver,
+    Provider,
+}
+
+impl ComponentKind {
+    ///Returns the component kind declared by elements with the given tag.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "activity" => Some(Self::Activity),
+            "service" => Some(Self::Service),
+            "receiver" => Some(Self::Receiver),
+            "provider" => Some(Self::Provider),
+            _ => None,
+        }
+    }
+
+    ///Returns the tag of elements declaring this kind of component.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::Activity => "activity",
+            Self::Service => "service",
+            Self::Receiver => "receiver",
+            Self::Provider => "provider",
+        }
+    }
+}
+
+impl XmlDocument {
+    ///Returns a [Manifest] view of the document, or `None` if its root is
+    ///not a `manifest` element.
+    pub fn manifest(&self) -> Option<Manifest<'_>> {
+        match self.get_root() {
+            Some(Node::Element(root)) => Manifest::new(root),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Manifest<'a> {
+    ///Wraps a `manifest` element, or returns `None` if `root` is any other
+    ///element.
+    pub fn new(root: &'a Element) -> Option<Self> {
+        if root.get_tag() == "manifest" {
+            Some(Self { root })
+        } else {
+            None
+        }
+    }
+
+    ///Builds the smallest manifest Android installs and launches: `package`
+    ///with version 1, a `uses-sdk` element, and an application with a
+    ///single exported `.MainActivity` in the launcher.
+    ///
+    ///The document is ready to be [encoded](crate::encode_to_vec), and can
+    ///be extended with [XmlDocument::insert] or [Element::set_attribute]
+    ///first.
+    ///
+    ///```rust
+    ///use axmldecoder::Manifest;
+    ///let xml = Manifest::minimal("com.example.stub", 21, 34);
+    ///let manifest = xml.manifest().unwrap();
+    ///assert_eq!(manifest.package(), Some("com.example.stub"));
+    ///assert_eq!(manifest.target_sdk_version(), Some(34));
+    ///let data = axmldecoder::encode_to_vec(&xml).unwrap();
+    ///```
+    pub fn minimal(package: &str, min_sdk: u32, target_sdk: u32) -> XmlDocument {
+        let mut uses_sdk = Element::new("uses-sdk");
+        uses_sdk.set_attribute("android:minSdkVersion", &min_sdk.to_string());
+        uses_sdk.set_attribute("android:targetSdkVersion", &target_sdk.to_string());
+
+        let mut action = Element::new("action");
+        action.set_attribute("android:name", ACTION_MAIN);
+        let mut category = Element::new("category");
+        category.set_attribute("android:name", CATEGORY_LAUNCHER);
+        let mut intent_filter = Element::new("intent-filter");
+        intent_filter
+            .get_children_mut()
+            .extend(vec![Node::Element(action), Node::Element(category)]);
+
+        let mut activity = Element::new("activity");
+        activity.set_attribute("android:name", ".MainActivity");
+        // Required of activities with intent filters since Android 12.
+        activity.set_attribute("android:exported", "true");
+        activity
+            .get_children_mut()
+            .push(Node::Element(intent_filter));
+
+        let mut application = Element::new("application");
+        application.get_children_mut().push(Node::Element(activity));
+
+        let mut root = Element::new("manifest");
+        root.set_attribute("android:versionCode", "1");
+        root.set_attribute("android:versionName", "1.0");
+        root.set_attribute("package", package);
+        root.get_children_mut()
+            .extend(vec![Node::Element(uses_sdk), Node::Element(application)]);
+
+        let mut xml = XmlDocument::new(Node::Element(root));
+        xml.declare_namespace("android", crate::ANDROID_NAMESPACE);
+        xml
+    }
+
+    ///Returns the `manifest` element.
+    pub fn root(&self) -> &'a Element {
+        self.root
+    }
+
+    ///Returns the package name.
+    pub fn package(&self) -> Option<&'a str> {
+        self.root.get_attribute("package")
+    }
+
+    ///Returns the version code, combining `android:versionCode` with
+    ///`android:versionCodeMajor` into the 64-bit value Android compares
+    ///versions by.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut root = Element::new("manifest");
+    ///root.set_attribute("android:versionCode", "3");
+    ///root.set_attribute("android:versionCodeMajor", "1");
+    ///let xml = XmlDocument::new(Node::Element(root));
+    ///assert_eq!(xml.manifest().unwrap().long_version_code(), Some(0x1_0000_0003));
+    ///```
+    pub fn long_version_code(&self) -> Option<u64> {
+        let minor = self
+            .root
+            .android_attribute("versionCode")?
+            .integer_value()?;
+        let major = self
+            .root
+            .android_attribute("versionCodeMajor")
+            .and_then(Attribute::integer_value)
+            .unwrap_or(0);
+        Some(u64::from(major) << 32 | u64::from(minor))
+    }
+
+    ///Returns the highest OpenGL ES version required by a `uses-feature`
+    ///element, such as `3.1`.
+    pub fn gl_es_version(&self) -> Option<String> {
+        child_elements(self.root)
+            .filter(|e| e.get_tag() == "uses-feature")
+            .filter_map(|e| e.android_attribute("glEsVersion")?.integer_value())
+            .max()
+            .map(format_gl_es_version)
+    }
+
+    ///Returns the `android:minSdkVersion` of the `uses-sdk` element, or
+    ///`None` if it is not set or is a codename.
+    pub fn min_sdk_version(&self) -> Option<u32> {
+        self.uses_sdk("minSdkVersion")
+    }
+
+    ///Returns the `android:targetSdkVersion` of the `uses-sdk` element.
+    ///Like Android, falls back to the minimum SDK version if it is not set.
+    pub fn target_sdk_version(&self) -> Option<u32> {
+        self.uses_sdk("targetSdkVersion")
+            .or_else(|| self.min_sdk_version())
+    }
+
+    fn uses_sdk(&self, name: &str) -> Option<u32> {
+        child_elements(self.root)
+            .find(|e| e.get_tag() == "uses-sdk")?
+            .android_attribute(name)?
+            .integer_value()
+    }
+
+    ///Returns the `application` element.
+    pub fn application(&self) -> Option<&'a Element> {
+        child_elements(self.root).find(|e| e.get_tag() == "application")
+    }
+
+    ///Expands a class name relative to the package, as in
+    ///`android:name=".MainActivity"`, into a fully qualified one.
+    ///Names that are already fully qualified are returned unchanged.
+    pub fn resolve_class_name(&self, name: &str) -> String {
+        let package = self.package().unwrap_or_default();
+        if name.starts_with('.') {
+            format!("{}{}", package, name)
+        } else if !name.contains('.') {
+            format!("{}.{}", package, name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    ///Returns every activity, service, receiver and provider of the
+    ///application, in document order.
+    pub fn components(&self) -> impl Iterator<Item = (&'a Element, ComponentKind)> {
+        self.application()
+            .into_iter()
+            .flat_map(child_elements)
+            .filter_map(|e| Some((e, ComponentKind::from_tag(e.get_tag())?)))
+    }
+
+    ///Finds the component with the given class name. Both `name` and the
+    ///names in the manifest may be relative to the package.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, ComponentKind};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let manifest = xml.manifest().unwrap();
+    ///let (activity, kind) = manifest
+    ///    .find_component("org.t0t0.androguard.TC.TCActivity")
+    ///    .unwrap();
+    ///assert_eq!(kind, ComponentKind::Activity);
+    ///```
+    pub fn find_component(&self, name: &str) -> Option<(&'a Element, ComponentKind)> {
+        let name = self.resolve_class_name(name);
+        self.components().find(|(e, _)| {
+            e.android_attribute("name")
+                .is_some_and(|attr| self.resolve_class_name(&attr.value) == name)
+        })
+    }
+}
+
+///Formats an `android:glEsVersion` value, which stores the major version in
+///the upper 16 bits and the minor version in the lower ones, as `3.1`.
+pub fn format_gl_es_version(value: u32) -> String {
+    format!("{}.{}", value >> 16, value & 0xffff)
+}
+
+pub(crate) fn child_elements(element: &Element) -> impl Iterator<Item = &Element> {
+    element.get_children().iter().filter_map(|c| match c {
+        Node::Element(e) => Some(e),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_version_helpers() {
+    let mut root = Element::new("manifest");
+    root.set_attribute("android:versionCode", "0xffffffff");
+    for value in ["0x00020000", "0x00030001"] {
+        let mut feature = Element::new("uses-feature");
+        feature.set_attribute("android:glEsVersion", value);
+        root.get_children_mut().push(Node::Element(feature));
+    }
+    let xml = XmlDocument::new(Node::Element(root));
+    let manifest = xml.manifest().unwrap();
+
+    assert_eq!(manifest.long_version_code(), Some(0xffff_ffff));
+    assert_eq!(manifest.gl_es_version().as_deref(), Some("3.1"));
+    assert_eq!(format_gl_es_version(0x0002_0000), "2.0");
+}
+
+#[test]
+fn test_find_component() {
+    let mut application = Element::new("application");
+    for (tag, name) in [
+        ("activity", ".MainActivity"),
+        ("service", "SyncService"),
+        ("receiver", "org.other.Receiver"),
+    ] {
+        let mut component = Element::new(tag);
+        component.set_attribute("android:name", name);
+        application
+            .get_children_mut()
+            .push(Node::Element(component));
+    }
+    let mut root = Element::new("manifest");
+    root.set_attribute("package", "com.example");
+    root.get_children_mut().push(Node::Element(application));
+    let xml = XmlDocument::new(Node::Element(root));
+    let manifest = xml.manifest().unwrap();
+
+    let find = |name| manifest.find_component(name).map(|(_, kind)| kind);
+    assert_eq!(
+        find("com.example.MainActivity"),
+        Some(ComponentKind::Activity)
+    );
+    assert_eq!(find(".MainActivity"), Some(ComponentKind::Activity));
+    assert_eq!(
+        find("com.example.SyncService"),
+        Some(ComponentKind::Service)
+    );
+    assert_eq!(find("org.other.Receiver"), Some(ComponentKind::Receiver));
+    assert_eq!(find("com.example.Missing"), None);
+}
+
+#[test]
+fn test_minimal() {
+    use std::convert::TryFrom;
+
+    let xml = Manifest::minimal("com.example.stub", 21, 34);
+    assert_eq!(xml.validate(), []);
+
+    let binaryxml = crate::BinaryXmlDocument::try_from(&xml).unwrap();
+    let xml = XmlDocument::try_from(binaryxml).unwrap();
+    let manifest = xml.manifest().unwrap();
+    assert_eq!(manifest.package(), Some("com.example.stub"));
+    assert_eq!(manifest.long_version_code(), Some(1));
+    assert_eq!(manifest.min_sdk_version(), Some(21));
+    assert_eq!(manifest.target_sdk_version(), Some(34));
+
+    let launchable = manifest.launchable_activities();
+    assert_eq!(launchable.len(), 1);
+    assert_eq!(launchable[0].name, "com.example.stub.MainActivity");
+    let (activity, _) = manifest.find_component(".MainActivity").unwrap();
+    assert_eq!(activity.android_bool("exported"), Some(true));
+}
@@ -0,0 +1,105 @@
+use std::mem::{size_of, size_of_val};
+
+use indexmap::IndexMap;
+
+use crate::xml::Attribute;
+use crate::{Cdata, Element, Node, XmlDocument};
+
+impl XmlDocument {
+    ///Returns an estimate of the memory the document takes up in bytes,
+    ///counting nodes, strings and attributes along with the document itself.
+    ///
+    ///Vectors and strings are counted by length, so the estimate leaves out
+    ///spare capacity and allocator overhead. It is meant for sizing caches,
+    ///not for exact accounting.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///assert!(xml.approx_memory_usage() > std::mem::size_of_val(&xml));
+    ///```
+    pub fn approx_memory_usage(&self) -> usize {
+        let namespaces = self.namespaces.len() * size_of::<(String, String)>()
+            + self
+                .namespaces
+                .iter()
+                .map(|(prefix, uri)| prefix.len() + uri.len())
+                .sum::<usize>();
+        let metadata = self
+            .metadata
+            .as_ref()
+            .map_or(0, |m| m.chunks.len() * size_of::<crate::ChunkInfo>());
+        size_of::<Self>() + nodes_usage(self.roots()) + namespaces + metadata
+    }
+}
+
+///Returns the memory taken up by a vector of nodes and everything they own.
+fn nodes_usage(nodes: &[Node]) -> usize {
+    size_of_val(nodes)
+        + nodes
+            .iter()
+            .map(|node| match node {
+                Node::Element(e) => element_usage(e),
+                Node::Cdata(c) => cdata_usage(c),
+            })
+            .sum::<usize>()
+}
+
+///Returns the memory owned by an element, leaving out the element itself.
+fn element_usage(e: &Element) -> usize {
+    let attributes = map_usage(&e.attributes)
+        + e.typed_attributes.len() * size_of::<Attribute>()
+        + e.typed_attributes
+            .iter()
+            .map(attribute_usage)
+            .sum::<usize>();
+    let declarations = e.namespace_declarations.len() * size_of::<(String, String)>()
+        + e.namespace_declarations
+            .iter()
+            .map(|(prefix, uri)| prefix.len() + uri.len())
+            .sum::<usize>();
+    let comments =
+        e.comments.len() * size_of::<String>() + e.comments.iter().map(String::len).sum::<usize>();
+
+    e.get_tag().len()
+        + e.name.len()
+        + e.namespace.as_ref().map_or(0, String::len)
+        + attributes
+        + declarations
+        + comments
+        + nodes_usage(e.get_children())
+}
+
+fn attribute_usage(a: &Attribute) -> usize {
+    a.qualified_name.len()
+        + a.namespace.as_ref().map_or(0, String::len)
+        + a.name.len()
+        + a.raw_value.as_ref().map_or(0, String::len)
+        + a.value.len()
+}
+
+fn cdata_usage(c: &Cdata) -> usize {
+    c.get_data().len()
+}
+
+///Returns the memory taken up by a map of strings: its entries with their
+///hashes, its index table with one control byte per bucket, and the
+///strings.
+fn map_usage(map: &IndexMap<String, String>) -> usize {
+    map.capacity() * (size_of::<(usize, String, String)>() + size_of::<usize>() + 1)
+        + map.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>()
+}
+
+#[test]
+fn test_approx_memory_usage() {
+    let mut element = Element::new("activity");
+    let empty = XmlDocument::new(Node::Element(element.clone())).approx_memory_usage();
+    assert!(empty >= size_of::<XmlDocument>() + size_of::<Node>() + "activity".len());
+
+    element.set_attribute("android:name", "com.example.MainActivity");
+    let xml = XmlDocument::new(Node::Element(element));
+    // The name and value are stored both formatted and typed.
+    assert!(xml.approx_memory_usage() >= empty + 2 * "com.example.MainActivity".len());
+}
@@ -0,0 +1,420 @@
+use thiserror::Error;
+
+use crate::intent::IntentFilter;
+use crate::path::child_paths;
+use crate::xml::{split_prefix, Attribute};
+use crate::{Element, Node, XmlDocument};
+
+///The namespace of the `tools:` attributes that instruct the merger.
+const TOOLS_NAMESPACE: &str = "http://schemas.android.com/tools";
+
+///How [merge] resolves an attribute that the base and the overlay both set
+///to different values, unless the overlay lists it in `tools:replace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    ///Fail with [MergeError::Conflict], as the Gradle manifest merger does.
+    Error,
+    ///Keep the value of the overlay.
+    PreferOverlay,
+    ///Keep the value of the base.
+    PreferBase,
+}
+
+///The errors of [merge].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    #[error("cannot merge a document with root {overlay} into one with root {base}")]
+    RootMismatch { base: String, overlay: String },
+
+    #[error(
+        "{path}: attribute {attribute} is {base:?} in the base and {overlay:?} in the overlay"
+    )]
+    Conflict {
+        path: String,
+        attribute: String,
+        base: String,
+        overlay: String,
+    },
+
+    #[error("{path}: unsupported tools:node=\"{operation}\"")]
+    UnsupportedOperation { path: String, operation: String },
+}
+
+///Merges `overlay` into a copy of `base`, following a subset of the rules of
+///the Gradle manifest merger:
+///
+///* Elements of the overlay are matched with those of the base by tag and
+///  `android:name`. Elements without a name match the first element with
+///  the same tag and no name, except for `intent-filter` elements, which
+///  only match an identical filter.
+///* Attributes and children of matched elements are merged recursively.
+///  Elements that do not match are added.
+///* `tools:node` on an overlay element may be `merge`, the default,
+///  `merge-only-attributes`, `replace` to replace the matching element,
+///  `remove` to remove it, or `removeAll` to remove every element with the
+///  same tag.
+///* `tools:replace` lists attributes for which the overlay wins regardless
+///  of `policy`, and `tools:remove` attributes to drop from the result.
+///
+///`tools:` attributes are not copied into the result. Text in the overlay
+///is ignored.
+///
+///```rust
+///use axmldecoder::{merge, Element, MergePolicy, Node, XmlDocument};
+///let mut application = Element::new("application");
+///application.set_attribute("android:debuggable", "false");
+///let mut root = Element::new("manifest");
+///root.get_children_mut().push(Node::Element(application));
+///let base = XmlDocument::new(Node::Element(root));
+///
+///let mut application = Element::new("application");
+///application.set_attribute("android:debuggable", "true");
+///application.set_attribute("tools:replace", "android:debuggable");
+///let mut root = Element::new("manifest");
+///root.get_children_mut().push(Node::Element(application));
+///let overlay = XmlDocument::new(Node::Element(root));
+///
+///let merged = merge(&base, &overlay, MergePolicy::Error).unwrap();
+///let application = merged.get_elements_by_tag_name("application")[0];
+///assert_eq!(application.get_attribute("android:debuggable"), Some("true"));
+///assert!(application.get_attribute("tools:replace").is_none());
+///```
+pub fn merge(
+    base: &XmlDocument,
+    overlay: &XmlDocument,
+    policy: MergePolicy,
+) -> Result<XmlDocument, MergeError> {
+    let (base_root, overlay_root) = match (base.get_root(), overlay.get_root()) {
+        (Some(Node::Element(b)), Some(Node::Element(o))) if b.get_tag() == o.get_tag() => (b, o),
+        (b, o) => {
+            let tag = |node: Option<&Node>| match node {
+                Some(Node::Element(e)) => e.get_tag().to_string(),
+                _ => String::new(),
+            };
+            return Err(MergeError::RootMismatch {
+                base: tag(b),
+                overlay: tag(o),
+            });
+        }
+    };
+
+    let mut root = base_root.clone();
+    merge_element(&mut root, overlay_root, base_root.get_tag(), policy, true)?;
+
+    let mut merged = XmlDocument::new(Node::Element(root));
+    for (prefix, uri) in &base.namespaces {
+        merged.declare_namespace(prefix, uri);
+    }
+    for (prefix, uri) in &overlay.namespaces {
+        if uri != TOOLS_NAMESPACE {
+            merged.declare_namespace(prefix, uri);
+        }
+    }
+    Ok(merged)
+}
+
+fn merge_element(
+    merged: &mut Element,
+    overlay: &Element,
+    path: &str,
+    policy: MergePolicy,
+    with_children: bool,
+) -> Result<(), MergeError> {
+    let replace = tools_list(overlay, "replace");
+    for attr in overlay.typed_attributes.iter().filter(|a| !is_tools(a)) {
+        let existing = merged
+            .typed_attributes
+            .iter()
+            .find(|a| same_attribute(a, attr));
+        match existing {
+            Some(existing) if existing.value == attr.value => continue,
+            Some(existing)
+                if policy == MergePolicy::Error
+                    && !replace.iter().any(|name| names_attribute(name, attr)) =>
+            {
+                return Err(MergeError::Conflict {
+                    path: path.to_string(),
+                    attribute: attr.qualified_name.clone(),
+                    base: existing.value.clone(),
+                    overlay: attr.value.clone(),
+                });
+            }
+            Some(_)
+                if policy == MergePolicy::PreferBase
+                    && !replace.iter().any(|name| names_attribute(name, attr)) =>
+            {
+                continue
+            }
+            Some(existing) => {
+                // Keep the name the base uses, should the prefixes differ.
+                let attr = Attribute {
+                    qualified_name: existing.qualified_name.clone(),
+                    ..attr.clone()
+                };
+                merged.put_attribute(attr);
+            }
+            None => merged.put_attribute(attr.clone()),
+        }
+    }
+    let remove = tools_list(overlay, "remove");
+    merged.retain_attributes(|a| !remove.iter().any(|name| names_attribute(name, a)));
+
+    if !with_children {
+        return Ok(());
+    }
+    for child in overlay.get_children() {
+        let child = match child {
+            Node::Element(child) => child,
+            Node::Cdata(_) => continue,
+        };
+        let operation = tools_value(child, "node").unwrap_or("merge");
+        let position = merged.get_children().iter().position(|c| match c {
+            Node::Element(e) => refers_to(e, child),
+            Node::Cdata(_) => false,
+        });
+        let child_path = match position {
+            Some(i) => element_path(merged, path, i),
+            None => format!("{}/{}", path, child.get_tag()),
+        };
+
+        match (operation, position) {
+            ("remove", Some(i)) => {
+                merged.get_children_mut().remove(i);
+            }
+            ("removeAll", _) => merged
+                .get_children_mut()
+                .retain(|c| !matches!(c, Node::Element(e) if e.get_tag() == child.get_tag())),
+            ("remove", None) => (),
+            ("replace", Some(i)) => merged.get_children_mut()[i] = Node::Element(stripped(child)),
+            ("merge" | "merge-only-attributes" | "replace", None) => merged
+                .get_children_mut()
+                .push(Node::Element(stripped(child))),
+            (operation @ ("merge" | "merge-only-attributes"), Some(i)) => {
+                if let Node::Element(e) = &mut merged.get_children_mut()[i] {
+                    merge_element(e, child, &child_path, policy, operation == "merge")?;
+                }
+            }
+            (operation, _) => {
+                return Err(MergeError::UnsupportedOperation {
+                    path: child_path,
+                    operation: operation.to_string(),
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+///Whether the element `base` is the one `overlay` refers to.
+fn refers_to(base: &Element, overlay: &Element) -> bool {
+    if base.get_tag() != overlay.get_tag() {
+        return false;
+    }
+    if base.get_tag() == "intent-filter" {
+        return IntentFilter::from_element(base) == IntentFilter::from_element(overlay);
+    }
+    base.android_value("name") == overlay.android_value("name")
+}
+
+///Returns the path of the `index`th child of `parent`, which must be an
+///element.
+fn element_path(parent: &Element, path: &str, index: usize) -> String {
+    let element = match &parent.get_children()[index] {
+        Node::Element(e) => e,
+        Node::Cdata(_) => unreachable!("only elements are matched"),
+    };
+    child_paths(parent, path)
+        .into_iter()
+        .find(|(_, e)| std::ptr::eq(*e, element))
+        .map(|(path, _)| path)
+        .unwrap_or_default()
+}
+
+///Copies an element added from the overlay without its `tools:` attributes
+///and without descendants that are marked for removal.
+fn stripped(element: &Element) -> Element {
+    let mut element = element.clone();
+    strip(&mut element);
+    element
+}
+
+fn strip(element: &mut Element) {
+    element.retain_attributes(|a| !is_tools(a));
+    element
+        .namespace_declarations
+        .retain(|(_, uri)| uri != TOOLS_NAMESPACE);
+    element.get_children_mut().retain(|c| match c {
+        Node::Element(e) => !matches!(tools_value(e, "node"), Some("remove" | "removeAll")),
+        Node::Cdata(_) => true,
+    });
+    for child in element.get_children_mut() {
+        if let Node::Element(e) = child {
+            strip(e);
+        }
+    }
+}
+
+fn is_tools(attr: &Attribute) -> bool {
+    match &attr.namespace {
+        Some(namespace) => namespace == TOOLS_NAMESPACE,
+        None => split_prefix(&attr.qualified_name).0 == Some("tools"),
+    }
+}
+
+fn tools_value<'e>(element: &'e Element, name: &str) -> Option<&'e str> {
+    element
+        .typed_attributes
+        .iter()
+        .find(|a| a.name == name && is_tools(a))
+        .map(|a| a.value.as_str())
+}
+
+///Returns the comma separated attribute names of a `tools:` attribute.
+fn tools_list<'e>(element: &'e Element, name: &str) -> Vec<&'e str> {
+    tools_value(element, name)
+        .map(|v| v.split(',').map(str::trim).collect())
+        .unwrap_or_default()
+}
+
+///Whether two attributes set the same name, comparing attributes in the
+///android namespace by name whatever their prefix.
+fn same_attribute(a: &Attribute, b: &Attribute) -> bool {
+    if a.is_android() || b.is_android() {
+        a.is_android() && b.is_android() && a.name == b.name
+    } else {
+        a.qualified_name == b.qualified_name
+    }
+}
+
+///Whether `name`, as written in `tools:replace` or `tools:remove`, refers to
+///`attr`.
+fn names_attribute(name: &str, attr: &Attribute) -> bool {
+    match split_prefix(name) {
+        (Some("android"), local) => attr.is_android() && attr.name == local,
+        _ => attr.qualified_name == name,
+    }
+}
+
+#[test]
+fn test_merge() {
+    use crate::test_support::{element, manifest_document};
+
+    let base = manifest_document(vec![
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.CAMERA")],
+            vec![],
+        ),
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.INTERNET")],
+            vec![],
+        ),
+        element(
+            "application",
+            &[("android:label", "Base"), ("android:allowBackup", "true")],
+            vec![
+                element(
+                    "activity",
+                    &[("android:name", ".Main")],
+                    vec![element("intent-filter", &[], vec![])],
+                ),
+                element("receiver", &[("android:name", ".Boot")], vec![]),
+                element("service", &[("android:name", ".Sync")], vec![]),
+            ],
+        ),
+    ]);
+    let overlay = manifest_document(vec![
+        element(
+            "uses-permission",
+            &[
+                ("android:name", "android.permission.CAMERA"),
+                ("tools:node", "remove"),
+            ],
+            vec![],
+        ),
+        element(
+            "uses-permission",
+            &[("android:name", "android.permission.VIBRATE")],
+            vec![],
+        ),
+        element(
+            "application",
+            &[
+                ("android:debuggable", "true"),
+                ("tools:remove", "android:allowBackup"),
+            ],
+            vec![
+                element(
+                    "activity",
+                    &[("android:name", ".Main"), ("android:exported", "true")],
+                    vec![element("intent-filter", &[], vec![])],
+                ),
+                element(
+                    "receiver",
+                    &[("android:name", ".Boot"), ("tools:node", "replace")],
+                    vec![element("intent-filter", &[], vec![])],
+                ),
+            ],
+        ),
+    ]);
+
+    let merged = merge(&base, &overlay, MergePolicy::Error).unwrap();
+    let names = |tag| -> Vec<String> {
+        merged
+            .get_elements_by_tag_name(tag)
+            .iter()
+            .map(|e| e.get_attribute("android:name").unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        names("uses-permission"),
+        ["android.permission.INTERNET", "android.permission.VIBRATE"]
+    );
+    assert_eq!(names("service"), [".Sync"]);
+
+    let application = merged.get_elements_by_tag_name("application")[0];
+    let mut attributes: Vec<_> = application.attributes().collect();
+    attributes.sort_unstable();
+    assert_eq!(
+        attributes,
+        [("android:debuggable", "true"), ("android:label", "Base")]
+    );
+    let activity = merged.get_elements_by_tag_name("activity")[0];
+    assert_eq!(activity.get_attribute("android:exported"), Some("true"));
+    assert_eq!(merged.get_elements_by_tag_name("intent-filter").len(), 2);
+    let receiver = merged.get_elements_by_tag_name("receiver")[0];
+    assert!(receiver.get_attribute("tools:node").is_none());
+    assert_eq!(receiver.get_children().len(), 1);
+
+    let conflicting = manifest_document(vec![element(
+        "application",
+        &[("android:label", "Overlay")],
+        vec![],
+    )]);
+    assert_eq!(
+        merge(&base, &conflicting, MergePolicy::Error).unwrap_err(),
+        MergeError::Conflict {
+            path: "manifest/application".to_string(),
+            attribute: "android:label".to_string(),
+            base: "Base".to_string(),
+            overlay: "Overlay".to_string(),
+        }
+    );
+    let label = |policy| {
+        let merged = merge(&base, &conflicting, policy).unwrap();
+        let application = merged.get_elements_by_tag_name("application")[0];
+        application
+            .get_attribute("android:label")
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(label(MergePolicy::PreferOverlay), "Overlay");
+    assert_eq!(label(MergePolicy::PreferBase), "Base");
+
+    let other_root = XmlDocument::new(Node::Element(Element::new("resources")));
+    assert!(matches!(
+        merge(&base, &other_root, MergePolicy::Error),
+        Err(MergeError::RootMismatch { .. })
+    ));
+}
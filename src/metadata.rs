@@ -0,0 +1,124 @@
+use crate::xml::XmlDocument;
+
+///Characteristics of the binary XML a document was parsed from, for tools
+///that want to reproduce them when encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DocumentMetadata {
+    ///Whether the string pool is encoded as UTF-8 rather than UTF-16.
+    pub utf8_strings: bool,
+    ///Whether the string pool has any styled strings.
+    pub has_styles: bool,
+    ///Number of resource ids in the resource map.
+    pub resource_map_len: usize,
+    ///The resource map, as returned by [XmlDocument::resource_map].
+    pub resource_map: Vec<ResourceMapEntry>,
+    ///Size of the document as declared by the XML chunk.
+    pub declared_size: u32,
+    ///Number of bytes from the start of the document to the end of the
+    ///input.
+    ///For input parsed without seeking, this is where the document ends.
+    pub file_size: u64,
+    ///Every chunk that was read, in the order it appears in the input,
+    ///starting with the XML chunk containing all others.
+    pub chunks: Vec<ChunkInfo>,
+}
+
+///Where a chunk was found and how much of it was read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChunkInfo {
+    ///The chunk type, such as `0x0102` for a start element.
+    pub typ: u16,
+    ///Offset of the chunk from the start of the document.
+    pub offset: u64,
+    ///The total size declared by the chunk.
+    pub declared_size: u32,
+    ///Number of bytes that were actually read, which is less than the
+    ///declared size for chunks with padding or unused trailing data.
+    pub bytes_read: u64,
+}
+
+///An attribute resource id of the resource map and the string of the same
+///index in the string pool, which is the name of the attribute it stands
+///for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ResourceMapEntry {
+    ///The resource id, such as `0x01010003` for `android:name`.
+    pub id: u32,
+    ///The attribute name from the string pool, or `None` if the pool has
+    ///fewer strings than the map has ids.
+    pub name: Option<String>,
+}
+
+impl XmlDocument {
+    ///Returns the resource ids of the attributes referenced by the binary
+    ///XML the document was parsed from, in resource map order, with their
+    ///names from the string pool. Documents without [metadata] have none.
+    ///
+    ///Names are whatever the compiler wrote, so comparing them with
+    ///[framework_attribute_name](crate::framework_attribute_name) finds
+    ///ids that were renamed, as obfuscators do.
+    ///
+    ///[metadata]: XmlDocument::metadata
+    ///
+    ///```rust
+    ///use axmldecoder::{framework_attribute_name, parse};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///for entry in xml.resource_map() {
+    ///    assert_eq!(entry.name.as_deref(), framework_attribute_name(entry.id));
+    ///}
+    ///```
+    pub fn resource_map(&self) -> &[ResourceMapEntry] {
+        self.metadata
+            .as_ref()
+            .map_or(&[], |metadata| &metadata.resource_map)
+    }
+
+    ///Returns metadata about the binary XML the document was parsed from,
+    ///or `None` if it was built by hand or parsed from another format.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let metadata = xml.metadata().unwrap();
+    ///assert!(!metadata.utf8_strings);
+    ///assert_eq!(metadata.chunks[0].typ, 0x0003);
+    ///```
+    pub fn metadata(&self) -> Option<&DocumentMetadata> {
+        self.metadata.as_ref()
+    }
+}
+
+#[test]
+fn test_metadata() {
+    let data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut std::io::Cursor::new(&data)).unwrap();
+    let metadata = xml.metadata().unwrap();
+
+    let raw = crate::read_chunks(&mut std::io::Cursor::new(&data)).unwrap();
+    let order: Vec<_> = metadata.chunks.iter().map(|c| (c.typ, c.offset)).collect();
+    let expected: Vec<_> = raw.iter().map(|c| (c.typ, c.offset)).collect();
+    assert_eq!(order, expected);
+    assert_eq!(metadata.declared_size as usize, data.len());
+    assert_eq!(metadata.file_size as usize, data.len());
+    assert!(metadata.resource_map_len > 0);
+    assert_eq!(xml.resource_map().len(), metadata.resource_map_len);
+    assert_eq!(
+        xml.resource_map()[0],
+        ResourceMapEntry {
+            id: 0x0101021b,
+            name: Some("versionCode".to_string())
+        }
+    );
+    assert!(!metadata.has_styles);
+
+    let built = XmlDocument::new(crate::Node::Cdata(crate::Cdata::new("")));
+    assert!(built.metadata().is_none());
+    assert!(built.resource_map().is_empty());
+}
@@ -0,0 +1,177 @@
+use crate::xml::{Element, Node, XmlDocument};
+
+///Identifies a node of an [XmlDocument] by its position in document order,
+///counting every element and text node of every root.
+///
+///Ids are plain numbers, so they can be stored in analysis results without
+///borrowing the document. They stay valid until the document is modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NodeId(usize);
+
+impl NodeId {
+    ///Returns the position of the node in document order.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl XmlDocument {
+    ///Returns every node with its id, in document order.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, Node};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let activities: Vec<_> = xml
+    ///    .nodes()
+    ///    .filter(|(_, node)| matches!(node, Node::Element(e) if e.get_tag() == "activity"))
+    ///    .map(|(id, _)| id)
+    ///    .collect();
+    ///for id in activities {
+    ///    assert!(matches!(xml.get(id), Some(Node::Element(_))));
+    ///}
+    ///```
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &Node)> + '_ {
+        Nodes {
+            stack: self.roots().iter().rev().collect(),
+        }
+        .enumerate()
+        .map(|(i, node)| (NodeId(i), node))
+    }
+
+    ///Returns the node with the given id.
+    pub fn get(&self, id: NodeId) -> Option<&Node> {
+        self.nodes().nth(id.0).map(|(_, node)| node)
+    }
+
+    ///Returns a mutable reference to the node with the given id.
+    pub fn get_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        let mut remaining = id.0;
+        self.roots_mut()
+            .iter_mut()
+            .find_map(|node| nth_node_mut(node, &mut remaining))
+    }
+
+    ///Returns the id of `element`, which must be borrowed from this
+    ///document, such as an element returned by [XmlDocument::find_all].
+    pub fn node_id(&self, element: &Element) -> Option<NodeId> {
+        self.nodes()
+            .find(|(_, node)| matches!(node, Node::Element(e) if std::ptr::eq(e, element)))
+            .map(|(id, _)| id)
+    }
+
+    ///Returns the indices leading to the node with the given id: the index
+    ///of its root, then of each child on the way down.
+    pub(crate) fn path_of(&self, id: NodeId) -> Option<Vec<usize>> {
+        let mut remaining = id.0;
+        let mut path = Vec::new();
+        if locate(self.roots(), &mut remaining, &mut path) {
+            Some(path)
+        } else {
+            None
+        }
+    }
+}
+
+///Pushes the indices leading to the node `remaining` positions into `nodes`
+///in document order onto `path`, or subtracts the number of nodes from
+///`remaining`.
+fn locate(nodes: &[Node], remaining: &mut usize, path: &mut Vec<usize>) -> bool {
+    for (i, node) in nodes.iter().enumerate() {
+        path.push(i);
+        if *remaining == 0 {
+            return true;
+        }
+        *remaining -= 1;
+        if let Node::Element(e) = node {
+            if locate(e.get_children(), remaining, path) {
+                return true;
+            }
+        }
+        path.pop();
+    }
+    false
+}
+
+///Visits nodes depth first in document order.
+struct Nodes<'a> {
+    ///Nodes still to visit, the next one last.
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if let Node::Element(e) = node {
+            self.stack.extend(e.get_children().iter().rev());
+        }
+        Some(node)
+    }
+}
+
+///Returns the node `remaining` positions into the subtree of `node` in
+///document order, or subtracts the size of the subtree from `remaining`.
+fn nth_node_mut<'a>(node: &'a mut Node, remaining: &mut usize) -> Option<&'a mut Node> {
+    if *remaining == 0 {
+        return Some(node);
+    }
+    *remaining -= 1;
+    match node {
+        Node::Element(e) => e
+            .get_children_mut()
+            .iter_mut()
+            .find_map(|child| nth_node_mut(child, remaining)),
+        Node::Cdata(_) => None,
+    }
+}
+
+#[test]
+fn test_node_ids() {
+    use crate::Cdata;
+
+    let mut activity = Element::new("activity");
+    activity
+        .get_children_mut()
+        .push(Node::Cdata(Cdata::new("text")));
+    let mut application = Element::new("application");
+    application.get_children_mut().push(Node::Element(activity));
+    application
+        .get_children_mut()
+        .push(Node::Element(Element::new("service")));
+    let mut xml = XmlDocument::new(Node::Element(application));
+
+    let tags: Vec<_> = xml
+        .nodes()
+        .map(|(id, node)| match node {
+            Node::Element(e) => (id.index(), e.get_tag().to_string()),
+            Node::Cdata(c) => (id.index(), c.get_data().to_string()),
+        })
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            (0, "application".to_string()),
+            (1, "activity".to_string()),
+            (2, "text".to_string()),
+            (3, "service".to_string())
+        ]
+    );
+
+    let service = xml.find_first(|e| e.get_tag() == "service").unwrap();
+    let id = xml.node_id(service).unwrap();
+    assert_eq!(id.index(), 3);
+    match xml.get_mut(id) {
+        Some(Node::Element(e)) => e.set_attribute("android:name", ".Sync"),
+        _ => panic!("missing service"),
+    }
+    match xml.get(id) {
+        Some(Node::Element(e)) => assert_eq!(e.get_attribute("android:name"), Some(".Sync")),
+        _ => panic!("missing service"),
+    }
+    assert!(xml.get(NodeId(4)).is_none());
+    assert!(xml.get_mut(NodeId(4)).is_none());
+}
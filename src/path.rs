@@ -0,0 +1,514 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::xml::{split_prefix, Element, Node, XmlDocument, ANDROID_NAMESPACE};
+
+///A path selecting elements of a document, such as
+///`manifest/application/activity[2]` or
+///`manifest/uses-permission[@android:name='android.permission.CAMERA']`.
+///
+///The first step names the root element and each following step children
+///of the elements selected so far. A step selects every child with its tag,
+///narrowed down by a one-based position among them, `[n]`, or by the value
+///of an attribute, `[@name='value']`, or both. Values are quoted with `'`
+///or `"` and may contain `/` and `]`. Prefixes are resolved through the
+///namespaces the document declares, so `android:name` also matches an
+///attribute whose prefix was renamed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementPath {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    tag: String,
+    position: Option<usize>,
+    attribute: Option<(String, String)>,
+}
+
+///Why a string is not a valid [ElementPath].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid path {path}: {reason}")]
+pub struct PathError {
+    pub path: String,
+    pub reason: &'static str,
+}
+
+impl FromStr for ElementPath {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let error = |reason| PathError {
+            path: path.to_string(),
+            reason,
+        };
+
+        // Steps and filters are split outside of quoted values only, which
+        // may contain `/` and `]`.
+        let mut steps = Vec::new();
+        let mut rest = path.trim_matches('/');
+        loop {
+            let (step, next) = match find_unquoted(rest, '/') {
+                Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+                None => (rest, None),
+            };
+            steps.push(Step::parse(step).map_err(error)?);
+            match next {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+
+        Ok(Self { steps })
+    }
+}
+
+impl Step {
+    fn parse(step: &str) -> Result<Self, &'static str> {
+        let (tag, mut filters) = step.split_at(step.find('[').unwrap_or(step.len()));
+        if tag.is_empty() {
+            return Err("empty step");
+        }
+
+        let mut step = Step {
+            tag: tag.to_string(),
+            position: None,
+            attribute: None,
+        };
+        while let Some(rest) = filters.strip_prefix('[') {
+            let end = find_unquoted(rest, ']').ok_or("unclosed filter")?;
+            let filter = &rest[..end];
+            filters = &rest[end + 1..];
+            match filter.strip_prefix('@') {
+                Some(filter) => {
+                    let (name, value) = filter
+                        .split_once('=')
+                        .ok_or("attribute filter without a value")?;
+                    let value = ["'", "\""]
+                        .iter()
+                        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+                        .ok_or("attribute value is not quoted")?;
+                    step.attribute = Some((name.to_string(), value.to_string()));
+                }
+                None => match filter.parse::<usize>() {
+                    Ok(position) if position > 0 => step.position = Some(position),
+                    _ => return Err("positions are numbers starting from 1"),
+                },
+            }
+        }
+        if !filters.is_empty() {
+            return Err("unexpected text after a filter");
+        }
+        Ok(step)
+    }
+}
+
+///Returns the index of the first `target` in `s` that is not inside a
+///quoted attribute value.
+fn find_unquoted(s: &str, target: char) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None if c == target => return Some(i),
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None => (),
+        }
+    }
+    None
+}
+
+impl fmt::Display for ElementPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.tag)?;
+        if let Some(position) = self.position {
+            write!(f, "[{}]", position)?;
+        }
+        if let Some((name, value)) = &self.attribute {
+            let quote = if value.contains('\'') { '"' } else { '\'' };
+            write!(f, "[@{}={}{}{}]", name, quote, value, quote)?;
+        }
+        Ok(())
+    }
+}
+
+///Selects elements either by an [ElementPath] or by a predicate, for
+///[XmlDocument::remove_matching].
+pub enum Selector<'a> {
+    Path(ElementPath),
+    ///Selects every element the predicate returns true for. Descendants of
+    ///a selected element are not looked at.
+    Predicate(Box<dyn FnMut(&Element) -> bool + 'a>),
+}
+
+impl From<ElementPath> for Selector<'_> {
+    fn from(path: ElementPath) -> Self {
+        Selector::Path(path)
+    }
+}
+
+impl<'a, F: FnMut(&Element) -> bool + 'a> From<F> for Selector<'a> {
+    fn from(predicate: F) -> Self {
+        Selector::Predicate(Box::new(predicate))
+    }
+}
+
+impl Element {
+    ///Returns the path of the element in `document`, such as
+    ///`manifest/application/activity[3]`, or `None` if the element is not
+    ///part of it.
+    ///
+    ///Elements do not know their parents, so the path is found by looking
+    ///for this very element, not an equal one, in `document`. Each step is
+    ///the tag of an element, with its one-based position among the siblings
+    ///sharing that tag if there is more than one. Lints, validation issues
+    ///and other diagnostics of this crate name elements the same way, and
+    ///[XmlDocument::select] takes the path back to the element.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let activity = xml.find_first(|e| e.get_tag() == "activity").unwrap();
+    ///let path = activity.path(&xml).unwrap();
+    ///assert_eq!(path.to_string(), "manifest/application/activity");
+    ///assert!(std::ptr::eq(xml.select(&path)[0], activity));
+    ///```
+    pub fn path(&self, document: &XmlDocument) -> Option<ElementPath> {
+        let mut steps = Vec::new();
+        if find_steps(document.roots(), self, &mut steps) {
+            Some(ElementPath { steps })
+        } else {
+            None
+        }
+    }
+}
+
+impl XmlDocument {
+    ///Returns the elements `path` selects, in document order.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, ElementPath};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let path: ElementPath = "manifest/application/activity".parse().unwrap();
+    ///assert_eq!(xml.select(&path).len(), 1);
+    ///```
+    pub fn select(&self, path: &ElementPath) -> Vec<&Element> {
+        path.locate(self)
+            .iter()
+            .filter_map(|location| {
+                let (first, rest) = location.split_first()?;
+                let mut element = match self.roots().get(*first)? {
+                    Node::Element(e) => e,
+                    Node::Cdata(_) => return None,
+                };
+                for &i in rest {
+                    element = match element.get_children().get(i)? {
+                        Node::Element(e) => e,
+                        Node::Cdata(_) => return None,
+                    };
+                }
+                Some(element)
+            })
+            .collect()
+    }
+
+    ///Calls `f` on each element `path` selects, in document order, and
+    ///returns how many there were. This is the mutable counterpart of
+    ///[XmlDocument::select].
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, ElementPath};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut xml = parse(&mut std::fs::File::open(manifest_file).unwrap()).unwrap();
+    ///let path: ElementPath = "manifest/application/activity".parse().unwrap();
+    ///let n = xml.select_mut(&path, |activity| {
+    ///    activity.set_attribute("android:exported", "false");
+    ///});
+    ///assert_eq!(n, 1);
+    ///```
+    pub fn select_mut(&mut self, path: &ElementPath, mut f: impl FnMut(&mut Element)) -> usize {
+        let locations = path.locate(self);
+        for location in &locations {
+            if let Some(element) = self.element_at_mut(location) {
+                f(element);
+            }
+        }
+        locations.len()
+    }
+}
+
+///Looks for `target` below `nodes`, pushing the steps leading to it onto
+///`steps`. Returns whether it was found.
+fn find_steps(nodes: &[Node], target: &Element, steps: &mut Vec<Step>) -> bool {
+    for (step, element) in sibling_steps(nodes) {
+        steps.push(step);
+        if std::ptr::eq(element, target) || find_steps(element.get_children(), target, steps) {
+            return true;
+        }
+        steps.pop();
+    }
+    false
+}
+
+///Returns the elements among `nodes` with the steps naming them. Siblings
+///sharing a tag are told apart by their 1-based position among each other.
+fn sibling_steps(nodes: &[Node]) -> Vec<(Step, &Element)> {
+    let elements: Vec<&Element> = nodes
+        .iter()
+        .filter_map(|c| match c {
+            Node::Element(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+
+    let mut totals: HashMap<&str, usize> = HashMap::new();
+    for element in &elements {
+        *totals.entry(element.get_tag()).or_default() += 1;
+    }
+
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    elements
+        .into_iter()
+        .map(|element| {
+            let tag = element.get_tag();
+            let count = seen.entry(tag).or_default();
+            *count += 1;
+            let position = if totals[tag] > 1 { Some(*count) } else { None };
+            let step = Step {
+                tag: tag.to_string(),
+                position,
+                attribute: None,
+            };
+            (step, element)
+        })
+        .collect()
+}
+
+///Returns the child elements of `element` with their paths, given the path
+///of `element`, in the form of [Element::path].
+pub(crate) fn child_paths<'a>(element: &'a Element, path: &str) -> Vec<(String, &'a Element)> {
+    sibling_steps(element.get_children())
+        .into_iter()
+        .map(|(step, child)| (format!("{}/{}", path, step), child))
+        .collect()
+}
+
+impl ElementPath {
+    ///Returns the positions of the selected elements, each as the index of
+    ///its root followed by the child indices leading to it, in document
+    ///order.
+    pub(crate) fn locate(&self, document: &XmlDocument) -> Vec<Vec<usize>> {
+        let mut found = Vec::new();
+        let (first, rest) = match self.steps.split_first() {
+            Some(steps) => steps,
+            None => return found,
+        };
+        let scope = Scope(document);
+        let roots = document
+            .roots()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, node)| match node {
+                Node::Element(e) => Some((i, e)),
+                Node::Cdata(_) => None,
+            });
+        for (i, root) in first.select(roots, &scope) {
+            locate_steps(root, rest, &scope, &mut vec![i], &mut found);
+        }
+        found
+    }
+}
+
+fn locate_steps(
+    element: &Element,
+    steps: &[Step],
+    scope: &Scope,
+    path: &mut Vec<usize>,
+    found: &mut Vec<Vec<usize>>,
+) {
+    let (step, rest) = match steps.split_first() {
+        Some(steps) => steps,
+        None => return found.push(path.clone()),
+    };
+    let children = element
+        .get_children()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, node)| match node {
+            Node::Element(e) => Some((i, e)),
+            Node::Cdata(_) => None,
+        });
+    for (i, child) in step.select(children, scope) {
+        path.push(i);
+        locate_steps(child, rest, scope, path, found);
+        path.pop();
+    }
+}
+
+impl Step {
+    ///Filters `(index, element)` pairs of siblings down to the ones the step
+    ///selects.
+    fn select<'e>(
+        &self,
+        siblings: impl Iterator<Item = (usize, &'e Element)>,
+        scope: &Scope,
+    ) -> Vec<(usize, &'e Element)> {
+        siblings
+            .filter(|(_, e)| scope.names_match(e.get_tag(), e.get_namespace(), &e.name, &self.tag))
+            .enumerate()
+            .filter(|(n, _)| self.position.map_or(true, |position| position == n + 1))
+            .map(|(_, sibling)| sibling)
+            .filter(|(_, e)| match &self.attribute {
+                Some((name, value)) => e.typed_attributes.iter().any(|a| {
+                    a.value == *value
+                        && scope.names_match(
+                            &a.qualified_name,
+                            a.namespace.as_deref(),
+                            &a.name,
+                            name,
+                        )
+                }),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+///Resolves the prefixes of a path through the namespaces of a document.
+pub(crate) struct Scope<'a>(pub(crate) &'a XmlDocument);
+
+impl Scope<'_> {
+    fn uri(&self, prefix: &str) -> Option<&str> {
+        match self.0.namespaces.iter().find(|(p, _)| p == prefix) {
+            Some((_, uri)) => Some(uri),
+            None if prefix == "android" => Some(ANDROID_NAMESPACE),
+            None => None,
+        }
+    }
+
+    ///Returns whether a tag or attribute name matches `name` from a path,
+    ///either literally or by namespace URI and local name.
+    pub(crate) fn names_match(
+        &self,
+        qualified_name: &str,
+        namespace: Option<&str>,
+        local_name: &str,
+        name: &str,
+    ) -> bool {
+        if qualified_name == name {
+            return true;
+        }
+        match split_prefix(name) {
+            (Some(prefix), local) => {
+                local == local_name && namespace.is_some() && namespace == self.uri(prefix)
+            }
+            (None, _) => false,
+        }
+    }
+}
+
+#[test]
+fn test_parse_path() {
+    let path: ElementPath = "/manifest/application/activity[2]".parse().unwrap();
+    assert_eq!(path.to_string(), "manifest/application/activity[2]");
+
+    let path: ElementPath = "manifest/uses-permission[@android:name=\"android.permission.CAMERA\"]"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        path.to_string(),
+        "manifest/uses-permission[@android:name='android.permission.CAMERA']"
+    );
+
+    // Quoted values may contain the characters that separate steps and
+    // close filters.
+    for path in [
+        "manifest/application/provider/path-permission[@android:pathPrefix='/x']",
+        "manifest/application/activity[2][@android:name='a/b]c']",
+        "manifest/application/activity[@android:label=\"it's\"]",
+    ] {
+        assert_eq!(path.parse::<ElementPath>().unwrap().to_string(), path);
+    }
+    let path: ElementPath = "manifest/data[@android:pathPrefix='/x]/y']"
+        .parse()
+        .unwrap();
+    assert_eq!(path.steps.len(), 2);
+    assert_eq!(
+        path.steps[1].attribute,
+        Some(("android:pathPrefix".to_string(), "/x]/y".to_string()))
+    );
+
+    assert!("manifest//application".parse::<ElementPath>().is_err());
+    assert!("manifest/activity[@android:name='x]"
+        .parse::<ElementPath>()
+        .is_err());
+    assert!("manifest/activity[1]x".parse::<ElementPath>().is_err());
+    assert!("manifest/activity[0]".parse::<ElementPath>().is_err());
+    assert!("manifest/activity[@android:name=x]"
+        .parse::<ElementPath>()
+        .is_err());
+}
+
+#[test]
+fn test_element_path() {
+    let mut application = Element::new("application");
+    for name in [".First", ".Second", ".Third"].iter() {
+        let mut activity = Element::new("activity");
+        activity.set_attribute("android:name", name);
+        application.get_children_mut().push(Node::Element(activity));
+    }
+    application
+        .get_children_mut()
+        .push(Node::Element(Element::new("service")));
+    let mut manifest = Element::new("manifest");
+    manifest.get_children_mut().push(Node::Element(application));
+    let xml = XmlDocument::new(Node::Element(manifest));
+
+    let third = xml
+        .find_first(|e| e.get_attribute("android:name") == Some(".Third"))
+        .unwrap();
+    let path = third.path(&xml).unwrap();
+    assert_eq!(path.to_string(), "manifest/application/activity[3]");
+    let selected = xml.select(&path);
+    assert_eq!(selected.len(), 1);
+    assert!(std::ptr::eq(selected[0], third));
+
+    let service = xml.find_first(|e| e.get_tag() == "service").unwrap();
+    assert_eq!(
+        service.path(&xml).unwrap().to_string(),
+        "manifest/application/service"
+    );
+    assert_eq!(third.clone().path(&xml), None);
+
+    let mut xml = xml;
+    let mut activity = Element::new("activity");
+    activity.set_attribute("android:name", "com.example/a]b");
+    let application = "manifest/application".parse().unwrap();
+    xml.insert(&application, 0, Node::Element(activity))
+        .unwrap();
+    let path: ElementPath = "manifest/application/activity[@android:name='com.example/a]b']"
+        .parse()
+        .unwrap();
+    assert_eq!(xml.select(&path).len(), 1);
+    assert_eq!(xml.remove_matching(path).len(), 1);
+}
@@ -0,0 +1,217 @@
+use std::fmt;
+
+use crate::manifest::{child_elements, Manifest};
+use crate::resource_value::{format_flags, parse_flags, Flag};
+use crate::xml::{Attribute, Element};
+
+///Names of the protection levels and their flags, as declared in the
+///framework's `attrs_manifest.xml`. The level is stored in the lowest four
+///bits, the flags above it.
+pub(crate) const PROTECTION_LEVELS: &[Flag] = &[
+    (0xf, 0, "normal"),
+    (0xf, 1, "dangerous"),
+    (0xf, 2, "signature"),
+    (0xf, 3, "signatureOrSystem"),
+    (0xf, 4, "internal"),
+    (0x0010, 0x0010, "privileged"),
+    (0x0020, 0x0020, "development"),
+    (0x0040, 0x0040, "appop"),
+    (0x0080, 0x0080, "pre23"),
+    (0x0100, 0x0100, "installer"),
+    (0x0200, 0x0200, "verifier"),
+    (0x0400, 0x0400, "preinstalled"),
+    (0x0800, 0x0800, "setup"),
+    (0x1000, 0x1000, "instant"),
+    (0x2000, 0x2000, "runtime"),
+    (0x4000, 0x4000, "oem"),
+    (0x8000, 0x8000, "vendorPrivileged"),
+    (0x1_0000, 0x1_0000, "textClassifier"),
+    (0x400_0000, 0x400_0000, "role"),
+    (0x800_0000, 0x800_0000, "knownSigner"),
+];
+
+///The `android:protectionLevel` of a permission. It is displayed the way
+///it is written in a manifest, such as `signature|privileged`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProtectionLevel(pub u32);
+
+impl ProtectionLevel {
+    pub const NORMAL: Self = Self(0);
+    pub const DANGEROUS: Self = Self(1);
+    pub const SIGNATURE: Self = Self(2);
+    pub const SIGNATURE_OR_SYSTEM: Self = Self(3);
+
+    ///Returns the level without its flags.
+    pub fn base(self) -> Self {
+        Self(self.0 & 0xf)
+    }
+
+    ///Returns the flags without the level.
+    pub fn flags(self) -> u32 {
+        self.0 & !0xf
+    }
+
+    ///Returns whether the permission is granted at runtime by the user.
+    pub fn is_dangerous(self) -> bool {
+        self.base() == Self::DANGEROUS
+    }
+
+    ///Returns whether the permission is only granted to apps signed with
+    ///the same certificate as the app declaring it.
+    pub fn is_signature(self) -> bool {
+        matches!(self.base(), Self::SIGNATURE | Self::SIGNATURE_OR_SYSTEM)
+    }
+
+    ///Reads the level from an attribute that holds either the compiled
+    ///integer or the names, as in documents built by hand.
+    fn from_attribute(attr: &Attribute) -> Option<Self> {
+        attr.integer_value()
+            .or_else(|| parse_flags(PROTECTION_LEVELS, &attr.value))
+            .map(Self)
+    }
+}
+
+impl fmt::Display for ProtectionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match format_flags(PROTECTION_LEVELS, self.0) {
+            Some(names) => f.write_str(&names),
+            None => write!(f, "0x{:x}", self.0),
+        }
+    }
+}
+
+///A permission declared by a `permission` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Permission {
+    pub name: String,
+    ///The protection level, [ProtectionLevel::NORMAL] if not given.
+    pub protection_level: ProtectionLevel,
+    ///The `android:permissionGroup` the permission belongs to.
+    pub group: Option<String>,
+    pub label: Option<String>,
+    pub description: Option<String>,
+}
+
+///A group of permissions declared by a `permission-group` element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PermissionGroup {
+    pub name: String,
+    pub label: Option<String>,
+    pub description: Option<String>,
+}
+
+///A namespace of permissions declared by a `permission-tree` element. The
+///app may add permissions below `name` at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PermissionTree {
+    pub name: String,
+    pub label: Option<String>,
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the permissions the app declares. Elements without a name are
+    ///left out.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut permission = Element::new("permission");
+    ///permission.set_attribute("android:name", "com.example.READ");
+    ///permission.set_attribute("android:protectionLevel", "0x12");
+    ///let mut root = Element::new("manifest");
+    ///root.get_children_mut().push(Node::Element(permission));
+    ///
+    ///let xml = XmlDocument::new(Node::Element(root));
+    ///let permissions = xml.manifest().unwrap().permissions();
+    ///assert_eq!(permissions[0].protection_level.to_string(), "signature|privileged");
+    ///```
+    pub fn permissions(&self) -> Vec<Permission> {
+        self.declarations("permission", |e, name| Permission {
+            name,
+            protection_level: e
+                .android_attribute("protectionLevel")
+                .and_then(ProtectionLevel::from_attribute)
+                .unwrap_or_default(),
+            group: value(e, "permissionGroup"),
+            label: value(e, "label"),
+            description: value(e, "description"),
+        })
+    }
+
+    ///Returns the permission groups the app declares.
+    pub fn permission_groups(&self) -> Vec<PermissionGroup> {
+        self.declarations("permission-group", |e, name| PermissionGroup {
+            name,
+            label: value(e, "label"),
+            description: value(e, "description"),
+        })
+    }
+
+    ///Returns the permission trees the app declares.
+    pub fn permission_trees(&self) -> Vec<PermissionTree> {
+        self.declarations("permission-tree", |e, name| PermissionTree {
+            name,
+            label: value(e, "label"),
+        })
+    }
+
+    fn declarations<T>(&self, tag: &str, f: impl Fn(&Element, String) -> T) -> Vec<T> {
+        child_elements(self.root())
+            .filter(|e| e.get_tag() == tag)
+            .filter_map(|e| Some(f(e, value(e, "name")?)))
+            .collect()
+    }
+}
+
+fn value(e: &Element, name: &str) -> Option<String> {
+    e.android_value(name).map(str::to_string)
+}
+
+#[test]
+fn test_permissions() {
+    use crate::{Node, XmlDocument};
+
+    let mut root = Element::new("manifest");
+    for (tag, name, level) in [
+        ("permission", "com.example.A", None),
+        ("permission", "com.example.B", Some("dangerous")),
+        ("permission", "com.example.C", Some("0x00000012")),
+        ("permission-group", "com.example.GROUP", None),
+        ("permission-tree", "com.example.tree", None),
+    ] {
+        let mut e = Element::new(tag);
+        e.set_attribute("android:name", name);
+        if let Some(level) = level {
+            e.set_attribute("android:protectionLevel", level);
+        }
+        root.get_children_mut().push(Node::Element(e));
+    }
+    root.get_children_mut()
+        .push(Node::Element(Element::new("permission")));
+    let xml = XmlDocument::new(Node::Element(root));
+    let manifest = xml.manifest().unwrap();
+
+    let levels: Vec<_> = manifest
+        .permissions()
+        .iter()
+        .map(|p| p.protection_level)
+        .collect();
+    assert_eq!(
+        levels,
+        [
+            ProtectionLevel::NORMAL,
+            ProtectionLevel::DANGEROUS,
+            ProtectionLevel(0x12)
+        ]
+    );
+    assert!(levels[1].is_dangerous());
+    assert!(levels[2].is_signature());
+    assert_eq!(levels[2].flags(), 0x10);
+    assert_eq!(levels[0].to_string(), "normal");
+    assert_eq!(ProtectionLevel(0x2_0000).to_string(), "0x20000");
+    assert_eq!(manifest.permission_groups()[0].name, "com.example.GROUP");
+    assert_eq!(manifest.permission_trees()[0].name, "com.example.tree");
+}
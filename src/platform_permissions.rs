@@ -0,0 +1,221 @@
+//!The protection levels of the permissions defined by the platform,
+//!available with the `permission-levels` feature.
+
+use std::collections::HashSet;
+
+use crate::manifest::{child_elements, Manifest};
+use crate::permissions::ProtectionLevel;
+
+///A permission defined by the Android platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlatformPermission {
+    pub name: &'static str,
+    ///The protection level with its flags, as declared in the framework's
+    ///`AndroidManifest.xml`.
+    pub protection_level: ProtectionLevel,
+    ///The API level that added the permission.
+    pub added_in: u32,
+}
+
+///Name, protection level and API level of the platform permissions apps
+///commonly request, from the framework's `AndroidManifest.xml`. Sorted by
+///name.
+const PLATFORM_PERMISSIONS: &[(&str, u32, u32)] = &[
+    ("android.permission.ACCEPT_HANDOVER", 0x1, 28),
+    ("android.permission.ACCESS_BACKGROUND_LOCATION", 0x1, 29),
+    ("android.permission.ACCESS_COARSE_LOCATION", 0x1, 1),
+    ("android.permission.ACCESS_FINE_LOCATION", 0x1, 1),
+    ("android.permission.ACCESS_LOCATION_EXTRA_COMMANDS", 0x0, 1),
+    ("android.permission.ACCESS_MEDIA_LOCATION", 0x1, 29),
+    ("android.permission.ACCESS_NETWORK_STATE", 0x0, 1),
+    ("android.permission.ACCESS_NOTIFICATION_POLICY", 0x0, 23),
+    ("android.permission.ACCESS_WIFI_STATE", 0x0, 1),
+    ("android.permission.ACTIVITY_RECOGNITION", 0x1, 29),
+    ("android.permission.ANSWER_PHONE_CALLS", 0x1, 26),
+    ("android.permission.BIND_ACCESSIBILITY_SERVICE", 0x2, 16),
+    ("android.permission.BIND_DEVICE_ADMIN", 0x2, 8),
+    ("android.permission.BIND_INPUT_METHOD", 0x2, 3),
+    (
+        "android.permission.BIND_NOTIFICATION_LISTENER_SERVICE",
+        0x2,
+        18,
+    ),
+    ("android.permission.BIND_VPN_SERVICE", 0x2, 14),
+    ("android.permission.BLUETOOTH", 0x0, 1),
+    ("android.permission.BLUETOOTH_ADMIN", 0x0, 1),
+    ("android.permission.BLUETOOTH_ADVERTISE", 0x1, 31),
+    ("android.permission.BLUETOOTH_CONNECT", 0x1, 31),
+    ("android.permission.BLUETOOTH_SCAN", 0x1, 31),
+    ("android.permission.BODY_SENSORS", 0x1, 20),
+    ("android.permission.BODY_SENSORS_BACKGROUND", 0x1, 33),
+    ("android.permission.BROADCAST_STICKY", 0x0, 1),
+    ("android.permission.CALL_PHONE", 0x1, 1),
+    ("android.permission.CAMERA", 0x1, 1),
+    ("android.permission.CHANGE_NETWORK_STATE", 0x0, 1),
+    ("android.permission.CHANGE_WIFI_MULTICAST_STATE", 0x0, 4),
+    ("android.permission.CHANGE_WIFI_STATE", 0x0, 1),
+    ("android.permission.DISABLE_KEYGUARD", 0x0, 1),
+    ("android.permission.EXPAND_STATUS_BAR", 0x0, 1),
+    ("android.permission.FOREGROUND_SERVICE", 0x0, 28),
+    ("android.permission.FOREGROUND_SERVICE_CAMERA", 0x0, 34),
+    ("android.permission.FOREGROUND_SERVICE_DATA_SYNC", 0x0, 34),
+    ("android.permission.FOREGROUND_SERVICE_LOCATION", 0x0, 34),
+    (
+        "android.permission.FOREGROUND_SERVICE_MEDIA_PLAYBACK",
+        0x0,
+        34,
+    ),
+    ("android.permission.FOREGROUND_SERVICE_MICROPHONE", 0x0, 34),
+    ("android.permission.GET_ACCOUNTS", 0x1, 1),
+    ("android.permission.GET_PACKAGE_SIZE", 0x0, 1),
+    ("android.permission.GET_TASKS", 0x0, 1),
+    ("android.permission.HIGH_SAMPLING_RATE_SENSORS", 0x0, 31),
+    ("android.permission.INSTALL_PACKAGES", 0x12, 1),
+    ("android.permission.INTERNET", 0x0, 1),
+    ("android.permission.KILL_BACKGROUND_PROCESSES", 0x0, 8),
+    ("android.permission.MANAGE_EXTERNAL_STORAGE", 0x442, 30),
+    ("android.permission.MANAGE_OWN_CALLS", 0x0, 26),
+    ("android.permission.MODIFY_AUDIO_SETTINGS", 0x0, 1),
+    ("android.permission.NEARBY_WIFI_DEVICES", 0x1, 33),
+    ("android.permission.NFC", 0x0, 9),
+    ("android.permission.PACKAGE_USAGE_STATS", 0x72, 21),
+    ("android.permission.POST_NOTIFICATIONS", 0x1, 33),
+    ("android.permission.PROCESS_OUTGOING_CALLS", 0x1, 1),
+    ("android.permission.QUERY_ALL_PACKAGES", 0x0, 30),
+    ("android.permission.READ_CALENDAR", 0x1, 1),
+    ("android.permission.READ_CALL_LOG", 0x1, 16),
+    ("android.permission.READ_CONTACTS", 0x1, 1),
+    ("android.permission.READ_EXTERNAL_STORAGE", 0x1, 16),
+    ("android.permission.READ_LOGS", 0x32, 1),
+    ("android.permission.READ_MEDIA_AUDIO", 0x1, 33),
+    ("android.permission.READ_MEDIA_IMAGES", 0x1, 33),
+    ("android.permission.READ_MEDIA_VIDEO", 0x1, 33),
+    (
+        "android.permission.READ_MEDIA_VISUAL_USER_SELECTED",
+        0x1,
+        34,
+    ),
+    ("android.permission.READ_PHONE_NUMBERS", 0x1, 26),
+    ("android.permission.READ_PHONE_STATE", 0x1, 1),
+    ("android.permission.READ_SMS", 0x1, 1),
+    ("android.permission.READ_SYNC_SETTINGS", 0x0, 1),
+    ("android.permission.READ_SYNC_STATS", 0x0, 1),
+    ("android.permission.RECEIVE_BOOT_COMPLETED", 0x0, 1),
+    ("android.permission.RECEIVE_MMS", 0x1, 1),
+    ("android.permission.RECEIVE_SMS", 0x1, 1),
+    ("android.permission.RECEIVE_WAP_PUSH", 0x1, 1),
+    ("android.permission.RECORD_AUDIO", 0x1, 1),
+    ("android.permission.REORDER_TASKS", 0x0, 1),
+    ("android.permission.REQUEST_DELETE_PACKAGES", 0x0, 26),
+    (
+        "android.permission.REQUEST_IGNORE_BATTERY_OPTIMIZATIONS",
+        0x0,
+        23,
+    ),
+    ("android.permission.REQUEST_INSTALL_PACKAGES", 0x42, 23),
+    ("android.permission.SCHEDULE_EXACT_ALARM", 0x40, 31),
+    ("android.permission.SEND_SMS", 0x1, 1),
+    ("android.permission.SET_ALARM", 0x0, 9),
+    ("android.permission.SET_WALLPAPER", 0x0, 1),
+    ("android.permission.SET_WALLPAPER_HINTS", 0x0, 1),
+    ("android.permission.SYSTEM_ALERT_WINDOW", 0x9e2, 1),
+    ("android.permission.TRANSMIT_IR", 0x0, 19),
+    ("android.permission.USE_BIOMETRIC", 0x0, 28),
+    ("android.permission.USE_EXACT_ALARM", 0x0, 33),
+    ("android.permission.USE_FINGERPRINT", 0x0, 23),
+    ("android.permission.USE_FULL_SCREEN_INTENT", 0x0, 29),
+    ("android.permission.USE_SIP", 0x1, 9),
+    ("android.permission.UWB_RANGING", 0x1, 31),
+    ("android.permission.VIBRATE", 0x0, 1),
+    ("android.permission.WAKE_LOCK", 0x0, 1),
+    ("android.permission.WRITE_CALENDAR", 0x1, 1),
+    ("android.permission.WRITE_CALL_LOG", 0x1, 16),
+    ("android.permission.WRITE_CONTACTS", 0x1, 1),
+    ("android.permission.WRITE_EXTERNAL_STORAGE", 0x1, 4),
+    ("android.permission.WRITE_SECURE_SETTINGS", 0x32, 3),
+    ("android.permission.WRITE_SETTINGS", 0x4c2, 1),
+    ("android.permission.WRITE_SYNC_SETTINGS", 0x0, 1),
+    ("com.android.voicemail.permission.ADD_VOICEMAIL", 0x1, 14),
+];
+
+///Looks up a permission defined by the platform, such as
+///`android.permission.CAMERA`. Returns `None` for permissions defined by
+///apps and for platform permissions missing from the table.
+///
+///```rust
+///use axmldecoder::platform_permission;
+///let camera = platform_permission("android.permission.CAMERA").unwrap();
+///assert!(camera.protection_level.is_dangerous());
+///assert_eq!(camera.added_in, 1);
+///```
+pub fn platform_permission(name: &str) -> Option<PlatformPermission> {
+    let i = PLATFORM_PERMISSIONS
+        .binary_search_by(|(n, _, _)| (*n).cmp(name))
+        .ok()?;
+    let (name, level, added_in) = PLATFORM_PERMISSIONS[i];
+    Some(PlatformPermission {
+        name,
+        protection_level: ProtectionLevel(level),
+        added_in,
+    })
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the permissions requested by `uses-permission` and
+    ///`uses-permission-sdk-23` elements that the user has to grant at
+    ///runtime, in document order. A permission is dangerous if the platform
+    ///defines it so, or if the manifest declares it itself with a
+    ///`dangerous` protection level.
+    pub fn dangerous_permissions(&self) -> Vec<&'a str> {
+        let declared: HashSet<String> = self
+            .permissions()
+            .into_iter()
+            .filter(|p| p.protection_level.is_dangerous())
+            .map(|p| p.name)
+            .collect();
+        let mut seen = HashSet::new();
+        child_elements(self.root())
+            .filter(|e| matches!(e.get_tag(), "uses-permission" | "uses-permission-sdk-23"))
+            .filter_map(|e| e.android_value("name"))
+            .filter(|name| {
+                declared.contains(*name)
+                    || platform_permission(name).is_some_and(|p| p.protection_level.is_dangerous())
+            })
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+}
+
+#[test]
+fn test_platform_permissions() {
+    use crate::{Element, Node, XmlDocument};
+
+    assert!(PLATFORM_PERMISSIONS.windows(2).all(|w| w[0].0 < w[1].0));
+    assert!(platform_permission("android.permission.INSTALL_PACKAGES")
+        .unwrap()
+        .protection_level
+        .is_signature());
+    assert_eq!(platform_permission("com.example.CUSTOM"), None);
+
+    let mut root = Element::new("manifest");
+    let mut permission = Element::new("permission");
+    permission.set_attribute("android:name", "com.example.CUSTOM");
+    permission.set_attribute("android:protectionLevel", "dangerous");
+    root.get_children_mut().push(Node::Element(permission));
+    for (tag, name) in [
+        ("uses-permission", "android.permission.INTERNET"),
+        ("uses-permission", "android.permission.CAMERA"),
+        ("uses-permission-sdk-23", "com.example.CUSTOM"),
+        ("uses-permission-sdk-23", "android.permission.CAMERA"),
+    ] {
+        let mut e = Element::new(tag);
+        e.set_attribute("android:name", name);
+        root.get_children_mut().push(Node::Element(e));
+    }
+    let xml = XmlDocument::new(Node::Element(root));
+    assert_eq!(
+        xml.manifest().unwrap().dangerous_permissions(),
+        ["android.permission.CAMERA", "com.example.CUSTOM"]
+    );
+}
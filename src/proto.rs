@@ -0,0 +1,419 @@
+//!Decoding of the protobuf XML format used by `aapt2` for app bundles,
+//!available with the `proto` feature.
+//!
+//!The messages below mirror the subset of `frameworks/base/tools/aapt2/
+//!Resources.proto` needed to rebuild a document.
+
+use std::path::Path;
+
+use prost::Message;
+
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::stringpool::ResStringPool;
+use crate::xml::{Attribute, ANDROID_NAMESPACE};
+use crate::{Cdata, Element, Node, ParseError, XmlDocument};
+
+const BUNDLE_MANIFEST_NAME: &str = "base/manifest/AndroidManifest.xml";
+
+#[derive(Clone, PartialEq, Message)]
+struct XmlNode {
+    #[prost(oneof = "XmlNodeValue", tags = "1, 2")]
+    node: Option<XmlNodeValue>,
+    #[prost(message, optional, tag = "3")]
+    source: Option<SourcePosition>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum XmlNodeValue {
+    #[prost(message, tag = "1")]
+    Element(XmlElement),
+    #[prost(string, tag = "2")]
+    Text(String),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct XmlElement {
+    #[prost(message, repeated, tag = "1")]
+    namespace_declaration: Vec<XmlNamespace>,
+    #[prost(string, tag = "2")]
+    namespace_uri: String,
+    #[prost(string, tag = "3")]
+    name: String,
+    #[prost(message, repeated, tag = "4")]
+    attribute: Vec<XmlAttribute>,
+    #[prost(message, repeated, tag = "5")]
+    child: Vec<XmlNode>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct XmlNamespace {
+    #[prost(string, tag = "1")]
+    prefix: String,
+    #[prost(string, tag = "2")]
+    uri: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct XmlAttribute {
+    #[prost(string, tag = "1")]
+    namespace_uri: String,
+    #[prost(string, tag = "2")]
+    name: String,
+    #[prost(string, tag = "3")]
+    value: String,
+    #[prost(uint32, tag = "5")]
+    resource_id: u32,
+    #[prost(message, optional, tag = "6")]
+    compiled_item: Option<Item>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SourcePosition {
+    #[prost(uint32, tag = "1")]
+    line_number: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Item {
+    #[prost(oneof = "ItemValue", tags = "1, 2, 3, 4, 5, 6, 7")]
+    value: Option<ItemValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum ItemValue {
+    #[prost(message, tag = "1")]
+    Ref(Reference),
+    #[prost(message, tag = "2")]
+    Str(StringValue),
+    #[prost(message, tag = "3")]
+    RawStr(StringValue),
+    #[prost(message, tag = "4")]
+    StyledStr(StringValue),
+    #[prost(message, tag = "5")]
+    File(StringValue),
+    #[prost(message, tag = "6")]
+    Id(Empty),
+    #[prost(message, tag = "7")]
+    Prim(Primitive),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Reference {
+    ///0 for `@` references, 1 for `?` attribute references.
+    #[prost(int32, tag = "1")]
+    typ: i32,
+    #[prost(uint32, tag = "2")]
+    id: u32,
+}
+
+///Any message whose first field is its string value, such as `String`,
+///`RawString`, `StyledString` and `FileReference`.
+#[derive(Clone, PartialEq, Message)]
+struct StringValue {
+    #[prost(string, tag = "1")]
+    value: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Empty {}
+
+#[derive(Clone, PartialEq, Message)]
+struct Primitive {
+    #[prost(
+        oneof = "PrimitiveValue",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14"
+    )]
+    value: Option<PrimitiveValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum PrimitiveValue {
+    #[prost(message, tag = "1")]
+    Null(Empty),
+    #[prost(message, tag = "2")]
+    Empty(Empty),
+    #[prost(float, tag = "3")]
+    Float(f32),
+    #[prost(float, tag = "4")]
+    DeprecatedDimension(f32),
+    #[prost(float, tag = "5")]
+    DeprecatedFraction(f32),
+    #[prost(int32, tag = "6")]
+    IntDecimal(i32),
+    #[prost(uint32, tag = "7")]
+    IntHexadecimal(u32),
+    #[prost(bool, tag = "8")]
+    Boolean(bool),
+    #[prost(uint32, tag = "9")]
+    ColorArgb8(u32),
+    #[prost(uint32, tag = "10")]
+    ColorRgb8(u32),
+    #[prost(uint32, tag = "11")]
+    ColorArgb4(u32),
+    #[prost(uint32, tag = "12")]
+    ColorRgb4(u32),
+    #[prost(uint32, tag = "13")]
+    Dimension(u32),
+    #[prost(uint32, tag = "14")]
+    Fraction(u32),
+}
+
+///Parses a document in the protobuf XML format `aapt2` uses inside app
+///bundles into the same model as binary XML documents.
+///
+///```rust,no_run
+///use axmldecoder::parse_proto;
+///# use axmldecoder::ParseError;
+///let data = std::fs::read("base/manifest/AndroidManifest.xml").unwrap();
+///let xml = parse_proto(&data)?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_proto(data: &[u8]) -> Result<XmlDocument, ParseError> {
+    let node = XmlNode::decode(data).map_err(ParseError::ProtoError)?;
+    let mut builder = ProtoTreeBuilder::default();
+    let mut root = match node.node {
+        Some(XmlNodeValue::Element(e)) => builder.build_element(&e, node.source.as_ref())?,
+        _ => return Err(ParseError::NoRootElement),
+    };
+
+    // Declarations on the root are kept on the document.
+    let declarations = std::mem::take(&mut root.namespace_declarations);
+    let mut document = XmlDocument::new(Node::Element(root));
+    for (prefix, uri) in &declarations {
+        document.declare_namespace(prefix, uri);
+    }
+    Ok(document)
+}
+
+///Extracts and parses the manifest of the base module of an app bundle.
+///
+///```rust,no_run
+///use axmldecoder::parse_aab;
+///# use axmldecoder::ParseError;
+///let xml = parse_aab("app.aab")?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_aab<P: AsRef<Path>>(path: P) -> Result<XmlDocument, ParseError> {
+    parse_proto(&crate::apk::read_entry(path, BUNDLE_MANIFEST_NAME)?)
+}
+
+#[derive(Default)]
+struct ProtoTreeBuilder {
+    ///Namespaces in scope, innermost last.
+    namespaces: Vec<(String, String)>,
+}
+
+impl ProtoTreeBuilder {
+    fn build_element(
+        &mut self,
+        e: &XmlElement,
+        source: Option<&SourcePosition>,
+    ) -> Result<Element, ParseError> {
+        let scope = self.namespaces.len();
+        for ns in &e.namespace_declaration {
+            self.namespaces.push((ns.prefix.clone(), ns.uri.clone()));
+        }
+
+        let mut element = Element::new(&self.qualify(&e.namespace_uri, &e.name)?);
+        element.namespace_declarations = self.namespaces[scope..].to_vec();
+        element.namespace = non_empty(&e.namespace_uri);
+        element.line_number = source.map_or(0, |s| s.line_number);
+
+        for attr in &e.attribute {
+            let typed_value = typed_value(attr);
+            let value = match typed_value.data_type {
+                ResourceValueType::String => attr.value.clone(),
+                _ => typed_value
+                    .get_value(&ResStringPool::new(false))?
+                    .to_string(),
+            };
+
+            element.push_attribute(Attribute {
+                qualified_name: self.qualify(&attr.namespace_uri, &attr.name)?,
+                namespace: non_empty(&attr.namespace_uri),
+                name: attr.name.clone(),
+                resource_id: Some(attr.resource_id).filter(|&id| id != 0),
+                raw_value: non_empty(&attr.value),
+                typed_value,
+                value,
+            });
+        }
+
+        for child in &e.child {
+            let node = match &child.node {
+                Some(XmlNodeValue::Element(e)) => {
+                    Node::Element(self.build_element(e, child.source.as_ref())?)
+                }
+                Some(XmlNodeValue::Text(text)) => Node::Cdata(Cdata::new(text)),
+                None => continue,
+            };
+            element.get_children_mut().push(node);
+        }
+
+        self.namespaces.truncate(scope);
+        Ok(element)
+    }
+
+    ///Prefixes `name` with the prefix bound to `uri`.
+    fn qualify(&self, uri: &str, name: &str) -> Result<String, ParseError> {
+        if uri.is_empty() {
+            return Ok(name.to_string());
+        }
+
+        let prefix = self
+            .namespaces
+            .iter()
+            .rev()
+            .find(|(_, u)| u == uri)
+            .map(|(prefix, _)| prefix.as_str())
+            .or(if uri == ANDROID_NAMESPACE {
+                Some("android")
+            } else {
+                None
+            })
+            .ok_or_else(|| ParseError::NamespaceNotFound(uri.to_string()))?;
+        if prefix.is_empty() {
+            return Ok(name.to_string());
+        }
+        Ok(format!("{}:{}", prefix, name))
+    }
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    Some(s.to_string()).filter(|s| !s.is_empty())
+}
+
+///Converts the compiled value of `attr` into its binary XML equivalent.
+///Attributes that were not compiled are plain strings.
+fn typed_value(attr: &XmlAttribute) -> ResourceValue {
+    let (data_type, data) = match attr.compiled_item.as_ref().and_then(|i| i.value.as_ref()) {
+        Some(ItemValue::Ref(r)) if r.typ == 1 => (ResourceValueType::Attribute, r.id),
+        Some(ItemValue::Ref(r)) => (ResourceValueType::Reference, r.id),
+        Some(ItemValue::Prim(p)) => match &p.value {
+            Some(PrimitiveValue::Null(_)) | None => (ResourceValueType::Null, 0),
+            Some(PrimitiveValue::Empty(_)) => (ResourceValueType::Null, 1),
+            Some(PrimitiveValue::Float(f))
+            | Some(PrimitiveValue::DeprecatedDimension(f))
+            | Some(PrimitiveValue::DeprecatedFraction(f)) => {
+                (ResourceValueType::Float, f.to_bits())
+            }
+            Some(PrimitiveValue::IntDecimal(i)) => (ResourceValueType::Dec, *i as u32),
+            Some(PrimitiveValue::IntHexadecimal(i)) => (ResourceValueType::Hex, *i),
+            Some(PrimitiveValue::Boolean(b)) => {
+                (ResourceValueType::Boolean, if *b { u32::MAX } else { 0 })
+            }
+            Some(PrimitiveValue::ColorArgb8(c)) => (ResourceValueType::ColorArgb8, *c),
+            Some(PrimitiveValue::ColorRgb8(c)) => (ResourceValueType::ColorRgb8, *c),
+            Some(PrimitiveValue::ColorArgb4(c)) => (ResourceValueType::ColorArgb4, *c),
+            Some(PrimitiveValue::ColorRgb4(c)) => (ResourceValueType::ColorRgb4, *c),
+            Some(PrimitiveValue::Dimension(d)) => (ResourceValueType::Dimension, *d),
+            Some(PrimitiveValue::Fraction(f)) => (ResourceValueType::Fraction, *f),
+        },
+        _ => (ResourceValueType::String, 0),
+    };
+
+    ResourceValue {
+        size: 8,
+        res: 0,
+        data_type,
+        data,
+    }
+}
+
+#[test]
+fn test_parse_proto() {
+    fn attribute(
+        name: &str,
+        value: &str,
+        resource_id: u32,
+        item: Option<ItemValue>,
+    ) -> XmlAttribute {
+        XmlAttribute {
+            namespace_uri: ANDROID_NAMESPACE.to_string(),
+            name: name.to_string(),
+            value: value.to_string(),
+            resource_id,
+            compiled_item: item.map(|value| Item { value: Some(value) }),
+        }
+    }
+
+    let uses_sdk = XmlElement {
+        name: "uses-sdk".to_string(),
+        attribute: vec![attribute(
+            "minSdkVersion",
+            "21",
+            0x0101020c,
+            Some(ItemValue::Prim(Primitive {
+                value: Some(PrimitiveValue::IntDecimal(21)),
+            })),
+        )],
+        ..XmlElement::default()
+    };
+    let application = XmlElement {
+        name: "application".to_string(),
+        attribute: vec![attribute(
+            "label",
+            "@string/app_name",
+            0x01010001,
+            Some(ItemValue::Ref(Reference {
+                typ: 0,
+                id: 0x7f0b0001,
+            })),
+        )],
+        ..XmlElement::default()
+    };
+    let manifest = XmlElement {
+        namespace_declaration: vec![XmlNamespace {
+            prefix: "android".to_string(),
+            uri: ANDROID_NAMESPACE.to_string(),
+        }],
+        name: "manifest".to_string(),
+        attribute: vec![XmlAttribute {
+            name: "package".to_string(),
+            value: "com.example".to_string(),
+            ..XmlAttribute::default()
+        }],
+        child: vec![uses_sdk, application]
+            .into_iter()
+            .map(|e| XmlNode {
+                node: Some(XmlNodeValue::Element(e)),
+                source: None,
+            })
+            .collect(),
+        ..XmlElement::default()
+    };
+    let data = XmlNode {
+        node: Some(XmlNodeValue::Element(manifest)),
+        source: Some(SourcePosition { line_number: 2 }),
+    }
+    .encode_to_vec();
+
+    let xml = parse_proto(&data).unwrap();
+    let root = match xml.get_root() {
+        Some(Node::Element(root)) => root,
+        _ => panic!("missing root element"),
+    };
+    assert_eq!(root.get_attribute("package"), Some("com.example"));
+    let children: Vec<_> = root
+        .get_children()
+        .iter()
+        .filter_map(|node| match node {
+            Node::Element(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        children[0].get_attribute("android:minSdkVersion"),
+        Some("21")
+    );
+    assert_eq!(
+        children[1].get_attribute("android:label"),
+        Some("@0x7f0b0001")
+    );
+
+    // The result encodes like any parsed document.
+    let mut output = std::io::Cursor::new(Vec::new());
+    crate::encode(&xml, &mut output).unwrap();
+    output.set_position(0);
+    crate::parse(&mut output).unwrap();
+}
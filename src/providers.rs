@@ -0,0 +1,166 @@
+use crate::manifest::{child_elements, ComponentKind, Manifest};
+use crate::xml::Element;
+
+///A content provider declared by a `provider` element, with what it
+///exposes and the permissions guarding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Provider {
+    ///The fully qualified class name.
+    pub name: String,
+    ///The authorities of `android:authorities`, which may list several
+    ///separated by `;`.
+    pub authorities: Vec<String>,
+    ///The `android:exported` attribute, if given. The default depends on
+    ///the target SDK.
+    pub exported: Option<bool>,
+    ///Whether `android:grantUriPermissions` lets the app grant access to
+    ///any of its data.
+    pub grant_uri_permissions: bool,
+    pub permission: Option<String>,
+    pub read_permission: Option<String>,
+    pub write_permission: Option<String>,
+    ///The `path-permission` children.
+    pub path_permissions: Vec<PathPermission>,
+    ///The paths of the `grant-uri-permission` children, which limit what
+    ///can be granted when `grant_uri_permissions` is not set.
+    pub grant_uri_permission_paths: Vec<PathPattern>,
+}
+
+///The paths of a provider that a `path-permission` element guards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PathPermission {
+    pub path: PathPattern,
+    pub permission: Option<String>,
+    pub read_permission: Option<String>,
+    pub write_permission: Option<String>,
+}
+
+///The path attributes of a `path-permission` or `grant-uri-permission`
+///element, each `None` if not given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PathPattern {
+    pub path: Option<String>,
+    pub path_prefix: Option<String>,
+    pub path_pattern: Option<String>,
+}
+
+impl<'a> Manifest<'a> {
+    ///Returns the content providers of the application.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut provider = Element::new("provider");
+    ///provider.set_attribute("android:name", ".DataProvider");
+    ///provider.set_attribute("android:authorities", "com.example.data;com.example.files");
+    ///let mut application = Element::new("application");
+    ///application.get_children_mut().push(Node::Element(provider));
+    ///let mut root = Element::new("manifest");
+    ///root.set_attribute("package", "com.example");
+    ///root.get_children_mut().push(Node::Element(application));
+    ///
+    ///let xml = XmlDocument::new(Node::Element(root));
+    ///let providers = xml.manifest().unwrap().providers();
+    ///assert_eq!(providers[0].name, "com.example.DataProvider");
+    ///assert_eq!(providers[0].authorities, ["com.example.data", "com.example.files"]);
+    ///```
+    pub fn providers(&self) -> Vec<Provider> {
+        self.components()
+            .filter(|(_, kind)| *kind == ComponentKind::Provider)
+            .map(|(e, _)| Provider {
+                name: self.resolve_class_name(e.android_value("name").unwrap_or_default()),
+                authorities: authorities(e).collect(),
+                exported: e.android_bool("exported"),
+                grant_uri_permissions: e.android_bool("grantUriPermissions").unwrap_or(false),
+                permission: value(e, "permission"),
+                read_permission: value(e, "readPermission"),
+                write_permission: value(e, "writePermission"),
+                path_permissions: child_elements(e)
+                    .filter(|c| c.get_tag() == "path-permission")
+                    .map(|c| PathPermission {
+                        path: PathPattern::from_element(c),
+                        permission: value(c, "permission"),
+                        read_permission: value(c, "readPermission"),
+                        write_permission: value(c, "writePermission"),
+                    })
+                    .collect(),
+                grant_uri_permission_paths: child_elements(e)
+                    .filter(|c| c.get_tag() == "grant-uri-permission")
+                    .map(PathPattern::from_element)
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl PathPattern {
+    fn from_element(e: &Element) -> Self {
+        Self {
+            path: value(e, "path"),
+            path_prefix: value(e, "pathPrefix"),
+            path_pattern: value(e, "pathPattern"),
+        }
+    }
+}
+
+///The authorities of a `provider` element, which `android:authorities`
+///separates by `;`.
+pub(crate) fn authorities<'e>(e: &'e Element) -> impl Iterator<Item = String> + 'e {
+    e.android_value("authorities")
+        .into_iter()
+        .flat_map(|a| a.split(';'))
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(str::to_string)
+}
+
+fn value(e: &Element, name: &str) -> Option<String> {
+    e.android_value(name).map(str::to_string)
+}
+
+#[test]
+fn test_providers() {
+    use crate::{Node, ResourceValue, XmlDocument};
+
+    let mut path_permission = Element::new("path-permission");
+    path_permission.set_attribute("android:pathPrefix", "/private");
+    path_permission.set_attribute("android:readPermission", "com.example.READ");
+    let mut grant = Element::new("grant-uri-permission");
+    grant.set_attribute("android:path", "/shared");
+    let mut provider = Element::new("provider");
+    provider.set_attribute("android:name", "com.example.Provider");
+    provider.set_attribute("android:authorities", "com.example");
+    provider.set_typed_attribute(None, "android:exported", ResourceValue::boolean(true));
+    provider
+        .get_children_mut()
+        .push(Node::Element(path_permission));
+    provider.get_children_mut().push(Node::Element(grant));
+    let mut application = Element::new("application");
+    application.get_children_mut().push(Node::Element(provider));
+    application
+        .get_children_mut()
+        .push(Node::Element(Element::new("activity")));
+    let mut root = Element::new("manifest");
+    root.get_children_mut().push(Node::Element(application));
+    let xml = XmlDocument::new(Node::Element(root));
+
+    let providers = xml.manifest().unwrap().providers();
+    assert_eq!(providers.len(), 1);
+    let provider = &providers[0];
+    assert_eq!(provider.exported, Some(true));
+    assert!(!provider.grant_uri_permissions);
+    assert_eq!(
+        provider.path_permissions[0].path.path_prefix.as_deref(),
+        Some("/private")
+    );
+    assert_eq!(
+        provider.path_permissions[0].read_permission.as_deref(),
+        Some("com.example.READ")
+    );
+    assert_eq!(
+        provider.grant_uri_permission_paths[0].path.as_deref(),
+        Some("/shared")
+    );
+}
@@ -0,0 +1,110 @@
+//!Python bindings, available with the `python` feature.
+//!
+//!The extension module is built with [maturin](https://www.maturin.rs) using
+//!the `pyproject.toml` at the root of the repository:
+//!
+//!```python
+//!import axmldecoder
+//!
+//!with open("AndroidManifest.xml", "rb") as f:
+//!    data = f.read()
+//!
+//!manifest = axmldecoder.parse(data)
+//!print(manifest["root"]["attributes"]["package"])
+//!
+//!document = axmldecoder.Document(data, hardened=True)
+//!cleaned = axmldecoder.encode(document)
+//!```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList};
+
+use crate::{Node, ParseConfig, XmlDocument};
+
+///A parsed binary XML document.
+#[pyclass(module = "axmldecoder")]
+struct Document {
+    document: XmlDocument,
+}
+
+#[pymethods]
+impl Document {
+    ///Parses `data`, in hardened mode if `hardened` is set.
+    #[new]
+    #[pyo3(signature = (data, hardened = false))]
+    fn new(data: &[u8], hardened: bool) -> PyResult<Self> {
+        let config = if hardened {
+            ParseConfig::hardened()
+        } else {
+            ParseConfig::strict()
+        };
+        let (document, _) = crate::parse_with_config(&mut std::io::Cursor::new(data), &config)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        Ok(Self { document })
+    }
+
+    ///Returns the document as nested dictionaries and lists.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        match self.document.get_root() {
+            Some(root) => dict.set_item("root", node_to_dict(py, root)?)?,
+            None => dict.set_item("root", py.None())?,
+        }
+        Ok(dict)
+    }
+
+    ///Encodes the document back into binary XML.
+    fn encode<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let output = crate::encode_to_vec(&self.document)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyBytes::new(py, &output))
+    }
+}
+
+fn node_to_dict<'py>(py: Python<'py>, node: &Node) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    match node {
+        Node::Element(e) => {
+            dict.set_item("type", "element")?;
+            dict.set_item("tag", e.get_tag())?;
+            let attributes = PyDict::new(py);
+            for (name, value) in e.attributes() {
+                attributes.set_item(name, value)?;
+            }
+            dict.set_item("attributes", attributes)?;
+            let children = PyList::empty(py);
+            for child in e.get_children() {
+                children.append(node_to_dict(py, child)?)?;
+            }
+            dict.set_item("children", children)?;
+        }
+        Node::Cdata(c) => {
+            dict.set_item("type", "cdata")?;
+            dict.set_item("data", c.get_data())?;
+        }
+    }
+    Ok(dict)
+}
+
+///Parses an Android binary XML into nested dictionaries and lists.
+#[pyfunction]
+#[pyo3(signature = (data, hardened = false))]
+fn parse<'py>(py: Python<'py>, data: &[u8], hardened: bool) -> PyResult<Bound<'py, PyDict>> {
+    Document::new(data, hardened)?.to_dict(py)
+}
+
+///Encodes a [Document] into binary XML.
+#[pyfunction]
+fn encode<'py>(py: Python<'py>, document: PyRef<'py, Document>) -> PyResult<Bound<'py, PyBytes>> {
+    document.encode(py)
+}
+
+#[pymodule]
+fn axmldecoder(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Document>()?;
+    m.add_function(wrap_pyfunction!(parse, m)?)?;
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::intent::IntentFilter;
+use crate::manifest::{child_elements, Manifest};
+use crate::providers::authorities;
+
+///What an app declares it needs to see of other apps in its `queries`
+///elements, the package visibility rules that apply from Android 11.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Queries {
+    ///Names of the `package` children.
+    pub packages: Vec<String>,
+    ///The `intent` children.
+    pub intents: Vec<IntentFilter>,
+    ///Authorities of the `provider` children. Each authority listed in a
+    ///`;` separated `android:authorities` is its own entry.
+    pub providers: Vec<String>,
+}
+
+impl<'a> Manifest<'a> {
+    ///Collects the contents of every `queries` element. Returns `None` if
+    ///there are none, which for apps targeting Android 11 or later means
+    ///they only see the apps visible to everyone.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut package = Element::new("package");
+    ///package.set_attribute("android:name", "com.example.other");
+    ///let mut queries = Element::new("queries");
+    ///queries.get_children_mut().push(Node::Element(package));
+    ///let mut root = Element::new("manifest");
+    ///root.get_children_mut().push(Node::Element(queries));
+    ///
+    ///let xml = XmlDocument::new(Node::Element(root));
+    ///let queries = xml.manifest().unwrap().queries().unwrap();
+    ///assert_eq!(queries.packages, ["com.example.other"]);
+    ///```
+    pub fn queries(&self) -> Option<Queries> {
+        let mut result: Option<Queries> = None;
+        for queries in child_elements(self.root()).filter(|e| e.get_tag() == "queries") {
+            let result = result.get_or_insert_with(Queries::default);
+            for e in child_elements(queries) {
+                match e.get_tag() {
+                    "package" => result
+                        .packages
+                        .extend(e.android_value("name").map(str::to_string)),
+                    "intent" => result.intents.push(IntentFilter::from_element(e)),
+                    "provider" => result.providers.extend(authorities(e)),
+                    _ => (),
+                }
+            }
+        }
+        result
+    }
+}
+
+#[test]
+fn test_queries() {
+    use crate::test_support::element;
+    use crate::{Element, Node, XmlDocument};
+
+    let root = element(
+        "manifest",
+        &[],
+        vec![
+            element(
+                "queries",
+                &[],
+                vec![
+                    element("package", &[("android:name", "com.example.a")], vec![]),
+                    element(
+                        "intent",
+                        &[],
+                        vec![
+                            element(
+                                "action",
+                                &[("android:name", "android.intent.action.SEND")],
+                                vec![],
+                            ),
+                            element("data", &[("android:mimeType", "image/*")], vec![]),
+                        ],
+                    ),
+                ],
+            ),
+            element(
+                "queries",
+                &[],
+                vec![element(
+                    "provider",
+                    &[("android:authorities", "com.example.b;com.example.c")],
+                    vec![],
+                )],
+            ),
+        ],
+    );
+    let xml = XmlDocument::new(Node::Element(root));
+    let queries = xml.manifest().unwrap().queries().unwrap();
+
+    assert_eq!(queries.packages, ["com.example.a"]);
+    assert!(queries.intents[0].has_action("android.intent.action.SEND"));
+    assert_eq!(
+        queries.intents[0].data[0].mime_type.as_deref(),
+        Some("image/*")
+    );
+    assert_eq!(queries.providers, ["com.example.b", "com.example.c"]);
+
+    let xml = XmlDocument::new(Node::Element(Element::new("manifest")));
+    assert_eq!(xml.manifest().unwrap().queries(), None);
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+
+///Looks up the names of resources by their id, so that references such as
+///`@0x7f040000` can be written as `@string/app_name`.
+///
+///This crate does not read resource tables itself. Resolvers are usually
+///backed by the `resources.arsc` of the APK being decoded, or by a list of
+///names dumped with `aapt2 dump resources`.
+///
+///```rust
+///use std::collections::HashMap;
+///use std::sync::Arc;
+///use axmldecoder::{parse, FormatOptions};
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///
+///let mut names = HashMap::new();
+///names.insert(0x7f040000, "string/app_name".to_string());
+///let options = FormatOptions {
+///    resolver: Some(Arc::new(names)),
+///    ..FormatOptions::default()
+///};
+///assert!(xml.to_xml_string(&options).contains("android:label=\"@string/app_name\""));
+///```
+pub trait ResourceResolver: Send + Sync {
+    ///Returns the name of the resource `id` as `type/name`, or as
+    ///`package:type/name` for resources of another package such as the
+    ///framework, or `None` if the id is unknown.
+    fn resolve(&self, id: u32) -> Option<String>;
+}
+
+impl<F: Fn(u32) -> Option<String> + Send + Sync> ResourceResolver for F {
+    fn resolve(&self, id: u32) -> Option<String> {
+        self(id)
+    }
+}
+
+impl ResourceResolver for HashMap<u32, String> {
+    fn resolve(&self, id: u32) -> Option<String> {
+        self.get(&id).cloned()
+    }
+}
+
+impl fmt::Debug for dyn ResourceResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ResourceResolver")
+    }
+}
+
+///Wraps a [ResourceResolver] and remembers what it returns for each id,
+///including ids it does not know, so that an expensive lookup such as a
+///search through a resource table happens once per id.
+///
+///The cache is never evicted, so a resolver shared across many documents
+///should be dropped or [cleared](CachedResolver::clear) once the resource
+///table it is backed by is no longer relevant.
+///
+///```rust
+///use std::sync::atomic::{AtomicUsize, Ordering};
+///use axmldecoder::{CachedResolver, ResourceResolver};
+///let lookups = AtomicUsize::new(0);
+///let resolver = CachedResolver::new(|id| {
+///    lookups.fetch_add(1, Ordering::Relaxed);
+///    Some(format!("string/s{}", id))
+///});
+///assert_eq!(resolver.resolve(1).as_deref(), Some("string/s1"));
+///assert_eq!(resolver.resolve(1).as_deref(), Some("string/s1"));
+///assert_eq!(lookups.load(Ordering::Relaxed), 1);
+///```
+pub struct CachedResolver<R> {
+    inner: R,
+    cache: RwLock<HashMap<u32, Option<String>>>,
+}
+
+impl<R: ResourceResolver> CachedResolver<R> {
+    ///Wraps `inner` with an empty cache.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    ///Forgets every cached result.
+    pub fn clear(&self) {
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    ///Returns the wrapped resolver.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ResourceResolver> ResourceResolver for CachedResolver<R> {
+    fn resolve(&self, id: u32) -> Option<String> {
+        // A poisoned lock only means another thread panicked while holding
+        // it; the map itself is always left consistent.
+        if let Some(name) = self
+            .cache
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+        {
+            return name.clone();
+        }
+
+        let name = self.inner.resolve(id);
+        self.cache
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, name.clone());
+        name
+    }
+}
+
+impl<R> fmt::Debug for CachedResolver<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedResolver").finish_non_exhaustive()
+    }
+}
+
+#[test]
+fn test_cached_resolver() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let counter = lookups.clone();
+    let resolver: Arc<dyn ResourceResolver> = Arc::new(CachedResolver::new(move |id| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        if id == 0x7f040000 {
+            Some("string/app_name".to_string())
+        } else {
+            None
+        }
+    }));
+
+    for _ in 0..3 {
+        assert_eq!(
+            resolver.resolve(0x7f040000).as_deref(),
+            Some("string/app_name")
+        );
+        assert_eq!(resolver.resolve(0x7f040001), None);
+    }
+    assert_eq!(lookups.load(Ordering::Relaxed), 2);
+}
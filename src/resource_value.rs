@@ -1,14 +1,54 @@
-use num_enum::{TryFromPrimitive, IntoPrimitive};
+use num_enum::TryFromPrimitive;
+use std::cmp::Eq;
 use std::convert::TryFrom;
 use std::io::{Read, Seek, Write};
 use std::rc::Rc;
-use std::cmp::Eq;
 
-use crate::stringpool::StringPool;
-use crate::{read_u16, read_u32, read_u8, ParseError, write_u16, write_u32, write_u8};
+use crate::config::FloatFormat;
+use crate::stringpool::ResStringPool;
+use crate::{read_u16, read_u32, read_u8, write_u16, write_u32, write_u8, ParseError};
 
-#[derive(Debug, Eq, PartialEq)]
-pub(crate) struct ResourceValue {
+///A name for some bits of a flag attribute, as `(mask, bits, name)`: the
+///name applies when the bits of the mask are equal to `bits`. Most names
+///are single bits, but some attributes combine enums in a few bits with
+///flags in others.
+pub(crate) type Flag = (u32, u32, &'static str);
+
+///Returns `value` as the names of its flags joined by `|`, or `None` if it
+///has bits without a name. Zero is written as the name for it, if any.
+pub(crate) fn format_flags(flags: &[Flag], value: u32) -> Option<String> {
+    let mut names = Vec::new();
+    let mut covered = 0;
+    for &(mask, bits, name) in flags {
+        if bits != 0 && value & mask == bits {
+            names.push(name);
+            covered |= mask;
+        }
+    }
+    if value & !covered != 0 {
+        return None;
+    }
+    if names.is_empty() {
+        let &(_, _, name) = flags.iter().find(|(_, bits, _)| *bits == 0)?;
+        names.push(name);
+    }
+
+    Some(names.join("|"))
+}
+
+///The inverse of [format_flags], or `None` if a name is unknown.
+pub(crate) fn parse_flags(flags: &[Flag], names: &str) -> Option<u32> {
+    names.split('|').try_fold(0, |value, name| {
+        let &(_, bits, _) = flags.iter().find(|(_, _, n)| *n == name.trim())?;
+        Some(value | bits)
+    })
+}
+
+///A typed value as stored in the binary document, such as the value of an
+///attribute or of a text node.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ResourceValue {
     pub(crate) size: u16,
     pub(crate) res: u8,
     pub(crate) data_type: ResourceValueType,
@@ -16,10 +56,68 @@ pub(crate) struct ResourceValue {
 }
 
 impl ResourceValue {
+    ///Creates a value of the `Res_value` data type `raw_type`. Returns
+    ///`None` for unknown types and for strings, which are set from their
+    ///text instead.
+    pub fn new(raw_type: u8, data: u32) -> Option<Self> {
+        match ResourceValueType::try_from(raw_type).ok()? {
+            ResourceValueType::String => None,
+            data_type => Some(Self::with_type(data_type, data)),
+        }
+    }
+
+    ///Creates a boolean value.
+    pub fn boolean(value: bool) -> Self {
+        // Android stores true as all bits set.
+        Self::with_type(ResourceValueType::Boolean, if value { u32::MAX } else { 0 })
+    }
+
+    ///Creates a decimal integer value.
+    pub fn integer(value: i32) -> Self {
+        Self::with_type(ResourceValueType::Dec, value as u32)
+    }
+
+    ///Creates a reference to the resource `id`, such as `@0x7f010001`.
+    pub fn reference(id: u32) -> Self {
+        Self::with_type(ResourceValueType::Reference, id)
+    }
+
+    ///Creates a color value in `0xAARRGGBB` form.
+    pub fn color(argb: u32) -> Self {
+        Self::with_type(ResourceValueType::ColorArgb8, argb)
+    }
+
+    fn with_type(data_type: ResourceValueType, data: u32) -> Self {
+        Self {
+            size: 8,
+            res: 0,
+            data_type,
+            data,
+        }
+    }
+
+    ///Returns the `Res_value` data type, such as `0x10` for a decimal
+    ///integer.
+    pub fn raw_type(&self) -> u8 {
+        self.data_type as u8
+    }
+
+    ///Returns the data type.
+    pub fn data_type(&self) -> ResourceValueType {
+        self.data_type
+    }
+
+    ///Returns the raw data, whose meaning depends on the type.
+    pub fn data(&self) -> u32 {
+        self.data
+    }
+
     pub(crate) fn read_from_file<F: Read + Seek>(input: &mut F) -> Result<Self, ParseError> {
         let size = read_u16(input)?;
         let res = read_u8(input)?;
-        let data_type = ResourceValueType::try_from(read_u8(input)?).unwrap();
+        let data_type = read_u8(input)?;
+        let data_type = ResourceValueType::try_from(data_type)
+            .map_err(|_| ParseError::UnsupportedResourceValueType(data_type))?;
         let data = read_u32(input)?;
 
         Ok(Self {
@@ -30,22 +128,169 @@ impl ResourceValue {
         })
     }
 
-    pub(crate) fn get_value(&self, string_pool: &StringPool) -> Rc<String> {
-        match &self.data_type {
-            ResourceValueType::String => string_pool
-                .get(usize::try_from(self.data).unwrap())
-                .unwrap(),
-            ResourceValueType::Dec => Rc::new(self.data.to_string()),
-            ResourceValueType::Hex => Rc::new(format!("0x{}", self.data)),
-            ResourceValueType::Boolean => Rc::new(match self.data {
+    pub(crate) fn get_value(&self, string_pool: &ResStringPool) -> Result<Rc<String>, ParseError> {
+        self.format(string_pool, FloatFormat::Decimal)
+    }
+
+    ///Formats the value, writing floats, dimensions and fractions in the
+    ///given format.
+    pub(crate) fn format(
+        &self,
+        string_pool: &ResStringPool,
+        float_format: FloatFormat,
+    ) -> Result<Rc<String>, ParseError> {
+        let format_float = |f| format_float(f, float_format);
+        let value = match &self.data_type {
+            ResourceValueType::String => {
+                return string_pool
+                    .get(self.data)
+                    .ok_or(ParseError::StringNotFound(self.data))
+            }
+            ResourceValueType::Null => match self.data {
+                DATA_NULL_EMPTY => "@empty".to_string(),
+                _ => String::new(),
+            },
+            ResourceValueType::Reference => format_reference('@', self.data),
+            ResourceValueType::Attribute => format_reference('?', self.data),
+            ResourceValueType::Float => format_float(f32::from_bits(self.data)),
+            ResourceValueType::Dimension => {
+                match DIMENSION_UNITS.get((self.data & complex::UNIT_MASK) as usize) {
+                    Some(unit) => format_float(complex_to_float(self.data)) + unit,
+                    None => self.format_unknown(),
+                }
+            }
+            ResourceValueType::Fraction => {
+                match FRACTION_UNITS.get((self.data & complex::UNIT_MASK) as usize) {
+                    Some(unit) => format_float(complex_to_float(self.data) * 100.0) + unit,
+                    None => self.format_unknown(),
+                }
+            }
+            ResourceValueType::Dec => self.data.to_string(),
+            ResourceValueType::Hex => format!("{:#x}", self.data),
+            ResourceValueType::Boolean => match self.data {
                 0 => "false".to_string(),
                 _ => "true".to_string(),
-            }),
-            n => Rc::new(format!("ResourceValueType::{:?}/{}", n, self.data)),
+            },
+            ResourceValueType::ColorArgb8 => format!("#{:08x}", self.data),
+            ResourceValueType::ColorRgb8 => format!("#{:06x}", self.data & 0xffffff),
+            ResourceValueType::ColorArgb4 => format!(
+                "#{:x}{:x}{:x}{:x}",
+                (self.data >> 28) & 0xf,
+                (self.data >> 20) & 0xf,
+                (self.data >> 12) & 0xf,
+                (self.data >> 4) & 0xf
+            ),
+            ResourceValueType::ColorRgb4 => format!(
+                "#{:x}{:x}{:x}",
+                (self.data >> 20) & 0xf,
+                (self.data >> 12) & 0xf,
+                (self.data >> 4) & 0xf
+            ),
+        };
+
+        Ok(Rc::new(value))
+    }
+
+    ///Formats values this crate cannot decode, such as dimensions in an
+    ///unknown unit, as their raw data.
+    fn format_unknown(&self) -> String {
+        format!("@0x{:08x}", self.data)
+    }
+
+    ///Returns whether the value can be formatted as what it stands for,
+    ///rather than as its raw data.
+    pub(crate) fn is_supported(&self) -> bool {
+        let unit = (self.data & complex::UNIT_MASK) as usize;
+        match self.data_type {
+            ResourceValueType::Dimension => unit < DIMENSION_UNITS.len(),
+            ResourceValueType::Fraction => unit < FRACTION_UNITS.len(),
+            _ => true,
         }
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    ///Converts `value`, as formatted by [ResourceValue::get_value], back into
+    ///a value of type `data_type`. The data of strings is left for the encoder
+    ///to fill in with a pool index.
+    pub(crate) fn from_str_as(data_type: ResourceValueType, value: &str) -> Option<Self> {
+        let data = match data_type {
+            ResourceValueType::String => 0,
+            ResourceValueType::Null => match value {
+                "" => 0,
+                "@empty" => DATA_NULL_EMPTY,
+                _ => return None,
+            },
+            ResourceValueType::Reference => parse_reference('@', value)?,
+            ResourceValueType::Attribute => parse_reference('?', value)?,
+            ResourceValueType::Float => value.parse::<f32>().ok()?.to_bits(),
+            ResourceValueType::Dimension => {
+                let (i, number) = DIMENSION_UNITS
+                    .iter()
+                    .position(|unit| value.ends_with(unit))
+                    .map(|i| (i, &value[..value.len() - DIMENSION_UNITS[i].len()]))
+                    .or_else(|| Some((1, value.strip_suffix("dp")?)))?;
+                float_to_complex(number.parse().ok()?) | i as u32
+            }
+            ResourceValueType::Fraction => {
+                let (i, number) = FRACTION_UNITS
+                    .iter()
+                    .rposition(|unit| value.ends_with(unit))
+                    .map(|i| (i, &value[..value.len() - FRACTION_UNITS[i].len()]))?;
+                float_to_complex(number.parse::<f32>().ok()? / 100.0) | i as u32
+            }
+            ResourceValueType::Dec => match value.parse::<u32>() {
+                Ok(data) => data,
+                Err(_) => value.parse::<i32>().ok()? as u32,
+            },
+            ResourceValueType::Hex => u32::from_str_radix(value.strip_prefix("0x")?, 16).ok()?,
+            ResourceValueType::Boolean => match value {
+                "true" => u32::MAX,
+                "false" => 0,
+                _ => return None,
+            },
+            ResourceValueType::ColorArgb8 => parse_color(value, 8)?,
+            ResourceValueType::ColorRgb8 => 0xff000000 | parse_color(value, 6)?,
+            ResourceValueType::ColorArgb4 => expand_color(parse_color(value, 4)?),
+            ResourceValueType::ColorRgb4 => 0xff000000 | expand_color(parse_color(value, 3)?),
+        };
+
+        Some(Self {
+            size: 8,
+            res: 0,
+            data_type,
+            data,
+        })
+    }
+
+    ///Picks the most specific type that can represent `value`. Floats and
+    ///fractions are never inferred, as plain text such as version names would
+    ///be mistaken for them.
+    pub(crate) fn infer(value: &str) -> Self {
+        [
+            ResourceValueType::Boolean,
+            ResourceValueType::Dec,
+            ResourceValueType::Hex,
+            ResourceValueType::Reference,
+            ResourceValueType::Attribute,
+            ResourceValueType::Dimension,
+            ResourceValueType::ColorArgb8,
+            ResourceValueType::ColorRgb8,
+            ResourceValueType::ColorArgb4,
+            ResourceValueType::ColorRgb4,
+        ]
+        .iter()
+        .find_map(|&data_type| Self::from_str_as(data_type, value))
+        .unwrap_or(Self {
+            size: 8,
+            res: 0,
+            data_type: ResourceValueType::String,
+            data: 0,
+        })
+    }
+
+    pub(crate) fn write_to_file<F: Write + Seek>(
+        &self,
+        output: &mut F,
+    ) -> Result<usize, std::io::Error> {
         let n = write_u16(output, self.size)?;
         let n = n + write_u8(output, self.res)?;
         let n = n + write_u8(output, self.data_type as u8)?;
@@ -54,40 +299,290 @@ impl ResourceValue {
     }
 }
 
+///Data of a [ResourceValueType::Null] value explicitly set to empty, as
+///opposed to left undefined.
+const DATA_NULL_EMPTY: u32 = 1;
+
+///Dimension units, indexed by their `COMPLEX_UNIT_*` value.
+const DIMENSION_UNITS: [&str; 6] = ["px", "dip", "sp", "pt", "in", "mm"];
+
+///Fraction units, indexed by their `COMPLEX_UNIT_*` value.
+const FRACTION_UNITS: [&str; 2] = ["%", "%p"];
+
+fn format_reference(prefix: char, data: u32) -> String {
+    match data {
+        0 => format!("{}null", prefix),
+        _ => format!("{}0x{:08x}", prefix, data),
+    }
+}
+
+fn parse_reference(prefix: char, value: &str) -> Option<u32> {
+    let value = value.strip_prefix(prefix)?;
+    if value == "null" {
+        return Some(0);
+    }
+    u32::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+///Formats `f` so that it always reads as a float, e.g. `16.0` rather than
+///`16`.
+fn format_float(f: f32, float_format: FloatFormat) -> String {
+    if float_format == FloatFormat::Aapt {
+        return format_float_g(f);
+    }
+    let s = f.to_string();
+    if f.is_finite() && !s.contains('.') {
+        s + ".0"
+    } else {
+        s
+    }
+}
+
+///Formats `f` like `printf("%g")` does, which is what aapt uses.
+fn format_float_g(f: f32) -> String {
+    const PRECISION: i32 = 6;
+
+    if !f.is_finite() {
+        return match f {
+            f if f.is_nan() => "nan".to_string(),
+            f if f > 0.0 => "inf".to_string(),
+            _ => "-inf".to_string(),
+        };
+    }
+    if f == 0.0 {
+        return if f.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    // The exponent after rounding to the precision decides the notation.
+    let scientific = format!("{:.*e}", (PRECISION - 1) as usize, f);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    let trim = |s: &str| {
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            s.to_string()
+        }
+    };
+
+    if (-4..PRECISION).contains(&exponent) {
+        trim(&format!("{:.*}", (PRECISION - 1 - exponent) as usize, f))
+    } else {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim(mantissa), sign, exponent.abs())
+    }
+}
+
+///Parses `digits` hex digits following a `#`.
+fn parse_color(value: &str, digits: usize) -> Option<u32> {
+    let value = value.strip_prefix('#')?;
+    if value.len() != digits || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(value, 16).ok()
+}
+
+///Expands a color with 4 bits per channel to 8 bits per channel.
+fn expand_color(color: u32) -> u32 {
+    (0..4).fold(0, |expanded, i| {
+        expanded | (((color >> (i * 4)) & 0xf) * 0x11) << (i * 8)
+    })
+}
+
+///Decodes the value of a complex dimension or fraction: a 24-bit signed
+///mantissa with one of four radixes selecting where its binary point is.
+fn complex_to_float(data: u32) -> f32 {
+    const RADIX_MULTIPLIERS: [f32; 4] = [
+        1.0 / (1u32 << 8) as f32,
+        1.0 / (1u32 << 15) as f32,
+        1.0 / (1u32 << 23) as f32,
+        1.0 / (1u32 << 31) as f32,
+    ];
+
+    let radix = (data >> complex::RADIX_SHIFT) & complex::RADIX_MASK;
+    ((data & 0xffffff00) as i32) as f32 * RADIX_MULTIPLIERS[radix as usize]
+}
+
+///Encodes `f` as the value of a complex dimension or fraction, picking the
+///radix that keeps the most precision like aapt does. The unit is left 0.
+fn float_to_complex(f: f32) -> u32 {
+    let negative = f < 0.0;
+    let bits = (f.abs() as f64 * f64::from(1u32 << 23) + 0.5) as u64;
+
+    let (radix, shift) = if bits & 0x7fffff == 0 {
+        (0, 23)
+    } else if bits & !0x7fffff == 0 {
+        (3, 0)
+    } else if bits & !0x7fffffff == 0 {
+        (2, 8)
+    } else if bits & !0x7fffffffff == 0 {
+        (1, 16)
+    } else {
+        (0, 23)
+    };
+
+    let mut mantissa = ((bits >> shift) & 0xffffff) as u32;
+    if negative {
+        mantissa = mantissa.wrapping_neg() & 0xffffff;
+    }
+    (mantissa << 8) | (radix << 4)
+}
+
+///The data types of a `Res_value`, as defined in the framework's
+///`ResourceTypes.h`.
 #[repr(u8)]
-#[derive(Debug, PartialEq, TryFromPrimitive, Copy, Clone, Eq)]
-pub(crate) enum ResourceValueType {
+#[derive(Debug, PartialEq, TryFromPrimitive, Copy, Clone, Eq, Hash)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum ResourceValueType {
+    ///No value, or an explicitly empty one.
     Null = 0x00,
+    ///A resource id, written as `@0x7f010001`.
     Reference = 0x01,
+    ///A theme attribute id, written as `?0x01010036`.
     Attribute = 0x02,
+    ///An index into the string pool.
     String = 0x03,
+    ///An IEEE 754 single precision float.
     Float = 0x04,
+    ///A complex dimension, see [complex].
     Dimension = 0x05,
+    ///A complex fraction, see [complex].
     Fraction = 0x06,
+    ///A decimal integer.
     Dec = 0x10,
+    ///An integer written in hexadecimal.
     Hex = 0x11,
+    ///A boolean, 0 for false and anything else for true.
     Boolean = 0x12,
+    ///A color written as `#aarrggbb`.
     ColorArgb8 = 0x1c,
+    ///A color written as `#rrggbb`.
     ColorRgb8 = 0x1d,
+    ///A color written as `#argb`.
     ColorArgb4 = 0x1e,
+    ///A color written as `#rgb`.
     ColorRgb4 = 0x1f,
 }
 
+///The layout of the data of [ResourceValueType::Dimension] and
+///[ResourceValueType::Fraction] values: a unit in the lowest four bits, a
+///radix in the two above them, and a signed 24-bit mantissa in the upper
+///bits.
+pub mod complex {
+    pub const UNIT_SHIFT: u32 = 0;
+    pub const UNIT_MASK: u32 = 0xf;
+    pub const UNIT_PX: u32 = 0;
+    pub const UNIT_DIP: u32 = 1;
+    pub const UNIT_SP: u32 = 2;
+    pub const UNIT_PT: u32 = 3;
+    pub const UNIT_IN: u32 = 4;
+    pub const UNIT_MM: u32 = 5;
+    ///The unit of fractions of the base size, written as `%`.
+    pub const UNIT_FRACTION: u32 = 0;
+    ///The unit of fractions of the parent size, written as `%p`.
+    pub const UNIT_FRACTION_PARENT: u32 = 1;
+
+    pub const RADIX_SHIFT: u32 = 4;
+    pub const RADIX_MASK: u32 = 0x3;
+    ///The mantissa is an integer.
+    pub const RADIX_23P0: u32 = 0;
+    ///The mantissa has 16 integer and 7 fraction bits.
+    pub const RADIX_16P7: u32 = 1;
+    ///The mantissa has 8 integer and 15 fraction bits.
+    pub const RADIX_8P15: u32 = 2;
+    ///The mantissa has no integer and 23 fraction bits.
+    pub const RADIX_0P23: u32 = 3;
+
+    pub const MANTISSA_SHIFT: u32 = 8;
+    pub const MANTISSA_MASK: u32 = 0xff_ffff;
+}
 
-    
 #[test]
-fn test_res_value_rw(){
+fn test_res_value_rw() {
     let src = [0x08, 0x00, 0x00, 0x12, 0x00, 0x00, 0x00, 0x00];
     let mut dst: Vec<u8> = vec![];
     let mut cursor = std::io::Cursor::new(src);
     let v = ResourceValue::read_from_file(&mut cursor).unwrap();
-    assert_eq!(v, ResourceValue {
-        size: 8,
-        res: 0,
-        data_type: ResourceValueType::Boolean,
-        data: 0
-    });
+    assert_eq!(
+        v,
+        ResourceValue {
+            size: 8,
+            res: 0,
+            data_type: ResourceValueType::Boolean,
+            data: 0
+        }
+    );
     let mut cursor = std::io::Cursor::new(&mut dst);
     let n = v.write_to_file(&mut cursor).unwrap();
+    assert_eq!(n, src.len());
     assert_eq!(&src, dst.as_slice());
 }
+
+#[test]
+fn test_complex_values() {
+    for &(data_type, value) in &[
+        (ResourceValueType::Dimension, "16.0dip"),
+        (ResourceValueType::Dimension, "-0.5px"),
+        (ResourceValueType::Dimension, "1.25sp"),
+        (ResourceValueType::Fraction, "50.0%"),
+        (ResourceValueType::Fraction, "12.5%p"),
+        (ResourceValueType::Float, "0.75"),
+        (ResourceValueType::Reference, "@0x7f010001"),
+        (ResourceValueType::Reference, "@null"),
+        (ResourceValueType::Attribute, "?0x01010036"),
+        (ResourceValueType::ColorArgb8, "#80ff0000"),
+        (ResourceValueType::ColorRgb8, "#00ff00"),
+        (ResourceValueType::ColorArgb4, "#8f00"),
+        (ResourceValueType::ColorRgb4, "#0f0"),
+        (ResourceValueType::Null, "@empty"),
+    ] {
+        let v = ResourceValue::from_str_as(data_type, value).unwrap();
+        let pool = ResStringPool::new(false);
+        assert_eq!(v.get_value(&pool).unwrap().as_str(), value);
+    }
+
+    assert_eq!(
+        ResourceValue::from_str_as(ResourceValueType::Dimension, "16dp")
+            .unwrap()
+            .data,
+        0x1001
+    );
+    assert_eq!(
+        ResourceValue::infer("#ff0000").data_type,
+        ResourceValueType::ColorRgb8
+    );
+    assert_eq!(
+        ResourceValue::infer("1.5").data_type,
+        ResourceValueType::String
+    );
+}
+
+#[test]
+fn test_aapt_float_format() {
+    for &(f, expected) in &[
+        (0.75, "0.75"),
+        (16.0, "16"),
+        (-2.5, "-2.5"),
+        (0.1, "0.1"),
+        (1e-5, "1e-05"),
+        (0.0001, "0.0001"),
+        (100000.0, "100000"),
+        (1234567.0, "1.23457e+06"),
+        (999999.5, "1e+06"),
+        (0.0, "0"),
+    ] {
+        assert_eq!(format_float_g(f), expected, "{}", f);
+    }
+
+    let pool = ResStringPool::new(false);
+    let value = |data_type, value| {
+        ResourceValue::from_str_as(data_type, value)
+            .unwrap()
+            .format(&pool, FloatFormat::Aapt)
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(value(ResourceValueType::Dimension, "16.0dip"), "16dip");
+    assert_eq!(value(ResourceValueType::Fraction, "12.5%p"), "12.5%p");
+    assert_eq!(value(ResourceValueType::Float, "0.75"), "0.75");
+}
@@ -0,0 +1,309 @@
+use crate::aliases::intent_filters;
+use crate::lint::EXPORTED_REQUIRED_SDK;
+use crate::manifest::{child_elements, Manifest};
+use crate::permissions::{Permission, ProtectionLevel};
+use crate::xml::Element;
+
+///The API level from which cleartext traffic is disabled by default.
+const CLEARTEXT_DISABLED_SDK: u32 = 28;
+///The API level from which providers are no longer exported by default.
+const PROVIDER_UNEXPORTED_SDK: u32 = 17;
+
+///The `android:launchMode` values that let an activity start in a task
+///other than the one of its caller.
+const SINGLE_TASK: u32 = 2;
+const SINGLE_INSTANCE: u32 = 3;
+
+///A summary of the security relevant settings of a manifest, as returned by
+///[analyze_security].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SecurityReport {
+    ///Whether `android:debuggable` is set on the application.
+    pub debuggable: bool,
+    ///Whether the app data is included in backups, which is the default
+    ///unless `android:allowBackup` is `false`.
+    pub allow_backup: bool,
+    ///Whether the app may use cleartext network traffic, as set by
+    ///`android:usesCleartextTraffic` or by default when targeting an API
+    ///level below 28. A network security config may override it.
+    pub cleartext_traffic: bool,
+    ///The `android:networkSecurityConfig` resource, if any.
+    pub network_security_config: Option<String>,
+    ///Components other apps can start without holding a permission.
+    pub unprotected_components: Vec<ExposedComponent>,
+    ///Permissions the app declares with a `normal` or `dangerous`
+    ///protection level, which any app can obtain.
+    pub weak_permissions: Vec<Permission>,
+    ///Activities that other apps could place their own activities in front
+    ///of.
+    pub task_hijacking_risks: Vec<TaskHijackingRisk>,
+}
+
+///A component exported without a permission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExposedComponent {
+    ///The tag of the component, such as `activity-alias`.
+    pub tag: String,
+    ///The fully qualified name of the component.
+    pub name: String,
+    ///Whether the component is exported only because it has an intent
+    ///filter and does not set `android:exported`.
+    pub implicitly_exported: bool,
+}
+
+///An activity with a task affinity that is launched into its own task or
+///can move between tasks, which lets other apps with the same affinity
+///take over its task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TaskHijackingRisk {
+    ///The fully qualified name of the activity.
+    pub activity: String,
+    ///The task affinity, the package name unless set otherwise.
+    pub task_affinity: String,
+    ///Whether the activity is launched as `singleTask` or `singleInstance`.
+    pub own_task: bool,
+    ///Whether `android:allowTaskReparenting` is set.
+    pub allow_task_reparenting: bool,
+}
+
+///Summarizes the security posture of an app from its manifest.
+///
+///```rust
+///use axmldecoder::{analyze_security, parse};
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let mut f = std::fs::File::open(manifest_file).unwrap();
+///let xml = parse(&mut f).unwrap();
+///let report = analyze_security(&xml.manifest().unwrap());
+///assert!(report.debuggable);
+///```
+pub fn analyze_security(manifest: &Manifest<'_>) -> SecurityReport {
+    let target_sdk = manifest.target_sdk_version().unwrap_or(1);
+    let application = match manifest.application() {
+        Some(application) => application,
+        None => {
+            return SecurityReport {
+                allow_backup: true,
+                cleartext_traffic: target_sdk < CLEARTEXT_DISABLED_SDK,
+                weak_permissions: weak_permissions(manifest),
+                ..SecurityReport::default()
+            }
+        }
+    };
+
+    let mut report = SecurityReport {
+        debuggable: application.android_bool("debuggable") == Some(true),
+        allow_backup: application.android_bool("allowBackup") != Some(false),
+        cleartext_traffic: application
+            .android_bool("usesCleartextTraffic")
+            .unwrap_or(target_sdk < CLEARTEXT_DISABLED_SDK),
+        network_security_config: application
+            .android_value("networkSecurityConfig")
+            .map(str::to_string),
+        weak_permissions: weak_permissions(manifest),
+        ..SecurityReport::default()
+    };
+
+    let app_permission = application.android_value("permission");
+    let default_affinity = application
+        .android_value("taskAffinity")
+        .or_else(|| manifest.package())
+        .unwrap_or_default();
+    for e in child_elements(application) {
+        let tag = e.get_tag();
+        if !matches!(
+            tag,
+            "activity" | "activity-alias" | "service" | "receiver" | "provider"
+        ) || e.android_bool("enabled") == Some(false)
+        {
+            continue;
+        }
+        let name = manifest.resolve_class_name(e.android_value("name").unwrap_or_default());
+
+        let implicitly_exported = implicitly_exported(e, target_sdk);
+        let exported = e.android_bool("exported") == Some(true) || implicitly_exported;
+        if exported && !is_protected(e, tag, app_permission) {
+            report.unprotected_components.push(ExposedComponent {
+                tag: tag.to_string(),
+                name: name.clone(),
+                implicitly_exported,
+            });
+        }
+
+        if tag == "activity" {
+            let task_affinity = e.android_value("taskAffinity").unwrap_or(default_affinity);
+            let own_task = launch_mode(e).is_some_and(|m| m == SINGLE_TASK || m == SINGLE_INSTANCE);
+            let allow_task_reparenting = e
+                .android_bool("allowTaskReparenting")
+                .or_else(|| application.android_bool("allowTaskReparenting"))
+                == Some(true);
+            if !task_affinity.is_empty() && (own_task || allow_task_reparenting) {
+                report.task_hijacking_risks.push(TaskHijackingRisk {
+                    activity: name,
+                    task_affinity: task_affinity.to_string(),
+                    own_task,
+                    allow_task_reparenting,
+                });
+            }
+        }
+    }
+    report
+}
+
+fn weak_permissions(manifest: &Manifest<'_>) -> Vec<Permission> {
+    manifest
+        .permissions()
+        .into_iter()
+        .filter(|p| {
+            let level = p.protection_level.base();
+            level == ProtectionLevel::NORMAL || level == ProtectionLevel::DANGEROUS
+        })
+        .collect()
+}
+
+///Whether a component without `android:exported` is exported by default
+///in an app targeting `target_sdk`.
+pub(crate) fn implicitly_exported(component: &Element, target_sdk: u32) -> bool {
+    component.android_bool("exported").is_none()
+        && if component.get_tag() == "provider" {
+            target_sdk < PROVIDER_UNEXPORTED_SDK
+        } else {
+            target_sdk < EXPORTED_REQUIRED_SDK && !intent_filters(component).is_empty()
+        }
+}
+
+///Whether starting or accessing the component requires a permission. A
+///provider is only protected if both reading and writing are.
+fn is_protected(component: &Element, tag: &str, app_permission: Option<&str>) -> bool {
+    let permission = component.android_value("permission").or(app_permission);
+    if tag != "provider" {
+        return permission.is_some();
+    }
+    let read = component.android_value("readPermission").or(permission);
+    let write = component.android_value("writePermission").or(permission);
+    read.is_some() && write.is_some()
+}
+
+///Reads `android:launchMode`, which is compiled to an integer but may be
+///given by name in documents built by hand.
+fn launch_mode(activity: &Element) -> Option<u32> {
+    let attr = activity.android_attribute("launchMode")?;
+    attr.integer_value().or(match attr.value.as_str() {
+        "standard" => Some(0),
+        "singleTop" => Some(1),
+        "singleTask" => Some(SINGLE_TASK),
+        "singleInstance" => Some(SINGLE_INSTANCE),
+        "singleInstancePerTask" => Some(4),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_analyze_security() {
+    use crate::test_support::{element, manifest_document};
+
+    let manifest = |target_sdk: &str| {
+        manifest_document(vec![
+            element(
+                "uses-sdk",
+                &[("android:targetSdkVersion", target_sdk)],
+                vec![],
+            ),
+            element(
+                "permission",
+                &[
+                    ("android:name", "com.example.WEAK"),
+                    ("android:protectionLevel", "dangerous"),
+                ],
+                vec![],
+            ),
+            element(
+                "permission",
+                &[
+                    ("android:name", "com.example.STRONG"),
+                    ("android:protectionLevel", "signature"),
+                ],
+                vec![],
+            ),
+            element(
+                "application",
+                &[("android:allowBackup", "false")],
+                vec![
+                    element(
+                        "activity",
+                        &[("android:name", ".Main")],
+                        vec![element("intent-filter", &[], vec![])],
+                    ),
+                    element(
+                        "activity",
+                        &[
+                            ("android:name", ".Single"),
+                            ("android:launchMode", "singleTask"),
+                            ("android:exported", "false"),
+                        ],
+                        vec![],
+                    ),
+                    element(
+                        "activity",
+                        &[
+                            ("android:name", ".Isolated"),
+                            ("android:launchMode", "singleTask"),
+                            ("android:taskAffinity", ""),
+                        ],
+                        vec![],
+                    ),
+                    element(
+                        "service",
+                        &[
+                            ("android:name", ".Protected"),
+                            ("android:exported", "true"),
+                            ("android:permission", "com.example.STRONG"),
+                        ],
+                        vec![],
+                    ),
+                    element(
+                        "provider",
+                        &[
+                            ("android:name", ".Provider"),
+                            ("android:readPermission", "com.example.STRONG"),
+                        ],
+                        vec![],
+                    ),
+                ],
+            ),
+        ])
+    };
+
+    let xml = manifest("16");
+    let report = analyze_security(&xml.manifest().unwrap());
+    assert!(!report.debuggable);
+    assert!(!report.allow_backup);
+    assert!(report.cleartext_traffic);
+    let exposed: Vec<_> = report
+        .unprotected_components
+        .iter()
+        .map(|c| (c.name.as_str(), c.implicitly_exported))
+        .collect();
+    assert_eq!(
+        exposed,
+        [("com.example.Main", true), ("com.example.Provider", true)]
+    );
+    assert_eq!(report.weak_permissions.len(), 1);
+    assert_eq!(report.weak_permissions[0].name, "com.example.WEAK");
+    assert_eq!(
+        report.task_hijacking_risks,
+        [TaskHijackingRisk {
+            activity: "com.example.Single".to_string(),
+            task_affinity: "com.example".to_string(),
+            own_task: true,
+            allow_task_reparenting: false,
+        }]
+    );
+
+    let xml = manifest("33");
+    let report = analyze_security(&xml.manifest().unwrap());
+    assert!(!report.cleartext_traffic);
+    assert!(report.unprotected_components.is_empty());
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+///Statistics about the structure of a binary XML file, gathered while
+///parsing it with [parse_with_stats](crate::parse_with_stats).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParseStats {
+    ///Number of chunks read per chunk type, including the XML chunk that
+    ///contains all others and any chunks that were skipped.
+    pub chunk_counts: BTreeMap<u16, usize>,
+    ///Number of entries in the string pool.
+    pub string_count: usize,
+    ///Whether the string pool is encoded as UTF-8 rather than UTF-16.
+    pub utf8_strings: bool,
+    ///Total number of attributes over all elements.
+    pub attribute_count: usize,
+    ///Maximum nesting depth of elements, the root being at depth 1.
+    pub max_depth: usize,
+    ///Number of bytes from the start of the document to the end of the last
+    ///chunk that was read.
+    pub bytes_consumed: u64,
+    ///Number of bytes from the start of the document to the end of the
+    ///input. Anything beyond `bytes_consumed` was never looked at.
+    pub file_size: u64,
+}
+
+impl ParseStats {
+    pub(crate) fn count_chunk(&mut self, typ: u16) {
+        *self.chunk_counts.entry(typ).or_default() += 1;
+    }
+}
+
+#[test]
+fn test_parse_stats() {
+    let mut data = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let size = data.len() as u64;
+    data.extend_from_slice(b"trailing");
+
+    let config = crate::ParseConfig::default();
+    let (_, stats) = crate::parse_with_stats(&mut std::io::Cursor::new(&data), &config).unwrap();
+    assert_eq!(stats.chunk_counts[&0x0003], 1);
+    assert_eq!(stats.chunk_counts[&0x0001], 1);
+    assert_eq!(stats.chunk_counts[&0x0102], stats.chunk_counts[&0x0103]);
+    assert_eq!(stats.max_depth, 5);
+    assert_eq!(stats.bytes_consumed, size);
+    assert_eq!(stats.file_size, size + 8);
+    assert!(stats.string_count > 0);
+    assert!(stats.attribute_count >= 10);
+}
@@ -1,13 +1,16 @@
 use byteorder::ByteOrder;
 use byteorder::LittleEndian;
 use std::convert::TryFrom;
-use std::io::{Read, Seek, Write, SeekFrom};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::rc::Rc;
 
-use crate::binaryxml::ChunkHeader;
-use crate::{read_u32, ParseError, write_u32, write_u16};
+use crate::binaryxml::{ChunkHeader, ResourceType};
+use crate::config::ParseConfig;
+use crate::diagnostics::{push_warning, Warning};
+use crate::{read_u32, write_u16, write_u32, write_u8, ParseError};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct StringPoolHeader {
     pub(crate) chunk_header: ChunkHeader,
     pub(crate) string_count: u32,
@@ -22,7 +25,7 @@ impl StringPoolHeader {
         input: &mut F,
         chunk_header: &ChunkHeader,
     ) -> Result<Self, ParseError> {
-        let chunk_header = chunk_header.clone();
+        let chunk_header = *chunk_header;
         let string_count = read_u32(input)?;
         let style_count = read_u32(input)?;
         let flags = read_u32(input)?;
@@ -42,7 +45,7 @@ impl StringPoolHeader {
         Ok(header)
     }
 
-    fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    fn write_to_file<F: Write + Seek>(&self, output: &mut F) -> Result<usize, std::io::Error> {
         let mut n = self.chunk_header.write_to_file(output)?;
         n += write_u32(output, self.string_count)?;
         n += write_u32(output, self.style_count)?;
@@ -53,25 +56,150 @@ impl StringPoolHeader {
     }
 }
 
+///Size of the string pool chunk header, including the chunk header itself.
+const STRING_POOL_HEADER_SIZE: usize = std::mem::size_of::<StringPoolHeader>();
+
+///The longest string a UTF-8 pool can store, in bytes and in UTF-16 code
+///units.
+const MAX_UTF8_POOL_LENGTH: usize = 0x7fff;
+
+///Set in [StringPoolHeader::flags] when strings are encoded as UTF-8.
+const UTF8_FLAG: u32 = 1 << 8;
+
+///Terminates the list of spans of a style.
+const SPAN_END: u32 = u32::MAX;
+
+///A `ResStringPool`, the string table shared by binary XML documents and
+///`resources.arsc` resource tables.
+///
+///Pools can be read and written on their own, with strings encoded as either
+///UTF-8 or UTF-16. The optional style of a string is a list of
+///[StringPoolSpan]s; the style at index `i` belongs to the string at index
+///`i`.
+///
+///```rust
+///use axmldecoder::{ResStringPool, StringPoolSpan};
+///
+///let mut pool = ResStringPool::new(true);
+///let hello = pool.push("Hello, world");
+///let bold = pool.push("b");
+///pool.set_style(hello, vec![StringPoolSpan { name: bold, first_char: 0, last_char: 4 }])?;
+///
+///let mut data = std::io::Cursor::new(Vec::new());
+///pool.write_to_file(&mut data).unwrap();
+///data.set_position(0);
+///
+///let pool = ResStringPool::read_from_file(&mut data).unwrap();
+///assert_eq!(pool.get_string(hello), Some("Hello, world"));
+///assert_eq!(pool.get_styles()[0][0].last_char, 4);
+///# Ok::<(), axmldecoder::ParseError>(())
+///```
 #[derive(Debug)]
-pub struct StringPool {
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ResStringPool {
     pub(crate) header: StringPoolHeader,
     pub(crate) strings: Vec<Rc<String>>,
+    pub(crate) styles: Vec<Vec<StringPoolSpan>>,
 }
 
-impl StringPool {
-    pub(crate) fn read_from_file<F: Read + Seek>(
+///A styled range of a string, such as `<b>` markup in a string resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct StringPoolSpan {
+    ///Index in the pool of the name of the span, for example `b`.
+    pub name: u32,
+    ///Index of the first character the span applies to.
+    pub first_char: u32,
+    ///Index of the last character the span applies to.
+    pub last_char: u32,
+}
+
+impl ResStringPool {
+    ///Creates an empty pool, encoding strings as UTF-8 if `utf8` is set and
+    ///as UTF-16 otherwise.
+    pub fn new(utf8: bool) -> Self {
+        let mut pool = Self::from_strings(Vec::new());
+        pool.set_utf8(utf8);
+        pool
+    }
+
+    ///Creates a UTF-16 pool holding `strings`. The header is filled in when
+    ///the pool is written.
+    pub(crate) fn from_strings(strings: Vec<Rc<String>>) -> Self {
+        Self {
+            header: StringPoolHeader {
+                chunk_header: ChunkHeader {
+                    typ: ResourceType::StringPool,
+                    header_size: STRING_POOL_HEADER_SIZE as u16,
+                    size: 0,
+                },
+                string_count: 0,
+                style_count: 0,
+                flags: 0,
+                string_start: 0,
+                style_start: 0,
+            },
+            strings,
+            styles: Vec::new(),
+        }
+    }
+
+    ///Reads a string pool chunk, starting at its chunk header.
+    pub fn read_from_file<F: Read + Seek>(input: &mut F) -> Result<Self, ParseError> {
+        let offset = input.stream_position().map_err(ParseError::IoError)?;
+        let input_end = input.seek(SeekFrom::End(0)).map_err(ParseError::IoError)?;
+        input
+            .seek(SeekFrom::Start(offset))
+            .map_err(ParseError::IoError)?;
+
+        let header = ChunkHeader::read_from_file(input, offset)?;
+        if header.typ != ResourceType::StringPool {
+            return Err(ParseError::UnexpectedChunkType {
+                found: header.typ.into(),
+                offset,
+            });
+        }
+
+        let config = ParseConfig::default();
+        header.validate(offset, input_end.saturating_sub(offset), &config)?;
+        Self::read_chunk(input, &header, &config, &mut Vec::new())
+    }
+
+    ///Returns the size of the string pool chunk past its header, failing if
+    ///it exceeds [ParseConfig::max_string_pool_size], so that an oversized
+    ///pool is rejected before any of it is read.
+    pub(crate) fn data_size(
+        chunk_header: &ChunkHeader,
+        config: &ParseConfig,
+    ) -> Result<usize, ParseError> {
+        let s = usize::try_from(chunk_header.size)
+            .ok()
+            .and_then(|size| size.checked_sub(STRING_POOL_HEADER_SIZE))
+            .ok_or(ParseError::SizeMismatch("string pool header"))?;
+        if let Some(max) = config.max_string_pool_size {
+            if s > max {
+                return Err(ParseError::LimitExceeded {
+                    limit: "string pool size",
+                    max,
+                });
+            }
+        }
+        Ok(s)
+    }
+
+    ///Reads the rest of a string pool chunk whose `chunk_header` was already
+    ///read.
+    pub(crate) fn read_chunk<F: Read + Seek>(
         input: &mut F,
         chunk_header: &ChunkHeader,
+        config: &ParseConfig,
+        warnings: &mut Vec<Warning>,
     ) -> Result<Self, ParseError> {
         let string_pool_header = StringPoolHeader::read_from_file(input, chunk_header)?;
-        assert_eq!(string_pool_header.style_count, 0);
 
-        let flag_is_utf8 = (string_pool_header.flags & (1 << 8)) != 0;
+        let flag_is_utf8 = (string_pool_header.flags & UTF8_FLAG) != 0;
 
-        const STRINGPOOL_HEADER_SIZE: usize = std::mem::size_of::<StringPoolHeader>();
-        let s =
-            usize::try_from(string_pool_header.chunk_header.size).unwrap() - STRINGPOOL_HEADER_SIZE;
+        let s = Self::data_size(&string_pool_header.chunk_header, config)?;
         let mut string_pool_data = vec![0; s];
 
         input
@@ -79,15 +207,23 @@ impl StringPool {
             .map_err(ParseError::IoError)?;
 
         // Parse string offsets
-        let num_offsets = usize::try_from(string_pool_header.string_count).unwrap();
-        let offsets = parse_offsets(&string_pool_data, num_offsets);
-
-        let string_data_start =
-            usize::try_from(string_pool_header.string_start).unwrap() - STRINGPOOL_HEADER_SIZE;
-        let string_data = &string_pool_data[string_data_start..];
-
-        let mut strings =
-            Vec::with_capacity(usize::try_from(string_pool_header.string_count).unwrap());
+        let num_offsets = usize::try_from(string_pool_header.string_count)
+            .map_err(|_| ParseError::SizeMismatch("string offsets"))?;
+        let offsets = parse_offsets(&string_pool_data, 0, num_offsets)?;
+
+        let string_data = usize::try_from(string_pool_header.string_start)
+            .ok()
+            .and_then(|start| start.checked_sub(STRING_POOL_HEADER_SIZE))
+            .and_then(|start| string_pool_data.get(start..))
+            .ok_or(ParseError::SizeMismatch("string data"))?;
+
+        let offsets = offsets
+            .into_iter()
+            .map(|offset| {
+                usize::try_from(offset).map_err(|_| ParseError::SizeMismatch("string offsets"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut strings = Vec::with_capacity(offsets.len());
 
         let parse_fn = if flag_is_utf8 {
             parse_utf8_string
@@ -95,53 +231,171 @@ impl StringPool {
             parse_utf16_string
         };
 
-        for offset in offsets {
-            strings.push(Rc::new(parse_fn(
-                &string_data,
-                usize::try_from(offset).unwrap(),
-            )?));
+        let decoded = decode_strings(string_data, &offsets, parse_fn, !flag_is_utf8);
+        for ((index, &offset), s) in (0..).zip(&offsets).zip(decoded) {
+            // Malformed strings are decoded again to get at the error, which
+            // is rare enough not to matter.
+            let s = match s.map_or_else(|| parse_fn(string_data, offset, false), Ok) {
+                Ok(s) => s,
+                Err(_) if config.substitute_invalid_strings => {
+                    push_warning(warnings, Warning::SubstitutedString(index));
+                    parse_fn(string_data, offset, true).unwrap_or_default()
+                }
+                Err(e) => return Err(e),
+            };
+            strings.push(Rc::new(s));
         }
 
-        strings.push(Rc::new("hello_world".to_string()));
+        // Style spans are not needed to recover the strings themselves, so
+        // malformed style data is dropped instead of failing.
+        let styles =
+            parse_styles(&string_pool_data, &string_pool_header, num_offsets).unwrap_or_default();
 
         Ok(Self {
             header: string_pool_header,
             strings,
+            styles,
         })
     }
 
-    pub(crate) fn get(&self, i: usize) -> Option<Rc<String>> {
-        if u32::try_from(i).unwrap() == u32::MAX {
+    pub(crate) fn get(&self, i: u32) -> Option<Rc<String>> {
+        if i == u32::MAX {
             return None;
         }
 
-        Some(self.strings.get(i)?.clone())
+        Some(self.strings.get(usize::try_from(i).ok()?)?.clone())
+    }
+
+    ///Returns the string at index `i`.
+    pub fn get_string(&self, i: u32) -> Option<&str> {
+        Some(self.strings.get(usize::try_from(i).ok()?)?.as_str())
     }
 
-    pub(crate) fn write_to_file<F: Write + Seek>(self:&Self, output: &mut F) -> Result<usize, std::io::Error> {
+    ///Returns the strings of the pool, in order.
+    pub fn get_strings(&self) -> impl Iterator<Item = &str> {
+        self.strings.iter().map(|s| s.as_str())
+    }
+
+    ///Returns the styles of the pool. There may be fewer styles than strings,
+    ///in which case the remaining strings are unstyled.
+    pub fn get_styles(&self) -> &[Vec<StringPoolSpan>] {
+        &self.styles
+    }
+
+    ///Returns the number of strings in the pool.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    ///Returns `true` if the pool holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    ///Returns `true` if strings are encoded as UTF-8 rather than UTF-16.
+    pub fn is_utf8(&self) -> bool {
+        self.header.flags & UTF8_FLAG != 0
+    }
+
+    ///Selects the encoding used when the pool is written. Pools holding a
+    ///string longer than 32767 bytes are written as UTF-16 regardless.
+    pub fn set_utf8(&mut self, utf8: bool) {
+        if utf8 {
+            self.header.flags |= UTF8_FLAG;
+        } else {
+            self.header.flags &= !UTF8_FLAG;
+        }
+    }
+
+    ///Appends `s` to the pool and returns its index. Strings are not
+    ///deduplicated.
+    pub fn push(&mut self, s: &str) -> u32 {
+        self.strings.push(Rc::new(s.to_string()));
+        (self.strings.len() - 1) as u32
+    }
+
+    ///Sets the style of the string at `index`, failing with
+    ///[ParseError::StringNotFound] if there is no such string in the pool.
+    pub fn set_style(&mut self, index: u32, spans: Vec<StringPoolSpan>) -> Result<(), ParseError> {
+        if index as usize >= self.strings.len() {
+            return Err(ParseError::StringNotFound(index));
+        }
+
+        let index = index as usize;
+        if self.styles.len() <= index {
+            self.styles.resize(index + 1, Vec::new());
+        }
+        self.styles[index] = spans;
+        Ok(())
+    }
+
+    ///Writes the pool as a string pool chunk, returning the number of bytes
+    ///written.
+    pub fn write_to_file<F: Write + Seek>(&self, output: &mut F) -> Result<usize, std::io::Error> {
         let mut header = self.header.clone();
-        let offset_header = output.seek(SeekFrom::Current(0))?;
+        let offset_header = output.stream_position()?;
         let mut n = self.header.write_to_file(output)?;
-        let offset_start = output.seek(SeekFrom::Current(0))?;
-        for i in 0..self.strings.len() {
-            n += write_u32(output,0)?;
+        let offset_start = output.stream_position()?;
+        for _ in 0..self.strings.len() + self.styles.len() {
+            n += write_u32(output, 0)?;
+        }
+
+        // UTF-8 pools store lengths of at most 0x7fff, so pools with longer
+        // strings are written as UTF-16 instead.
+        let utf8 = self.is_utf8() && self.strings.iter().all(|s| fits_utf8_pool(s));
+        if !utf8 {
+            header.flags &= !UTF8_FLAG;
         }
+        let write_string = if utf8 {
+            write_utf8_string
+        } else {
+            write_utf16_string
+        };
         let mut m = 0;
-        let mut v:Vec<u32> = vec![];
+        let mut v: Vec<u32> = vec![];
         for i in &self.strings {
-            let c = write_utf16_string(output, i.as_str())?;
+            let c = write_string(output, i.as_str())?;
             v.push(m);
             m += c as u32;
             n += c;
         }
-        let offset_end = output.seek(SeekFrom::Current(0))?;
-        let n = n; // no more changed 
+        // The style data and the chunk after the pool must start on a 4-byte
+        // boundary.
+        while m % 4 != 0 {
+            m += write_u8(output, 0)? as u32;
+            n += 1;
+        }
+        let string_data_size = m;
+
+        let mut m = 0;
+        for style in &self.styles {
+            v.push(m);
+            for span in style {
+                m += write_u32(output, span.name)? as u32;
+                m += write_u32(output, span.first_char)? as u32;
+                m += write_u32(output, span.last_char)? as u32;
+            }
+            m += write_u32(output, SPAN_END)? as u32;
+        }
+        if !self.styles.is_empty() {
+            m += write_u32(output, SPAN_END)? as u32;
+            m += write_u32(output, SPAN_END)? as u32;
+        }
+        n += m as usize;
+
+        let offset_end = output.stream_position()?;
+        let n = n; // no more changed
 
         output.seek(SeekFrom::Start(offset_header))?;
         header.chunk_header.size = n as u32;
         header.string_count = self.strings.len() as u32;
-        header.style_count = 0; // FixMe
-        header.string_start = (self.strings.len() * 4 + std::mem::size_of::<StringPoolHeader>()) as u32;
+        header.style_count = self.styles.len() as u32;
+        header.string_start = (v.len() * 4 + STRING_POOL_HEADER_SIZE) as u32;
+        header.style_start = if self.styles.is_empty() {
+            0
+        } else {
+            header.string_start + string_data_size
+        };
         header.write_to_file(output)?;
 
         output.seek(SeekFrom::Start(offset_start))?;
@@ -149,48 +403,213 @@ impl StringPool {
             write_u32(output, i)?;
         }
 
-        output.seek(SeekFrom::Start(offset_end))?;        
+        output.seek(SeekFrom::Start(offset_end))?;
         Ok(n)
     }
 }
 
+///Encodes `s` as UTF-16, characters outside the BMP as surrogate pairs.
 fn write_utf16_string<F: Write + Seek>(output: &mut F, s: &str) -> Result<usize, std::io::Error> {
-    let mut n = write_u16(output, s.len() as u16)?;
-    for i in s.chars() {
-        n += write_u16(output, i as u16)?;
+    let mut n = write_utf16_length(output, s.encode_utf16().count())?;
+    for unit in s.encode_utf16() {
+        n += write_u16(output, unit)?;
     }
     n += write_u16(output, 0)?;
     Ok(n)
 }
 
-fn parse_offsets(string_data: &[u8], count: usize) -> Vec<u32> {
-    let mut offsets = Vec::with_capacity(count);
-
-    for i in 0..count {
-        let index = i * 4;
-        let offset = LittleEndian::read_u32(&string_data[index..index + 4]);
-        offsets.push(offset);
+///Encodes a UTF-16 pool length prefix, counted in code units, the inverse
+///of the length handling in [parse_utf16_string].
+fn write_utf16_length<F: Write + Seek>(
+    output: &mut F,
+    len: usize,
+) -> Result<usize, std::io::Error> {
+    if len > 0x7fff {
+        let n = write_u16(output, 0x8000 | (len >> 16) as u16)?;
+        Ok(n + write_u16(output, len as u16)?)
+    } else {
+        write_u16(output, len as u16)
     }
+}
 
+type ParseFn = fn(&[u8], usize, bool) -> Result<String, ParseError>;
+
+///Decodes the strings at `offsets` in `string_data`, with `None` for those
+///that are malformed.
+#[cfg(not(feature = "rayon"))]
+fn decode_strings(
+    string_data: &[u8],
+    offsets: &[usize],
+    parse_fn: ParseFn,
+    _utf16: bool,
+) -> Vec<Option<String>> {
     offsets
+        .iter()
+        .map(|&offset| parse_fn(string_data, offset, false).ok())
+        .collect()
+}
+
+///Pools with fewer strings than this are decoded on the calling thread, as
+///handing them to the thread pool costs more than it saves.
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_STRINGS: usize = 4096;
+
+///Decodes the strings at `offsets` in `string_data`, with `None` for those
+///that are malformed.
+///
+///Large UTF-16 pools are decoded in parallel chunks. UTF-8 strings are
+///only validated and copied, which is too cheap to be worth splitting up.
+#[cfg(feature = "rayon")]
+fn decode_strings(
+    string_data: &[u8],
+    offsets: &[usize],
+    parse_fn: ParseFn,
+    utf16: bool,
+) -> Vec<Option<String>> {
+    use rayon::prelude::*;
+
+    let decode = |&offset: &usize| parse_fn(string_data, offset, false).ok();
+    if utf16 && offsets.len() >= PARALLEL_MIN_STRINGS {
+        offsets
+            .par_chunks(PARALLEL_MIN_STRINGS / 4)
+            .flat_map_iter(|chunk| chunk.iter().map(decode))
+            .collect()
+    } else {
+        offsets.iter().map(decode).collect()
+    }
 }
 
-fn parse_utf16_string(string_data: &[u8], offset: usize) -> Result<String, ParseError> {
-    let len = LittleEndian::read_u16(&string_data[offset..offset + 2]);
+fn parse_offsets(string_data: &[u8], start: usize, count: usize) -> Result<Vec<u32>, ParseError> {
+    let offset_data = get_bytes(string_data, start, count.checked_mul(4), "offsets")?;
+
+    Ok(offset_data
+        .chunks_exact(4)
+        .map(LittleEndian::read_u32)
+        .collect())
+}
 
-    // Handles the case where the string is > 32767 characters
-    if is_high_bit_set_16(len) {
-        unimplemented!()
+///Decodes the styles following the string offsets in `string_pool_data`,
+///the contents of the chunk after its header.
+fn parse_styles(
+    string_pool_data: &[u8],
+    header: &StringPoolHeader,
+    string_count: usize,
+) -> Result<Vec<Vec<StringPoolSpan>>, ParseError> {
+    let style_count = usize::try_from(header.style_count)
+        .map_err(|_| ParseError::SizeMismatch("style offsets"))?;
+    if style_count == 0 {
+        return Ok(Vec::new());
     }
 
-    // This needs to change if we ever implement support for long strings
-    let string_start = offset + 2;
+    let offsets = parse_offsets(
+        string_pool_data,
+        string_count
+            .checked_mul(4)
+            .ok_or(ParseError::SizeMismatch("style offsets"))?,
+        style_count,
+    )?;
+    let style_data = usize::try_from(header.style_start)
+        .ok()
+        .and_then(|start| start.checked_sub(STRING_POOL_HEADER_SIZE))
+        .and_then(|start| string_pool_data.get(start..))
+        .ok_or(ParseError::SizeMismatch("style data"))?;
+
+    let mut styles = Vec::with_capacity(offsets.len());
+    for offset in offsets {
+        let mut offset =
+            usize::try_from(offset).map_err(|_| ParseError::SizeMismatch("style offsets"))?;
+        let mut spans = Vec::new();
+        loop {
+            let name = LittleEndian::read_u32(get_bytes(style_data, offset, Some(4), "style")?);
+            if name == SPAN_END {
+                break;
+            }
+
+            let range = get_bytes(style_data, offset + 4, Some(8), "style")?;
+            spans.push(StringPoolSpan {
+                name,
+                first_char: LittleEndian::read_u32(&range[..4]),
+                last_char: LittleEndian::read_u32(&range[4..]),
+            });
+            offset += 12;
+        }
+        styles.push(spans);
+    }
+
+    Ok(styles)
+}
+
+///Returns `len` bytes of `data` starting at `start`, failing instead of
+///panicking when the range falls outside of the buffer. `structure` names
+///what the bytes hold, for the error.
+fn get_bytes<'a>(
+    data: &'a [u8],
+    start: usize,
+    len: Option<usize>,
+    structure: &'static str,
+) -> Result<&'a [u8], ParseError> {
+    len.and_then(|len| start.checked_add(len))
+        .and_then(|end| data.get(start..end))
+        .ok_or(ParseError::SizeMismatch(structure))
+}
+
+///Encodes `s` as UTF-8, prefixed with both its UTF-16 and UTF-8 lengths.
+fn write_utf8_string<F: Write + Seek>(output: &mut F, s: &str) -> Result<usize, std::io::Error> {
+    let mut n = write_utf8_length(output, s.encode_utf16().count())?;
+    n += write_utf8_length(output, s.len())?;
+    output.write_all(s.as_bytes())?;
+    n += s.len();
+    n += write_u8(output, 0)?;
+    Ok(n)
+}
+
+///Returns whether both lengths of `s` fit in a UTF-8 pool length prefix.
+fn fits_utf8_pool(s: &str) -> bool {
+    s.len() <= MAX_UTF8_POOL_LENGTH && s.encode_utf16().count() <= MAX_UTF8_POOL_LENGTH
+}
+
+///Encodes a UTF-8 pool length prefix, the inverse of [parse_utf8_length].
+fn write_utf8_length<F: Write + Seek>(output: &mut F, len: usize) -> Result<usize, std::io::Error> {
+    if len > MAX_UTF8_POOL_LENGTH {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "string too long for a UTF-8 string pool",
+        ))
+    } else if len > 0x7f {
+        let n = write_u8(output, 0x80 | (len >> 8) as u8)?;
+        Ok(n + write_u8(output, len as u8)?)
+    } else {
+        write_u8(output, len as u8)
+    }
+}
 
-    let mut s = Vec::with_capacity(len.into());
-    for i in 0..len {
-        let index = string_start + usize::try_from(i * 2).unwrap();
-        let char = LittleEndian::read_u16(&string_data[index..index + 2]);
-        s.push(char);
+///Decodes the UTF-16 string at `offset`. If `lossy` is set, invalid code
+///units are replaced instead of causing an error.
+fn parse_utf16_string(
+    string_data: &[u8],
+    offset: usize,
+    lossy: bool,
+) -> Result<String, ParseError> {
+    let len = LittleEndian::read_u16(get_bytes(string_data, offset, Some(2), "string")?);
+    let mut string_start = offset + 2;
+
+    // Strings longer than 32767 characters store their length in two u16s,
+    // with the high bit of the first one set.
+    let len = if is_high_bit_set_16(len) {
+        let low = LittleEndian::read_u16(get_bytes(string_data, string_start, Some(2), "string")?);
+        string_start += 2;
+        (usize::from(len & 0x7fff) << 16) | usize::from(low)
+    } else {
+        usize::from(len)
+    };
+
+    let s: Vec<u16> = get_bytes(string_data, string_start, len.checked_mul(2), "string")?
+        .chunks_exact(2)
+        .map(LittleEndian::read_u16)
+        .collect();
+
+    if lossy {
+        return Ok(String::from_utf16_lossy(&s));
     }
 
     let s = String::from_utf16(&s).map_err(ParseError::Utf16StringParseError)?;
@@ -201,30 +620,175 @@ fn is_high_bit_set_16(input: u16) -> bool {
     input & (1 << 15) != 0
 }
 
-fn parse_utf8_string(string_data: &[u8], offset: usize) -> Result<String, ParseError> {
-    let len = string_data[offset + 1];
+///Decodes the UTF-8 string at `offset`. If `lossy` is set, invalid byte
+///sequences are replaced instead of causing an error.
+fn parse_utf8_string(string_data: &[u8], offset: usize, lossy: bool) -> Result<String, ParseError> {
+    // UTF-8 strings are prefixed by both their length in UTF-16 code units
+    // and their length in bytes. Only the latter is needed here.
+    let (_, string_start) = parse_utf8_length(string_data, offset)?;
+    let (len, string_start) = parse_utf8_length(string_data, string_start)?;
 
-    // Handles the case where the length value has high bit set
-    // Not quite clear if the UTF-8 encoding actually has this but
-    // perform the check anyway...
-    if is_high_bit_set_8(len) {
-        unimplemented!()
-    }
+    let s = get_bytes(string_data, string_start, Some(len), "string")?.to_vec();
 
-    // This needs to change if we ever implement support for long strings
-    let string_start = offset + 2;
-
-    let mut s = Vec::with_capacity(len.into());
-    for i in 0..len {
-        let index = string_start + usize::try_from(i).unwrap();
-        let char = string_data[index];
-        s.push(char);
+    if lossy {
+        return Ok(String::from_utf8_lossy(&s).into_owned());
     }
 
     let s = String::from_utf8(s).map_err(ParseError::Utf8StringParseError)?;
     Ok(s)
 }
 
+///Decodes a UTF-8 pool length prefix, returning the length and the offset
+///just past it. Lengths above 127 are stored in two bytes, with the high bit
+///of the first one set.
+fn parse_utf8_length(string_data: &[u8], offset: usize) -> Result<(usize, usize), ParseError> {
+    let len = get_bytes(string_data, offset, Some(1), "string")?[0];
+
+    if is_high_bit_set_8(len) {
+        let low = get_bytes(string_data, offset + 1, Some(1), "string")?[0];
+        Ok((
+            (usize::from(len & 0x7f) << 8) | usize::from(low),
+            offset + 2,
+        ))
+    } else {
+        Ok((usize::from(len), offset + 1))
+    }
+}
+
 fn is_high_bit_set_8(input: u8) -> bool {
     input & (1 << 7) != 0
 }
+
+#[test]
+fn test_parse_string_lengths() {
+    let utf16 = [0x00, 0x80, 0x01, 0x00, b'a', 0x00];
+    assert_eq!(parse_utf16_string(&utf16, 0, false).unwrap(), "a");
+
+    let mut utf8 = vec![0x81, 0x00, 0x81, 0x00];
+    utf8.extend([b'b'; 256]);
+    assert_eq!(parse_utf8_string(&utf8, 0, false).unwrap(), "b".repeat(256));
+
+    assert!(matches!(
+        parse_utf16_string(&[0x05, 0x00, b'a'], 0, false),
+        Err(ParseError::SizeMismatch("string"))
+    ));
+    assert!(matches!(
+        parse_utf8_string(&[0x05], 0, false),
+        Err(ParseError::SizeMismatch("string"))
+    ));
+}
+
+#[test]
+fn test_write_astral_strings() {
+    for utf8 in [false, true] {
+        let mut pool = ResStringPool::new(utf8);
+        let emoji = pool.push("App \u{1f680} \u{10348}é");
+        // Long enough to need a two-part UTF-16 length, which UTF-8 pools
+        // cannot represent.
+        let long = "\u{1f600}".repeat(if utf8 { 2_000 } else { 20_000 });
+        let long_index = pool.push(&long);
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        pool.write_to_file(&mut data).unwrap();
+        data.set_position(0);
+
+        let read = ResStringPool::read_from_file(&mut data).unwrap();
+        assert_eq!(read.get_string(emoji), Some("App \u{1f680} \u{10348}é"));
+        assert_eq!(read.get_string(long_index), Some(long.as_str()));
+    }
+
+    // Surrogate pairs count as two code units in the length.
+    let mut data = std::io::Cursor::new(Vec::new());
+    write_utf16_string(&mut data, "\u{1f680}").unwrap();
+    assert_eq!(
+        data.into_inner(),
+        [0x02, 0x00, 0x3d, 0xd8, 0x80, 0xde, 0x00, 0x00]
+    );
+}
+
+#[test]
+fn test_string_pool_rw() {
+    for utf8 in [false, true] {
+        let mut pool = ResStringPool::new(utf8);
+        let plain = pool.push("plain");
+        let long = pool.push(&"x".repeat(300));
+        let span = StringPoolSpan {
+            name: plain,
+            first_char: 1,
+            last_char: 3,
+        };
+        pool.set_style(long, vec![span]).unwrap();
+        assert!(matches!(
+            pool.set_style(2, Vec::new()),
+            Err(ParseError::StringNotFound(2))
+        ));
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        let n = pool.write_to_file(&mut data).unwrap();
+        assert_eq!(n, data.get_ref().len());
+        assert_eq!(n % 4, 0);
+        data.set_position(0);
+
+        let read = ResStringPool::read_from_file(&mut data).unwrap();
+        assert_eq!(read.is_utf8(), utf8);
+        assert_eq!(read.header.style_start % 4, 0);
+        assert_eq!(
+            read.get_strings().collect::<Vec<_>>(),
+            vec!["plain", &"x".repeat(300)]
+        );
+        assert_eq!(read.get_styles(), &[vec![], vec![span]]);
+    }
+}
+
+#[test]
+fn test_large_string_pool() {
+    let mut pool = ResStringPool::new(false);
+    for i in 0..10_000 {
+        pool.push(&format!("string {}", i));
+    }
+    let mut data = std::io::Cursor::new(Vec::new());
+    pool.write_to_file(&mut data).unwrap();
+
+    // Replace the first character of string 5000 with a lone surrogate.
+    let bytes = data.get_mut();
+    let string_start = LittleEndian::read_u32(&bytes[20..24]) as usize;
+    let offset = LittleEndian::read_u32(&bytes[28 + 4 * 5000..]) as usize;
+    LittleEndian::write_u16(&mut bytes[string_start + offset + 2..], 0xd800);
+
+    data.set_position(0);
+    assert!(matches!(
+        ResStringPool::read_from_file(&mut data),
+        Err(ParseError::Utf16StringParseError(_))
+    ));
+
+    data.set_position(0);
+    let header = ChunkHeader::read_from_file(&mut data, 0).unwrap();
+    let mut warnings = Vec::new();
+    let read =
+        ResStringPool::read_chunk(&mut data, &header, &ParseConfig::hardened(), &mut warnings)
+            .unwrap();
+    assert_eq!(read.len(), 10_000);
+    assert_eq!(read.get_string(4999), Some("string 4999"));
+    assert_eq!(read.get_string(5000), Some("\u{fffd}tring 5000"));
+    assert_eq!(read.get_string(9999), Some("string 9999"));
+    assert!(matches!(warnings[..], [Warning::SubstitutedString(5000)]));
+}
+
+#[test]
+fn test_long_utf8_pool_string() {
+    let long = "a".repeat(40_000);
+    let mut pool = ResStringPool::new(true);
+    pool.push("short");
+    pool.push(&long);
+
+    let mut data = std::io::Cursor::new(Vec::new());
+    pool.write_to_file(&mut data).unwrap();
+    data.set_position(0);
+    let read = ResStringPool::read_from_file(&mut data).unwrap();
+    assert!(!read.is_utf8());
+    assert_eq!(read.get_string(0), Some("short"));
+    assert_eq!(read.get_string(1), Some(long.as_str()));
+
+    let mut output = std::io::Cursor::new(Vec::new());
+    assert!(write_utf8_string(&mut output, &long).is_err());
+}
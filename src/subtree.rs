@@ -0,0 +1,216 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::node_id::NodeId;
+use crate::xml::{split_prefix, Element, Node, XmlDocument};
+
+///An element taken out of a document together with the namespaces that
+///were in scope for it, so that it can be added to another document with
+///[XmlDocument::adopt].
+#[derive(Debug, Clone)]
+pub struct Subtree {
+    element: Element,
+    ///Namespaces declared outside the element, as `(prefix, uri)` pairs.
+    namespaces: Vec<(String, String)>,
+}
+
+impl Subtree {
+    ///Wraps an element whose prefixes are bound by `namespaces`, given as
+    ///`(prefix, uri)` pairs.
+    pub fn new(element: Element, namespaces: Vec<(String, String)>) -> Self {
+        Self {
+            element,
+            namespaces,
+        }
+    }
+
+    ///Returns the element.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    ///Returns the namespaces in scope where the element was taken from, as
+    ///`(prefix, uri)` pairs.
+    pub fn namespaces(&self) -> &[(String, String)] {
+        &self.namespaces
+    }
+
+    ///Returns the element, dropping the namespaces in scope for it.
+    pub fn into_element(self) -> Element {
+        self.element
+    }
+}
+
+impl XmlDocument {
+    ///Copies the element with the given id, or returns `None` if there is
+    ///no element by that id.
+    pub fn subtree(&self, id: NodeId) -> Option<Subtree> {
+        let path = self.path_of(id)?;
+        match self.get(id)? {
+            Node::Element(e) => Some(Subtree::new(e.clone(), self.scope(&path))),
+            Node::Cdata(_) => None,
+        }
+    }
+
+    ///Removes the element with the given id from the document, or returns
+    ///`None` if there is no element by that id. Ids of the nodes following
+    ///it are no longer valid.
+    pub fn detach(&mut self, id: NodeId) -> Option<Subtree> {
+        let path = self.path_of(id)?;
+        if !matches!(self.get(id)?, Node::Element(_)) {
+            return None;
+        }
+        let namespaces = self.scope(&path);
+
+        let (last, ancestors) = path.split_last()?;
+        let mut siblings = self.roots_mut();
+        for &i in ancestors {
+            siblings = match &mut siblings[i] {
+                Node::Element(e) => e.get_children_mut(),
+                Node::Cdata(_) => return None,
+            };
+        }
+        match siblings.remove(*last) {
+            Node::Element(element) => Some(Subtree::new(element, namespaces)),
+            Node::Cdata(_) => None,
+        }
+    }
+
+    ///Prepares `subtree`, usually taken from another document, to be added
+    ///to this one.
+    ///
+    ///Prefixes the subtree uses are renamed to the ones this document
+    ///declares for the same namespace. Namespaces this document does not
+    ///declare yet are declared, under a new prefix if the original one is
+    ///taken.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, Node};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut source = parse(&mut std::fs::File::open(manifest_file).unwrap()).unwrap();
+    ///let mut target = parse(&mut std::fs::File::open(manifest_file).unwrap()).unwrap();
+    ///
+    ///let filter = source.find_first(|e| e.get_tag() == "intent-filter").unwrap();
+    ///let id = source.node_id(filter).unwrap();
+    ///let filter = target.adopt(source.detach(id).unwrap());
+    ///if let Some(Node::Element(root)) = target.get_root_mut() {
+    ///    root.get_children_mut().push(Node::Element(filter));
+    ///}
+    ///```
+    pub fn adopt(&mut self, subtree: Subtree) -> Element {
+        let Subtree {
+            mut element,
+            namespaces,
+        } = subtree;
+
+        let mut used = BTreeSet::new();
+        used_prefixes(&element, &[], &mut used);
+
+        let mut renames = HashMap::new();
+        for prefix in used {
+            let uri = match namespaces.iter().rev().find(|(p, _)| *p == prefix) {
+                Some((_, uri)) => uri,
+                None => continue,
+            };
+            if let Some((own, _)) = self.namespaces.iter().find(|(_, u)| u == uri) {
+                if *own != prefix {
+                    renames.insert(prefix, own.clone());
+                }
+            } else if self.namespaces.iter().any(|(p, _)| *p == prefix) {
+                let fresh = (0..)
+                    .map(|i| format!("ns{}", i))
+                    .find(|p| !self.namespaces.iter().any(|(own, _)| own == p))
+                    .expect("prefixes are unbounded");
+                self.declare_namespace(&fresh, uri);
+                renames.insert(prefix, fresh);
+            } else {
+                self.declare_namespace(&prefix, uri);
+            }
+        }
+
+        element.rename_prefixes(&renames);
+        element
+    }
+
+    ///Returns the namespaces in scope for the node at `path`, excluding the
+    ///ones the node declares itself.
+    fn scope(&self, path: &[usize]) -> Vec<(String, String)> {
+        let mut namespaces = self.namespaces.clone();
+        let mut nodes = self.roots();
+        for &i in &path[..path.len().saturating_sub(1)] {
+            let e = match &nodes[i] {
+                Node::Element(e) => e,
+                Node::Cdata(_) => break,
+            };
+            for (prefix, uri) in &e.namespace_declarations {
+                namespaces.retain(|(p, _)| p != prefix);
+                namespaces.push((prefix.clone(), uri.clone()));
+            }
+            nodes = e.get_children();
+        }
+        namespaces
+    }
+}
+
+///Collects the prefixes of tags and attribute names in the subtree of `e`
+///that are not declared within it.
+fn used_prefixes(e: &Element, declared: &[&str], used: &mut BTreeSet<String>) {
+    let mut declared = declared.to_vec();
+    declared.extend(e.namespace_declarations.iter().map(|(p, _)| p.as_str()));
+
+    let names = std::iter::once(e.get_tag()).chain(e.typed_attributes().map(|a| a.qualified_name));
+    for name in names {
+        if let (Some(prefix), _) = split_prefix(name) {
+            if !declared.contains(&prefix) {
+                used.insert(prefix.to_string());
+            }
+        }
+    }
+    for child in e.get_children() {
+        if let Node::Element(child) = child {
+            used_prefixes(child, &declared, used);
+        }
+    }
+}
+
+#[test]
+fn test_detach_and_adopt() {
+    let mut action = Element::new("action");
+    action.set_attribute("android:name", "android.intent.action.VIEW");
+    let mut filter = Element::new("intent-filter");
+    filter.set_attribute("tools:node", "merge");
+    filter.get_children_mut().push(Node::Element(action));
+    let mut activity = Element::new("activity");
+    activity.get_children_mut().push(Node::Element(filter));
+    let mut source = XmlDocument::new(Node::Element(activity));
+    source.declare_namespace("android", crate::ANDROID_NAMESPACE);
+    source.declare_namespace("tools", "http://schemas.android.com/tools");
+
+    let mut target = XmlDocument::new(Node::Element(Element::new("application")));
+    target.declare_namespace("a", crate::ANDROID_NAMESPACE);
+    target.declare_namespace("tools", "urn:other");
+
+    let filter = source
+        .find_first(|e| e.get_tag() == "intent-filter")
+        .unwrap();
+    let id = source.node_id(filter).unwrap();
+    assert!(source.subtree(id).is_some());
+    let subtree = source.detach(id).unwrap();
+    match source.get_root() {
+        Some(Node::Element(root)) => assert!(root.get_children().is_empty()),
+        _ => panic!("missing root element"),
+    }
+
+    let filter = target.adopt(subtree);
+    assert_eq!(filter.get_attribute("ns0:node"), Some("merge"));
+    match &filter.get_children()[0] {
+        Node::Element(action) => assert_eq!(
+            action.get_attribute("a:name"),
+            Some("android.intent.action.VIEW")
+        ),
+        Node::Cdata(_) => panic!("unexpected text"),
+    }
+    assert!(target.namespaces.contains(&(
+        "ns0".to_string(),
+        "http://schemas.android.com/tools".to_string()
+    )));
+}
@@ -0,0 +1,259 @@
+//!Names of the values of framework attributes that are enums or flags,
+//!available with the `symbols` feature.
+//!
+//!Attributes such as `android:launchMode` or `android:configChanges` are
+//!written as names in the source manifest but compiled to an integer. With
+//![FormatOptions::symbolic_values](crate::FormatOptions::symbolic_values)
+//!set, they are written by name again:
+//!
+//!```rust
+//!use axmldecoder::{symbols, Element, FormatOptions, Node, ResourceValue, XmlDocument};
+//!assert_eq!(symbols::enum_symbol("launchMode", 2), Some("singleTask"));
+//!
+//!let mut activity = Element::new("activity");
+//!activity.set_typed_attribute(None, "android:launchMode", ResourceValue::integer(2));
+//!let options = FormatOptions {
+//!    symbolic_values: true,
+//!    ..FormatOptions::default()
+//!};
+//!let xml = XmlDocument::new(Node::Element(activity)).to_xml_string(&options);
+//!assert!(xml.contains("android:launchMode=\"singleTask\""));
+//!```
+
+use crate::permissions::PROTECTION_LEVELS;
+use crate::resource_value::{format_flags, Flag, ResourceValue, ResourceValueType};
+use crate::xml::Attribute;
+
+///Values of enum attributes by attribute name, as declared in the
+///framework's `attrs_manifest.xml`.
+const ENUMS: &[(&str, &[(u32, &str)])] = &[
+    (
+        "documentLaunchMode",
+        &[
+            (0, "intoExisting"),
+            (1, "always"),
+            (2, "none"),
+            (3, "never"),
+        ],
+    ),
+    (
+        "gwpAsanMode",
+        &[(u32::MAX, "default"), (0, "never"), (1, "always")],
+    ),
+    (
+        "installLocation",
+        &[(0, "auto"), (1, "internalOnly"), (2, "preferExternal")],
+    ),
+    (
+        "launchMode",
+        &[
+            (0, "standard"),
+            (1, "singleTop"),
+            (2, "singleTask"),
+            (3, "singleInstance"),
+            (4, "singleInstancePerTask"),
+        ],
+    ),
+    (
+        "lockTaskMode",
+        &[
+            (0, "normal"),
+            (1, "never"),
+            (2, "always"),
+            (3, "if_whitelisted"),
+        ],
+    ),
+    (
+        "memtagMode",
+        &[(u32::MAX, "default"), (0, "off"), (1, "async"), (2, "sync")],
+    ),
+    (
+        "persistableMode",
+        &[
+            (0, "persistRootOnly"),
+            (1, "persistNever"),
+            (2, "persistAcrossReboots"),
+        ],
+    ),
+    (
+        "screenOrientation",
+        &[
+            (u32::MAX, "unspecified"),
+            (0, "landscape"),
+            (1, "portrait"),
+            (2, "user"),
+            (3, "behind"),
+            (4, "sensor"),
+            (5, "nosensor"),
+            (6, "sensorLandscape"),
+            (7, "sensorPortrait"),
+            (8, "reverseLandscape"),
+            (9, "reversePortrait"),
+            (10, "fullSensor"),
+            (11, "userLandscape"),
+            (12, "userPortrait"),
+            (13, "fullUser"),
+            (14, "locked"),
+        ],
+    ),
+    ("uiOptions", &[(0, "none"), (1, "splitActionBarWhenNarrow")]),
+];
+
+///Values of flag attributes by attribute name.
+const FLAGS: &[(&str, &[Flag])] = &[
+    (
+        "configChanges",
+        &[
+            (0x0001, 0x0001, "mcc"),
+            (0x0002, 0x0002, "mnc"),
+            (0x0004, 0x0004, "locale"),
+            (0x0008, 0x0008, "touchscreen"),
+            (0x0010, 0x0010, "keyboard"),
+            (0x0020, 0x0020, "keyboardHidden"),
+            (0x0040, 0x0040, "navigation"),
+            (0x0080, 0x0080, "orientation"),
+            (0x0100, 0x0100, "screenLayout"),
+            (0x0200, 0x0200, "uiMode"),
+            (0x0400, 0x0400, "screenSize"),
+            (0x0800, 0x0800, "smallestScreenSize"),
+            (0x1000, 0x1000, "density"),
+            (0x2000, 0x2000, "layoutDirection"),
+            (0x4000, 0x4000, "colorMode"),
+            (0x8000, 0x8000, "grammaticalGender"),
+            (0x1000_0000, 0x1000_0000, "fontWeightAdjustment"),
+            (0x4000_0000, 0x4000_0000, "fontScale"),
+        ],
+    ),
+    (
+        "foregroundServiceType",
+        &[
+            (0x0001, 0x0001, "dataSync"),
+            (0x0002, 0x0002, "mediaPlayback"),
+            (0x0004, 0x0004, "phoneCall"),
+            (0x0008, 0x0008, "location"),
+            (0x0010, 0x0010, "connectedDevice"),
+            (0x0020, 0x0020, "mediaProjection"),
+            (0x0040, 0x0040, "camera"),
+            (0x0080, 0x0080, "microphone"),
+            (0x0100, 0x0100, "health"),
+            (0x0200, 0x0200, "remoteMessaging"),
+            (0x0400, 0x0400, "systemExempted"),
+            (0x0800, 0x0800, "shortService"),
+            (0x2000, 0x2000, "mediaProcessing"),
+            (0x4000_0000, 0x4000_0000, "specialUse"),
+        ],
+    ),
+    ("protectionLevel", PROTECTION_LEVELS),
+    (
+        "windowSoftInputMode",
+        &[
+            (0x0f, 0, "stateUnspecified"),
+            (0x0f, 1, "stateUnchanged"),
+            (0x0f, 2, "stateHidden"),
+            (0x0f, 3, "stateAlwaysHidden"),
+            (0x0f, 4, "stateVisible"),
+            (0x0f, 5, "stateAlwaysVisible"),
+            (0xf0, 0x00, "adjustUnspecified"),
+            (0xf0, 0x10, "adjustResize"),
+            (0xf0, 0x20, "adjustPan"),
+            (0xf0, 0x30, "adjustNothing"),
+        ],
+    ),
+];
+
+///Returns the name of `value` for the framework attribute `attribute`,
+///given without the `android:` prefix, or `None` if the attribute is not an
+///enum or has no value by that number.
+pub fn enum_symbol(attribute: &str, value: u32) -> Option<&'static str> {
+    let (_, values) = ENUMS.iter().find(|(name, _)| *name == attribute)?;
+    values
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map(|(_, symbol)| *symbol)
+}
+
+///Returns `value` as the names of its flags joined by `|`, such as
+///`keyboard|orientation|screenSize`, for the framework flag attribute
+///`attribute` given without the `android:` prefix.
+///
+///Returns `None` if the attribute is not a flag attribute or `value` has
+///bits without a name. Zero is written as the name for it, if there is one.
+pub fn flag_symbols(attribute: &str, value: u32) -> Option<String> {
+    let (_, flags) = FLAGS.iter().find(|(name, _)| *name == attribute)?;
+    format_flags(flags, value)
+}
+
+impl ResourceValue {
+    ///Returns the value by name if it is an integer value of the framework
+    ///enum or flag attribute `attribute`, given without the `android:`
+    ///prefix. The number itself is still available as
+    ///[ResourceValue::data].
+    ///
+    ///```rust
+    ///use axmldecoder::ResourceValue;
+    ///let value = ResourceValue::integer(0x4a0);
+    ///assert_eq!(
+    ///    value.symbolic("configChanges").as_deref(),
+    ///    Some("keyboardHidden|orientation|screenSize")
+    ///);
+    ///assert_eq!(value.data(), 0x4a0);
+    ///```
+    pub fn symbolic(&self, attribute: &str) -> Option<String> {
+        match self.data_type {
+            ResourceValueType::Dec | ResourceValueType::Hex => enum_symbol(attribute, self.data)
+                .map(str::to_string)
+                .or_else(|| flag_symbols(attribute, self.data)),
+            _ => None,
+        }
+    }
+}
+
+///Returns the symbolic form of an attribute, if it is a framework enum or
+///flag attribute stored as an integer.
+pub(crate) fn symbolic_value(attr: &Attribute) -> Option<String> {
+    if !attr.is_android() {
+        return None;
+    }
+    attr.typed_value.symbolic(&attr.name)
+}
+
+#[test]
+fn test_enum_symbol() {
+    assert_eq!(enum_symbol("launchMode", 2), Some("singleTask"));
+    assert_eq!(
+        enum_symbol("screenOrientation", u32::MAX),
+        Some("unspecified")
+    );
+    assert_eq!(enum_symbol("installLocation", 7), None);
+    assert_eq!(enum_symbol("versionCode", 1), None);
+}
+
+#[test]
+fn test_flag_symbols() {
+    assert_eq!(
+        flag_symbols("configChanges", 0x0490).as_deref(),
+        Some("keyboard|orientation|screenSize")
+    );
+    assert_eq!(
+        flag_symbols("windowSoftInputMode", 0x12).as_deref(),
+        Some("stateHidden|adjustResize")
+    );
+    assert_eq!(
+        flag_symbols("windowSoftInputMode", 0x30).as_deref(),
+        Some("adjustNothing")
+    );
+    assert_eq!(
+        flag_symbols("windowSoftInputMode", 0).as_deref(),
+        Some("stateUnspecified")
+    );
+    assert_eq!(
+        flag_symbols("protectionLevel", 0x12).as_deref(),
+        Some("signature|privileged")
+    );
+    assert_eq!(
+        flag_symbols("protectionLevel", 3).as_deref(),
+        Some("signatureOrSystem")
+    );
+    assert_eq!(flag_symbols("configChanges", 0x0020_0000), None);
+    assert_eq!(flag_symbols("launchMode", 1), None);
+}
@@ -0,0 +1,194 @@
+//!Helpers for property testing code that encodes documents, available with
+//!the `test-support` feature.
+//!
+//![arbitrary_document] builds a random document that the encoder can
+//!represent, and [assert_roundtrip] checks that encoding and parsing it again
+//!gives back the same document. Together they test that parsing undoes
+//!encoding, either from a seed:
+//!
+//!```rust
+//!use axmldecoder::test_support::{assert_roundtrip, random_document};
+//!
+//!for seed in 0..16 {
+//!    assert_roundtrip(&random_document(seed));
+//!}
+//!```
+//!
+//!or from the input of a fuzzer, through [arbitrary::Unstructured].
+
+use arbitrary::{Arbitrary, Unstructured};
+use std::io::Cursor;
+
+use crate::verify::first_difference;
+use crate::xml::ANDROID_NAMESPACE;
+use crate::{encode_to_vec, parse, Cdata, Element, Node, ResourceValue, XmlDocument};
+
+const TAGS: &[&str] = &[
+    "application",
+    "activity",
+    "service",
+    "meta-data",
+    "intent-filter",
+    "action",
+    "category",
+    "uses-permission",
+];
+
+///Framework attributes, which are encoded with their resource id.
+const ANDROID_ATTRIBUTES: &[&str] = &[
+    "name",
+    "label",
+    "icon",
+    "exported",
+    "enabled",
+    "versionCode",
+    "minSdkVersion",
+];
+
+const PLAIN_ATTRIBUTES: &[&str] = &["package", "key", "value", "tag"];
+
+const MAX_DEPTH: usize = 4;
+const MAX_CHILDREN: usize = 4;
+const MAX_ATTRIBUTES: usize = 6;
+
+///Builds a random `manifest` document from `u`. Elements get a mix of
+///framework and plain attributes of every type [Element::set_attribute] and
+///[Element::set_typed_attribute] can produce, and text between them.
+pub fn arbitrary_document(u: &mut Unstructured<'_>) -> arbitrary::Result<XmlDocument> {
+    let root = arbitrary_element(u, "manifest", 1)?;
+    let mut document = XmlDocument::new(Node::Element(root));
+    document.declare_namespace("android", ANDROID_NAMESPACE);
+    Ok(document)
+}
+
+///Builds a random document from `seed`, for property tests that do not run
+///under a fuzzer. The same seed always gives the same document.
+pub fn random_document(seed: u64) -> XmlDocument {
+    // xorshift64*, which is plenty for picking test inputs.
+    let mut state = seed ^ 0x9e37_79b9_7f4a_7c15;
+    let data: Vec<u8> = (0..4096)
+        .map(|_| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 56) as u8
+        })
+        .collect();
+    arbitrary_document(&mut Unstructured::new(&data))
+        .expect("generating a document never runs out of data")
+}
+
+fn arbitrary_element(
+    u: &mut Unstructured<'_>,
+    tag: &str,
+    depth: usize,
+) -> arbitrary::Result<Element> {
+    let mut element = Element::new(tag);
+
+    for _ in 0..u.int_in_range(0..=MAX_ATTRIBUTES)? {
+        if bool::arbitrary(u)? {
+            let name = format!("android:{}", u.choose(ANDROID_ATTRIBUTES)?);
+            set_arbitrary_value(u, &mut element, &name)?;
+        } else {
+            let name = *u.choose(PLAIN_ATTRIBUTES)?;
+            set_arbitrary_value(u, &mut element, name)?;
+        }
+    }
+
+    if depth < MAX_DEPTH {
+        let mut last_was_text = false;
+        for _ in 0..u.int_in_range(0..=MAX_CHILDREN)? {
+            // Adjacent text would be a single node once written as XML.
+            if !last_was_text && u.ratio(1, 4)? {
+                let text = format!("t{}", String::arbitrary(u)?);
+                element
+                    .get_children_mut()
+                    .push(Node::Cdata(Cdata::new(&text)));
+                last_was_text = true;
+            } else {
+                let tag = *u.choose(TAGS)?;
+                let child = arbitrary_element(u, tag, depth + 1)?;
+                element.get_children_mut().push(Node::Element(child));
+                last_was_text = false;
+            }
+        }
+    }
+
+    Ok(element)
+}
+
+fn set_arbitrary_value(
+    u: &mut Unstructured<'_>,
+    element: &mut Element,
+    name: &str,
+) -> arbitrary::Result<()> {
+    let value = match u.int_in_range(0..=4)? {
+        0 => ResourceValue::boolean(bool::arbitrary(u)?),
+        1 => ResourceValue::integer(i32::arbitrary(u)?),
+        2 => ResourceValue::reference(0x7f00_0000 | u32::from(u16::arbitrary(u)?)),
+        3 => ResourceValue::color(u32::arbitrary(u)?),
+        _ => {
+            // The prefix keeps the string from being inferred as another
+            // type.
+            element.set_attribute(name, &format!("s{}", String::arbitrary(u)?));
+            return Ok(());
+        }
+    };
+    element.set_typed_attribute(None, name, value);
+    Ok(())
+}
+
+///Encodes `document`, parses the result and panics unless it has the same
+///structure as `document`, or if encoding it again gives different bytes.
+#[track_caller]
+pub fn assert_roundtrip(document: &XmlDocument) {
+    let encoded = encode_to_vec(document).expect("failed to encode the document");
+    let reparsed = match parse(&mut Cursor::new(&encoded)) {
+        Ok(reparsed) => reparsed,
+        Err(e) => panic!("encoded document cannot be parsed: {}", e),
+    };
+    if let Some((path, reason)) = first_difference(document, &reparsed) {
+        panic!(
+            "document differs after a round trip at {}: {}",
+            path, reason
+        );
+    }
+
+    let reencoded = encode_to_vec(&reparsed).expect("failed to encode the parsed document");
+    assert!(
+        reencoded == encoded,
+        "encoding the parsed document gives different bytes"
+    );
+}
+
+///Builds an element with `attributes`, set with [Element::set_attribute],
+///and child `elements`, for the crate's own tests.
+#[cfg(test)]
+pub(crate) fn element(tag: &str, attributes: &[(&str, &str)], elements: Vec<Element>) -> Element {
+    let mut e = Element::new(tag);
+    for (name, value) in attributes {
+        e.set_attribute(name, value);
+    }
+    e.get_children_mut()
+        .extend(elements.into_iter().map(Node::Element));
+    e
+}
+
+///Builds a document whose root is a `manifest` element for the package
+///`com.example` with the given children, and which declares the `android`
+///namespace.
+#[cfg(test)]
+pub(crate) fn manifest_document(elements: Vec<Element>) -> XmlDocument {
+    let root = element("manifest", &[("package", "com.example")], elements);
+    let mut document = XmlDocument::new(Node::Element(root));
+    document.declare_namespace("android", ANDROID_NAMESPACE);
+    document
+}
+
+#[test]
+fn test_random_roundtrips() {
+    for seed in 0..256 {
+        assert_roundtrip(&random_document(seed));
+    }
+    assert_roundtrip(&arbitrary_document(&mut Unstructured::new(&[])).unwrap());
+}
@@ -0,0 +1,346 @@
+//!Fluent assertions about manifests, for tests and CI checks of apps.
+//!
+//![assert_manifest] starts a chain of checks that panic with the path and
+//!line number of the offending element when they fail:
+//!
+//!```rust
+//!use axmldecoder::testing::assert_manifest;
+//!use axmldecoder::Manifest;
+//!
+//!let xml = Manifest::minimal("com.example", 21, 34);
+//!assert_manifest(&xml)
+//!    .has_package("com.example")
+//!    .lacks_permission("android.permission.CAMERA")
+//!    .activity(".MainActivity")
+//!    .is_exported()
+//!    .handles_action("android.intent.action.MAIN");
+//!```
+//!
+//!For property testing of code built on the encoder, see the
+//!`test_support` module of the `test-support` feature instead.
+
+use crate::aliases::intent_filters;
+use crate::path::child_paths;
+use crate::security::implicitly_exported;
+use crate::{ComponentKind, Element, Manifest, Node, XmlDocument};
+
+///Starts a chain of assertions about the manifest `document`.
+///
+///# Panics
+///
+///If the root of `document` is not a `manifest` element.
+#[track_caller]
+pub fn assert_manifest(document: &XmlDocument) -> ManifestAssert<'_> {
+    match document.get_root() {
+        Some(Node::Element(root)) => match Manifest::new(root) {
+            Some(manifest) => ManifestAssert { manifest },
+            None => panic!(
+                "expected a manifest, but the root element is {}",
+                location(root.get_tag(), root)
+            ),
+        },
+        _ => panic!("expected a manifest, but the document has no root element"),
+    }
+}
+
+///Assertions about a manifest, started by [assert_manifest]. Each one
+///panics if it fails and returns the manifest otherwise, so they can be
+///chained.
+#[derive(Debug, Clone, Copy)]
+pub struct ManifestAssert<'a> {
+    manifest: Manifest<'a>,
+}
+
+impl<'a> ManifestAssert<'a> {
+    ///Checks the package name.
+    #[track_caller]
+    pub fn has_package(self, package: &str) -> Self {
+        let root = self.manifest.root();
+        assert!(
+            self.manifest.package() == Some(package),
+            "expected package {} at {}, found {:?}",
+            package,
+            location("manifest", root),
+            self.manifest.package()
+        );
+        self
+    }
+
+    ///Checks that `permission` is requested by a `uses-permission` or
+    ///`uses-permission-sdk-23` element.
+    #[track_caller]
+    pub fn has_permission(self, permission: &str) -> Self {
+        if self.permission(permission).is_none() {
+            panic!(
+                "expected a uses-permission for {} at {}, the requested permissions are {:?}",
+                permission,
+                location("manifest", self.manifest.root()),
+                self.requested_permissions()
+            );
+        }
+        self
+    }
+
+    ///Checks that `permission` is not requested.
+    #[track_caller]
+    pub fn lacks_permission(self, permission: &str) -> Self {
+        if let Some((path, element)) = self.permission(permission) {
+            panic!(
+                "expected no uses-permission for {}, found one at {}",
+                permission,
+                location(&path, element)
+            );
+        }
+        self
+    }
+
+    ///Checks the minimum SDK version set on the `uses-sdk` element.
+    #[track_caller]
+    pub fn has_min_sdk(self, min_sdk: u32) -> Self {
+        assert!(
+            self.manifest.min_sdk_version() == Some(min_sdk),
+            "expected minSdkVersion {}, found {:?}",
+            min_sdk,
+            self.manifest.min_sdk_version()
+        );
+        self
+    }
+
+    ///Checks the target SDK version, which defaults to the minimum one.
+    #[track_caller]
+    pub fn has_target_sdk(self, target_sdk: u32) -> Self {
+        assert!(
+            self.manifest.target_sdk_version() == Some(target_sdk),
+            "expected targetSdkVersion {}, found {:?}",
+            target_sdk,
+            self.manifest.target_sdk_version()
+        );
+        self
+    }
+
+    ///Checks that the application has an activity named `name`, which may
+    ///be relative to the package, and continues with assertions about it.
+    #[track_caller]
+    pub fn activity(self, name: &str) -> ComponentAssert<'a> {
+        self.component(ComponentKind::Activity, name)
+    }
+
+    ///Like [ManifestAssert::activity], for a service.
+    #[track_caller]
+    pub fn service(self, name: &str) -> ComponentAssert<'a> {
+        self.component(ComponentKind::Service, name)
+    }
+
+    ///Like [ManifestAssert::activity], for a broadcast receiver.
+    #[track_caller]
+    pub fn receiver(self, name: &str) -> ComponentAssert<'a> {
+        self.component(ComponentKind::Receiver, name)
+    }
+
+    ///Like [ManifestAssert::activity], for a content provider.
+    #[track_caller]
+    pub fn provider(self, name: &str) -> ComponentAssert<'a> {
+        self.component(ComponentKind::Provider, name)
+    }
+
+    #[track_caller]
+    fn component(self, kind: ComponentKind, name: &str) -> ComponentAssert<'a> {
+        let class = self.manifest.resolve_class_name(name);
+        let components = self.components(kind);
+        match components.iter().find(|(_, _, c)| *c == class) {
+            Some((path, element, _)) => ComponentAssert {
+                manifest: self,
+                element,
+                path: path.clone(),
+                name: class,
+            },
+            None => panic!(
+                "expected {} {}, the declared ones are {:?}",
+                kind.tag(),
+                class,
+                components.iter().map(|(_, _, c)| c).collect::<Vec<_>>()
+            ),
+        }
+    }
+
+    ///Returns the components of `kind` with their paths and class names.
+    fn components(&self, kind: ComponentKind) -> Vec<(String, &'a Element, String)> {
+        child_paths(self.manifest.root(), "manifest")
+            .into_iter()
+            .filter(|(_, e)| e.get_tag() == "application")
+            .flat_map(|(path, application)| child_paths(application, &path))
+            .filter(|(_, e)| e.get_tag() == kind.tag())
+            .map(|(path, e)| {
+                let class = self
+                    .manifest
+                    .resolve_class_name(e.android_value("name").unwrap_or_default());
+                (path, e, class)
+            })
+            .collect()
+    }
+
+    fn uses_permissions(&self) -> Vec<(String, &'a Element)> {
+        child_paths(self.manifest.root(), "manifest")
+            .into_iter()
+            .filter(|(_, e)| matches!(e.get_tag(), "uses-permission" | "uses-permission-sdk-23"))
+            .collect()
+    }
+
+    fn permission(&self, permission: &str) -> Option<(String, &'a Element)> {
+        self.uses_permissions()
+            .into_iter()
+            .find(|(_, e)| e.android_value("name") == Some(permission))
+    }
+
+    fn requested_permissions(&self) -> Vec<&'a str> {
+        self.uses_permissions()
+            .into_iter()
+            .filter_map(|(_, e)| e.android_value("name"))
+            .collect()
+    }
+}
+
+///Assertions about an application component, started by
+///[ManifestAssert::activity] and its siblings.
+#[derive(Debug, Clone)]
+pub struct ComponentAssert<'a> {
+    manifest: ManifestAssert<'a>,
+    element: &'a Element,
+    path: String,
+    name: String,
+}
+
+impl<'a> ComponentAssert<'a> {
+    ///Checks that the component can be started by other apps, either
+    ///through `android:exported` or by default at the target SDK version.
+    #[track_caller]
+    pub fn is_exported(self) -> Self {
+        assert!(
+            self.exported(),
+            "expected {} at {} to be exported",
+            self.name,
+            self.location()
+        );
+        self
+    }
+
+    ///Checks that the component cannot be started by other apps.
+    #[track_caller]
+    pub fn is_not_exported(self) -> Self {
+        assert!(
+            !self.exported(),
+            "expected {} at {} not to be exported",
+            self.name,
+            self.location()
+        );
+        self
+    }
+
+    ///Checks that the framework attribute `android:<name>` has the
+    ///formatted value `value`.
+    #[track_caller]
+    pub fn has_attribute(self, name: &str, value: &str) -> Self {
+        let found = self.element.android_value(name);
+        assert!(
+            found == Some(value),
+            "expected android:{}=\"{}\" on {} at {}, found {:?}",
+            name,
+            value,
+            self.name,
+            self.location(),
+            found
+        );
+        self
+    }
+
+    ///Checks that starting or binding to the component requires
+    ///`permission`, set on the component itself.
+    #[track_caller]
+    pub fn requires_permission(self, permission: &str) -> Self {
+        let found = self.element.android_value("permission");
+        assert!(
+            found == Some(permission),
+            "expected {} at {} to require {}, found {:?}",
+            self.name,
+            self.location(),
+            permission,
+            found
+        );
+        self
+    }
+
+    ///Checks that an intent filter of the component lists `action`.
+    #[track_caller]
+    pub fn handles_action(self, action: &str) -> Self {
+        let filters = intent_filters(self.element);
+        assert!(
+            filters
+                .iter()
+                .any(|f| f.actions.iter().any(|a| a == action)),
+            "expected an intent filter for {} on {} at {}, the handled actions are {:?}",
+            action,
+            self.name,
+            self.location(),
+            filters.iter().flat_map(|f| &f.actions).collect::<Vec<_>>()
+        );
+        self
+    }
+
+    ///Returns to assertions about the whole manifest.
+    pub fn and(self) -> ManifestAssert<'a> {
+        self.manifest
+    }
+
+    fn exported(&self) -> bool {
+        let target_sdk = self.manifest.manifest.target_sdk_version().unwrap_or(1);
+        self.element.android_bool("exported") == Some(true)
+            || implicitly_exported(self.element, target_sdk)
+    }
+
+    fn location(&self) -> String {
+        location(&self.path, self.element)
+    }
+}
+
+///Formats where an element is, for failure messages.
+fn location(path: &str, element: &Element) -> String {
+    if element.line_number == 0 {
+        path.to_string()
+    } else {
+        format!("{} (line {})", path, element.line_number)
+    }
+}
+
+#[test]
+fn test_assert_manifest() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    assert_manifest(&xml)
+        .has_package("org.t0t0.androguard.TC")
+        .lacks_permission("android.permission.INTERNET")
+        .activity("org.t0t0.androguard.TC.TCActivity")
+        .is_exported()
+        .handles_action("android.intent.action.MAIN");
+
+    let minimal = Manifest::minimal("com.example", 21, 34);
+    assert_manifest(&minimal)
+        .has_min_sdk(21)
+        .has_target_sdk(34)
+        .activity("com.example.MainActivity")
+        .has_attribute("name", ".MainActivity");
+
+    let failure = std::panic::catch_unwind(|| {
+        assert_manifest(&xml)
+            .activity(".TCActivity")
+            .is_not_exported();
+    })
+    .unwrap_err();
+    let message = failure.downcast_ref::<String>().unwrap();
+    assert!(
+        message.starts_with(
+            "expected org.t0t0.androguard.TC.TCActivity at manifest/application/activity (line "
+        ),
+        "{}",
+        message
+    );
+}
@@ -0,0 +1,111 @@
+use crate::{is_binary_xml, Cdata, Element, Node, ParseError, XmlDocument};
+
+///Parses either an Android binary XML or a textual XML document, as told
+///apart by [is_binary_xml], and returns the same [XmlDocument] for both.
+///
+///Debug builds and test APKs sometimes ship manifests as plain text, so
+///this is convenient for tools that take whatever is in the archive.
+///
+///```rust
+///use axmldecoder::parse_auto;
+///# use axmldecoder::ParseError;
+///# let manifest_file = "examples/AndroidManifest.xml";
+///let binary = std::fs::read(manifest_file).unwrap();
+///let text = r#"<manifest package="com.example" />"#;
+///assert_eq!(
+///    parse_auto(&binary)?.to_string(),
+///    axmldecoder::parse(&mut binary.as_slice())?.to_string()
+///);
+///parse_auto(text.as_bytes())?;
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_auto(data: &[u8]) -> Result<XmlDocument, ParseError> {
+    if is_binary_xml(data) {
+        crate::parse(&mut &data[..])
+    } else {
+        let text = String::from_utf8(data.to_vec()).map_err(ParseError::Utf8StringParseError)?;
+        parse_text(&text)
+    }
+}
+
+///Builds a document from textual XML. Values are stored with the types
+///[Element::set_attribute] infers for them, and text is trimmed, with
+///whitespace-only text dropped.
+///
+///```rust
+///use axmldecoder::parse_text;
+///# use axmldecoder::ParseError;
+///let xml = parse_text(
+///    r#"<manifest xmlns:android="http://schemas.android.com/apk/res/android"
+///    android:versionCode="1" />"#,
+///)?;
+///println!("{}", xml);
+///# Ok::<(), ParseError>(())
+///```
+pub fn parse_text(text: &str) -> Result<XmlDocument, ParseError> {
+    let text = roxmltree::Document::parse(text).map_err(ParseError::TextXmlError)?;
+    let mut document = XmlDocument::new(Node::Element(convert(&text, text.root_element())));
+    for node in text.descendants().filter(|n| n.is_element()) {
+        for namespace in node.namespaces() {
+            document.declare_namespace(namespace.name().unwrap_or(""), namespace.uri());
+        }
+    }
+
+    Ok(document)
+}
+
+fn qualified_name(node: roxmltree::Node, name: roxmltree::ExpandedName) -> String {
+    match name.namespace().and_then(|uri| node.lookup_prefix(uri)) {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name.name()),
+        _ => name.name().to_string(),
+    }
+}
+
+fn convert(document: &roxmltree::Document, node: roxmltree::Node) -> Element {
+    let mut element = Element::new(&qualified_name(node, node.tag_name()));
+    element.line_number = document.text_pos_at(node.range().start).row;
+    for attr in node.attributes() {
+        let name = match attr.namespace() {
+            Some(uri) => roxmltree::ExpandedName::from((uri, attr.name())),
+            None => roxmltree::ExpandedName::from(attr.name()),
+        };
+        element.set_attribute(&qualified_name(node, name), attr.value());
+    }
+
+    for child in node.children() {
+        if child.is_element() {
+            element
+                .get_children_mut()
+                .push(Node::Element(convert(document, child)));
+        } else if let Some(text) = child.text().map(str::trim).filter(|t| !t.is_empty()) {
+            element
+                .get_children_mut()
+                .push(Node::Cdata(Cdata::new(text)));
+        }
+    }
+    element
+}
+
+#[test]
+fn test_parse_auto() {
+    let binary = std::fs::read("examples/AndroidManifest.xml").unwrap();
+    let decoded = parse_auto(&binary).unwrap();
+
+    // Text written from a binary document reads back as the same tree.
+    let text = decoded.to_string();
+    assert!(!is_binary_xml(text.as_bytes()));
+    let reparsed = parse_auto(text.as_bytes()).unwrap();
+    let activity = reparsed.find_first(|e| e.get_tag() == "activity").unwrap();
+    let expected = decoded.find_first(|e| e.get_tag() == "activity").unwrap();
+    assert!(activity.attributes().eq(expected.attributes()));
+    assert!(activity.line_number > 1);
+
+    assert!(matches!(
+        parse_auto(b"<manifest>"),
+        Err(ParseError::TextXmlError(_))
+    ));
+    assert!(matches!(
+        parse_auto(b"\xff\xfe<"),
+        Err(ParseError::Utf8StringParseError(_))
+    ));
+}
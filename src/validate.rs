@@ -0,0 +1,218 @@
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::path::child_paths;
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::xml::split_prefix;
+use crate::{Element, Node, XmlDocument};
+
+///A problem found by [XmlDocument::validate].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub kind: ValidationKind,
+    ///Path of the offending element, such as
+    ///`manifest/application/activity[2]`, or an empty string for text
+    ///outside of any element.
+    pub path: String,
+    ///Line number of the offending element, or 0 if unknown.
+    pub line_number: u32,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}): {}",
+            self.path, self.line_number, self.kind
+        )
+    }
+}
+
+///The kinds of problems reported by [XmlDocument::validate].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationKind {
+    #[error("prefix {prefix} of {name} is not bound to a namespace")]
+    UndeclaredPrefix { name: String, prefix: String },
+
+    #[error("attribute name {0:?} is empty")]
+    EmptyAttributeName(String),
+
+    #[error("text outside of any element")]
+    TextOutsideElement,
+
+    #[error("{name} refers to {id:#010x}, which is not a resource id")]
+    ImplausibleReference { name: String, id: u32 },
+}
+
+impl XmlDocument {
+    ///Checks that the document is well-formed enough to be encoded and read
+    ///by Android, which is mostly of interest for documents built by hand:
+    ///
+    /// - every prefix of a tag or attribute name is bound to a namespace
+    ///   declared on the document or on an enclosing element,
+    /// - attribute names, and their local part, are not empty,
+    /// - text only appears inside elements,
+    /// - references and theme attributes name an id with a package and a
+    ///   type, as in `@0x7f010001`, or are `@null`.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, Node, XmlDocument};
+    ///let mut activity = Element::new("activity");
+    ///activity.set_attribute("android:name", ".Main");
+    ///let xml = XmlDocument::new(Node::Element(activity));
+    ///let issues = xml.validate();
+    ///assert_eq!(issues.len(), 1);
+    ///println!("{}", issues[0]);
+    ///```
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let prefixes: Vec<&str> = self.namespaces.iter().map(|(p, _)| p.as_str()).collect();
+        let mut issues = Vec::new();
+        for root in self.roots() {
+            match root {
+                Node::Element(e) => validate_element(e, e.get_tag(), &prefixes, &mut issues),
+                Node::Cdata(_) => issues.push(ValidationIssue {
+                    kind: ValidationKind::TextOutsideElement,
+                    path: String::new(),
+                    line_number: 0,
+                }),
+            }
+        }
+        issues
+    }
+}
+
+fn validate_element(
+    element: &Element,
+    path: &str,
+    prefixes: &[&str],
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut report = |kind| {
+        issues.push(ValidationIssue {
+            kind,
+            path: path.to_string(),
+            line_number: element.line_number,
+        })
+    };
+
+    let mut prefixes = prefixes.to_vec();
+    prefixes.extend(
+        element
+            .namespace_declarations
+            .iter()
+            .map(|(p, _)| p.as_str()),
+    );
+    let undeclared = |name: &str| match split_prefix(name) {
+        (Some(prefix), _) if prefix != "xml" && !prefixes.contains(&prefix) => {
+            Some(ValidationKind::UndeclaredPrefix {
+                name: name.to_string(),
+                prefix: prefix.to_string(),
+            })
+        }
+        _ => None,
+    };
+
+    if let Some(kind) = undeclared(element.get_tag()) {
+        report(kind);
+    }
+    for attr in &element.typed_attributes {
+        if split_prefix(&attr.qualified_name).1.is_empty() {
+            report(ValidationKind::EmptyAttributeName(
+                attr.qualified_name.clone(),
+            ));
+        } else if let Some(kind) = undeclared(&attr.qualified_name) {
+            report(kind);
+        }
+        if let Some(id) = implausible_reference(&attr.typed_value) {
+            report(ValidationKind::ImplausibleReference {
+                name: attr.qualified_name.clone(),
+                id,
+            });
+        }
+    }
+    for child in element.get_children() {
+        if let Node::Cdata(text) = child {
+            if let Some(id) = text.get_typed_value().and_then(implausible_reference) {
+                report(ValidationKind::ImplausibleReference {
+                    name: "text".to_string(),
+                    id,
+                });
+            }
+        }
+    }
+
+    for (child_path, child) in child_paths(element, path) {
+        validate_element(child, &child_path, &prefixes, issues);
+    }
+}
+
+///Returns the id a reference or theme attribute value refers to, if it
+///lacks the package or type a resource id always has. A reference to 0 is
+///`@null`.
+fn implausible_reference(value: &ResourceValue) -> Option<u32> {
+    let id = value.data;
+    let plausible = match value.data_type {
+        ResourceValueType::Reference if id == 0 => true,
+        ResourceValueType::Reference | ResourceValueType::Attribute => {
+            id >> 24 != 0 && (id >> 16) & 0xff != 0
+        }
+        _ => true,
+    };
+    if plausible {
+        None
+    } else {
+        Some(id)
+    }
+}
+
+#[test]
+fn test_validate() {
+    let mut f = std::fs::File::open("examples/AndroidManifest.xml").unwrap();
+    let xml = crate::parse(&mut f).unwrap();
+    assert_eq!(xml.validate(), []);
+
+    let mut activity = Element::new("activity");
+    activity.set_attribute("android:name", ".Main");
+    activity.set_attribute("tools:", "empty");
+    activity.set_typed_attribute(None, "android:theme", ResourceValue::reference(1));
+    let mut application = Element::new("application");
+    application.namespace_declarations =
+        vec![("android".to_string(), crate::ANDROID_NAMESPACE.to_string())];
+    application.get_children_mut().push(Node::Element(activity));
+    let mut manifest = Element::new("manifest");
+    manifest
+        .get_children_mut()
+        .push(Node::Element(Element::new("x:uses-feature")));
+    manifest.get_children_mut().push(Node::Element(application));
+    let xml = XmlDocument::new(Node::Element(manifest));
+
+    let kinds: Vec<_> = xml
+        .validate()
+        .into_iter()
+        .map(|issue| (issue.path, issue.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (
+                "manifest/x:uses-feature".to_string(),
+                ValidationKind::UndeclaredPrefix {
+                    name: "x:uses-feature".to_string(),
+                    prefix: "x".to_string()
+                }
+            ),
+            (
+                "manifest/application/activity".to_string(),
+                ValidationKind::EmptyAttributeName("tools:".to_string())
+            ),
+            (
+                "manifest/application/activity".to_string(),
+                ValidationKind::ImplausibleReference {
+                    name: "android:theme".to_string(),
+                    id: 1
+                }
+            ),
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+use crate::path::child_paths;
+use crate::{Element, Node, XmlDocument};
+
+///Where an encoded document first differs from the document it was encoded
+///from, as the path of the element and a description of the difference.
+pub(crate) type Divergence = (String, String);
+
+///Compares the structure of `found` with `expected`: tags, attributes with
+///their values and types, namespace declarations and children. Attribute
+///order, line numbers and comments are not compared, as the encoder does not
+///preserve them.
+pub(crate) fn first_difference(expected: &XmlDocument, found: &XmlDocument) -> Option<Divergence> {
+    match (expected.get_root(), found.get_root()) {
+        (Some(Node::Element(a)), Some(Node::Element(b))) => compare_elements(a, b, a.get_tag()),
+        (Some(Node::Cdata(a)), Some(Node::Cdata(b))) if a.get_data() == b.get_data() => None,
+        (None, None) => None,
+        _ => Some((String::new(), "root node differs".to_string())),
+    }
+}
+
+fn compare_elements(expected: &Element, found: &Element, path: &str) -> Option<Divergence> {
+    let difference = |reason: String| Some((path.to_string(), reason));
+
+    if expected.get_tag() != found.get_tag() {
+        return difference(format!(
+            "tag is {} instead of {}",
+            found.get_tag(),
+            expected.get_tag()
+        ));
+    }
+    if expected.namespace_declarations != found.namespace_declarations {
+        return difference("namespace declarations differ".to_string());
+    }
+
+    for attr in &expected.typed_attributes {
+        let other = found
+            .typed_attributes
+            .iter()
+            .find(|a| a.qualified_name == attr.qualified_name);
+        match other {
+            None => return difference(format!("attribute {} is missing", attr.qualified_name)),
+            Some(other) if other.value != attr.value => {
+                return difference(format!(
+                    "attribute {} is {:?} instead of {:?}",
+                    attr.qualified_name, other.value, attr.value
+                ))
+            }
+            Some(other) if other.typed_value.data_type != attr.typed_value.data_type => {
+                return difference(format!(
+                    "attribute {} is of type {:?} instead of {:?}",
+                    attr.qualified_name, other.typed_value.data_type, attr.typed_value.data_type
+                ))
+            }
+            Some(_) => (),
+        }
+    }
+    if let Some(extra) = found.typed_attributes.iter().find(|a| {
+        !expected
+            .typed_attributes
+            .iter()
+            .any(|b| b.qualified_name == a.qualified_name)
+    }) {
+        return difference(format!("unexpected attribute {}", extra.qualified_name));
+    }
+
+    let (a, b) = (expected.get_children(), found.get_children());
+    if a.len() != b.len() {
+        return difference(format!("{} children instead of {}", b.len(), a.len()));
+    }
+    for (i, (a, b)) in a.iter().zip(b).enumerate() {
+        match (a, b) {
+            (Node::Element(_), Node::Element(_)) => (),
+            (Node::Cdata(a), Node::Cdata(b)) if a.get_data() != b.get_data() => {
+                return difference(format!(
+                    "text {:?} instead of {:?}",
+                    b.get_data(),
+                    a.get_data()
+                ))
+            }
+            (Node::Cdata(_), Node::Cdata(_)) => (),
+            _ => return difference(format!("child {} is of a different kind", i + 1)),
+        }
+    }
+
+    // Both sides have the same kinds of children at this point, so their
+    // elements pair up.
+    let found_children = child_paths(found, path);
+    for ((child_path, a), (_, b)) in child_paths(expected, path).iter().zip(&found_children) {
+        if let Some(difference) = compare_elements(a, b, child_path) {
+            return Some(difference);
+        }
+    }
+
+    None
+}
+
+#[test]
+fn test_first_difference() {
+    let document = |label: &str| {
+        let mut activity = Element::new("activity");
+        activity.set_attribute("android:label", label);
+        let mut application = Element::new("application");
+        application
+            .get_children_mut()
+            .push(Node::Element(Element::new("activity")));
+        application.get_children_mut().push(Node::Element(activity));
+        let mut root = Element::new("manifest");
+        root.get_children_mut().push(Node::Element(application));
+        XmlDocument::new(Node::Element(root))
+    };
+
+    assert_eq!(first_difference(&document("a"), &document("a")), None);
+    assert_eq!(
+        first_difference(&document("a"), &document("b")),
+        Some((
+            "manifest/application/activity[2]".to_string(),
+            "attribute android:label is \"b\" instead of \"a\"".to_string()
+        ))
+    );
+}
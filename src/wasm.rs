@@ -0,0 +1,20 @@
+//!WebAssembly bindings, available with the `wasm` feature.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+///Parses an Android binary XML and returns the document as a plain
+///JavaScript object.
+///
+///The object has the same shape as the JSON serialization of
+///[XmlDocument](crate::XmlDocument): `{ root: node | null }`, where each
+///node is either `{ type: "element", tag, attributes, children }` or
+///`{ type: "cdata", data }`.
+#[wasm_bindgen]
+pub fn parse_bytes(data: &[u8]) -> Result<JsValue, JsError> {
+    let document = crate::parse(&mut std::io::Cursor::new(data))?;
+
+    document
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
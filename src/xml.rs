@@ -2,166 +2,813 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::rc::Rc;
 
-use crate::binaryxml::{XmlCdata, XmlElement, XmlStartElement, XmlStartNameSpace};
-use crate::stringpool::StringPool;
+use indexmap::IndexMap;
+
+use crate::binaryxml::{
+    PartialBinaryXmlDocument, XmlAttribute, XmlCdata, XmlElement, XmlEndElement, XmlNodeHeader,
+    XmlStartElement, XmlStartNameSpace,
+};
+use crate::config::{DuplicateAttributes, ParseConfig};
+use crate::diagnostics::{push_warning, Warning};
+use crate::metadata::DocumentMetadata;
+use crate::resource_value::{ResourceValue, ResourceValueType};
+use crate::stringpool::ResStringPool;
 use crate::ParseError;
 
 ///Struct representing a parsed XML document.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct XmlDocument {
-    root: Option<Node>,
+    ///The top-level nodes, normally only the root element.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "root", serialize_with = "serialize_first")
+    )]
+    roots: Vec<Node>,
+    ///Namespace declarations as `(prefix, uri)` pairs, in document order.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) namespaces: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) metadata: Option<DocumentMetadata>,
 }
 
 impl XmlDocument {
-    pub(crate) fn new(
+    pub(crate) fn from_binary(
         elements: Vec<XmlElement>,
-        string_pool: StringPool,
+        string_pool: ResStringPool,
         resource_map: Vec<u32>,
-    ) -> Result<Self, ParseError> {
-        let mut namespaces = HashMap::new();
-
-        let mut element_tracker: Vec<Element> = Vec::new();
+        config: &ParseConfig,
+    ) -> Result<(Self, Vec<Warning>), ParseError> {
+        let mut builder = TreeBuilder::new(&string_pool, &resource_map, config);
         for element in elements {
-            match element {
-                XmlElement::XmlStartNameSpace(e) => {
-                    let (uri, prefix) = Self::process_start_namespace(&e, &string_pool)?;
-                    namespaces.insert(uri.clone(), prefix.clone());
-                }
-                XmlElement::XmlEndNameSpace(_) => {}
-                XmlElement::XmlStartElement(e) => {
-                    element_tracker.push(Self::process_start_element(
-                        &e,
-                        &string_pool,
-                        &namespaces,
-                        &resource_map,
-                    )?);
-                }
-                XmlElement::XmlEndElement(_) => {
-                    let e = element_tracker.pop().unwrap();
+            builder.push(element)?;
+        }
 
-                    if element_tracker.is_empty() {
-                        return Ok(XmlDocument {
-                            root: Some(Node::Element(e)),
-                        });
-                    }
+        let (document, warnings) = builder.finish();
+        Ok((document, warnings))
+    }
 
-                    element_tracker
-                        .last_mut()
-                        .unwrap()
-                        .insert_children(Node::Element(e));
-                }
-                XmlElement::XmlCdata(e) => {
-                    let cdata = Self::process_cdata(&e, &string_pool)?;
-                    element_tracker
-                        .last_mut()
-                        .unwrap()
-                        .insert_children(Node::Cdata(cdata))
-                }
-            };
+    pub(crate) fn new_partial(
+        binaryxml: PartialBinaryXmlDocument,
+        config: &ParseConfig,
+    ) -> (Self, Option<ParseError>) {
+        let string_pool = match &binaryxml.string_pool {
+            Some(string_pool) => string_pool,
+            None => {
+                return (
+                    Self {
+                        roots: Vec::new(),
+                        namespaces: Vec::new(),
+                        metadata: Some(binaryxml.metadata),
+                    },
+                    Some(ParseError::MissingStringPoolChunk),
+                )
+            }
+        };
+        let resource_map = binaryxml.resource_map.as_deref().unwrap_or_default();
+
+        let mut builder = TreeBuilder::new(string_pool, resource_map, config);
+        let mut error = None;
+        for element in binaryxml.elements {
+            if let Err(e) = builder.push(element) {
+                error = Some(e);
+                break;
+            }
+        }
+
+        let (mut document, _) = builder.finish();
+        document.metadata = Some(binaryxml.metadata);
+        (document, error)
+    }
+
+    ///Returns the root [Element] of the XML document, the first of
+    ///[XmlDocument::roots].
+    pub fn get_root(&self) -> Option<&Node> {
+        self.roots.first()
+    }
+
+    ///Creates a document with the given root, for encoding.
+    pub fn new(root: Node) -> Self {
+        Self {
+            roots: vec![root],
+            namespaces: Vec::new(),
+            metadata: None,
         }
+    }
+
+    ///Returns a mutable reference to the root of the XML document.
+    pub fn get_root_mut(&mut self) -> Option<&mut Node> {
+        self.roots.first_mut()
+    }
+
+    ///Returns the top-level elements of the document. Well-formed documents
+    ///have exactly one, but malformed manifests Android still installs may
+    ///have stray elements after the root, each reported with a
+    ///[Warning::StrayRoot](crate::Warning::StrayRoot). Everything else,
+    ///such as formatting and encoding, only looks at the first.
+    pub fn roots(&self) -> &[Node] {
+        &self.roots
+    }
 
-        Ok(Self { root: None })
+    pub(crate) fn roots_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.roots
+    }
+
+    ///Returns every element with the given tag, including the root, in
+    ///document order.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///for activity in xml.get_elements_by_tag_name("activity") {
+    ///    println!("{:?}", activity.get_attribute("android:name"));
+    ///}
+    ///```
+    pub fn get_elements_by_tag_name(&self, tag: &str) -> Vec<&Element> {
+        let mut elements = Vec::new();
+        if let Some(Node::Element(root)) = self.get_root() {
+            if root.get_tag() == tag {
+                elements.push(root);
+            }
+            root.collect_elements_by_tag_name(tag, &mut elements);
+        }
+        elements
     }
 
-    ///Returns the root [Element] of the XML document.
-    pub fn get_root(&self) -> &Option<Node> {
-        &self.root
+    ///Returns the elements for which `predicate` returns true, including
+    ///the root, in document order. Elements are visited as the iterator is
+    ///advanced, so stopping early skips the rest of the document.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let exported = xml.find_all(|e| e.get_attribute("android:exported").is_some());
+    ///for e in exported {
+    ///    println!("{}", e.get_tag());
+    ///}
+    ///```
+    pub fn find_all<'a, P>(&'a self, mut predicate: P) -> impl Iterator<Item = &'a Element> + 'a
+    where
+        P: FnMut(&Element) -> bool + 'a,
+    {
+        self.elements().filter(move |e| predicate(e))
     }
 
-    fn process_cdata(e: &XmlCdata, string_pool: &StringPool) -> Result<Cdata, ParseError> {
+    ///Returns the first element in document order for which `predicate`
+    ///returns true.
+    ///
+    ///```rust
+    ///use axmldecoder::parse;
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let launcher = xml
+    ///    .find_first(|e| {
+    ///        e.get_attribute("android:name")
+    ///            .is_some_and(|name| name.ends_with(".LAUNCHER"))
+    ///    })
+    ///    .unwrap();
+    ///assert_eq!(launcher.get_tag(), "category");
+    ///```
+    pub fn find_first<P>(&self, mut predicate: P) -> Option<&Element>
+    where
+        P: FnMut(&Element) -> bool,
+    {
+        self.elements().find(|e| predicate(e))
+    }
+
+    fn elements(&self) -> Elements<'_> {
+        let stack = match self.get_root() {
+            Some(Node::Element(root)) => vec![root],
+            _ => Vec::new(),
+        };
+        Elements { stack }
+    }
+
+    ///Declares the namespace `uri` with the given `prefix` on the root
+    ///element. Prefixed tags and attribute names are resolved against these
+    ///declarations when the document is encoded.
+    pub fn declare_namespace(&mut self, prefix: &str, uri: &str) {
+        let declaration = (prefix.to_string(), uri.to_string());
+        if !self.namespaces.contains(&declaration) {
+            self.namespaces.push(declaration);
+        }
+    }
+
+    fn process_cdata(
+        e: &XmlCdata,
+        string_pool: &ResStringPool,
+        config: &ParseConfig,
+    ) -> Result<Cdata, ParseError> {
+        // Text is normally stored as a string, but compiled resources may
+        // store it as a typed value just like an attribute.
+        if !matches!(
+            e.typed_data.data_type,
+            ResourceValueType::Null | ResourceValueType::String
+        ) {
+            return Ok(Cdata {
+                data: e
+                    .typed_data
+                    .format(string_pool, config.float_format)?
+                    .to_string(),
+                typed_value: Some(e.typed_data.clone()),
+            });
+        }
+
         Ok(Cdata {
             data: string_pool
-                .get(usize::try_from(e.data).unwrap())
+                .get(e.data)
                 .ok_or(ParseError::StringNotFound(e.data))?
                 .to_string(),
+            typed_value: None,
         })
     }
 
     fn process_start_namespace(
         e: &XmlStartNameSpace,
-        string_pool: &StringPool,
+        string_pool: &ResStringPool,
     ) -> Result<(Rc<String>, Rc<String>), ParseError> {
         let uri = string_pool
-            .get(usize::try_from(e.uri).unwrap())
+            .get(e.uri)
             .ok_or(ParseError::StringNotFound(e.uri))?;
-        let prefix = string_pool
-            .get(usize::try_from(e.prefix).unwrap())
-            .ok_or(ParseError::StringNotFound(e.prefix))?;
+        // The default namespace may be declared without any prefix string.
+        let prefix = match e.prefix {
+            u32::MAX => Rc::new(String::new()),
+            i => string_pool.get(i).ok_or(ParseError::StringNotFound(i))?,
+        };
 
         Ok((uri, prefix))
     }
 
     fn process_start_element(
         e: &XmlStartElement,
-        string_pool: &StringPool,
+        string_pool: &ResStringPool,
         namespaces: &HashMap<Rc<String>, Rc<String>>,
         resource_map: &[u32],
+        config: &ParseConfig,
+        warnings: &mut Vec<Warning>,
     ) -> Result<Element, ParseError> {
-        let ns = string_pool.get(usize::try_from(e.attr_ext.ns).unwrap());
-        assert_eq!(ns, None);
-
         let name = string_pool
-            .get(usize::try_from(e.attr_ext.name).unwrap())
+            .get(e.attr_ext.name)
             .ok_or(ParseError::StringNotFound(e.attr_ext.name))?;
-        let name = (*name).clone();
 
-        let mut attributes: HashMap<String, String> = HashMap::new();
+        let mut tag = String::new();
+        if let Some(n) = string_pool.get(e.attr_ext.ns) {
+            let ns_prefix = namespaces
+                .get(&n)
+                .ok_or_else(|| ParseError::NamespaceNotFound(n.to_string()))?;
+            push_prefix(&mut tag, ns_prefix);
+        }
+        tag.push_str(&name);
+
+        let mut attributes: IndexMap<String, String> = IndexMap::new();
+        let mut typed_attributes = Vec::with_capacity(e.attributes.len());
         for attr in &e.attributes {
-            let ns = string_pool.get(usize::try_from(attr.ns).unwrap());
-            let name = string_pool
-                .get(usize::try_from(attr.name).unwrap())
-                .ok_or(ParseError::StringNotFound(attr.name))?;
-            let value = attr.typed_value.get_value(&string_pool);
-
-            let mut final_name = String::new();
-            if !name.is_empty() {
-                if let Some(n) = ns {
-                    let ns_prefix = namespaces
-                        .get(&n)
-                        .ok_or_else(|| ParseError::NamespaceNotFound(n.to_string()))?;
-                    final_name.push_str(ns_prefix);
-                    final_name.push(':');
+            match Self::process_attribute(attr, string_pool, namespaces, resource_map, config) {
+                Ok(attribute) => {
+                    if attributes.contains_key(&attribute.qualified_name) {
+                        let (element, name) = (tag.clone(), attribute.qualified_name.clone());
+                        match config.duplicate_attributes {
+                            DuplicateAttributes::Error => {
+                                return Err(ParseError::DuplicateAttribute {
+                                    element,
+                                    attribute: name,
+                                })
+                            }
+                            DuplicateAttributes::First => (),
+                            DuplicateAttributes::Last => {
+                                attributes.insert(name.clone(), attribute.value.clone());
+                            }
+                        }
+                        push_warning(
+                            warnings,
+                            Warning::DuplicateAttribute {
+                                element,
+                                attribute: name,
+                            },
+                        );
+                    } else {
+                        attributes
+                            .insert(attribute.qualified_name.clone(), attribute.value.clone());
+                    }
+                    typed_attributes.push(attribute);
                 }
-                final_name.push_str(&name);
-            } else {
-                let resource_id = resource_map
-                    .get(usize::try_from(attr.name).unwrap())
-                    .ok_or(ParseError::ResourceIdNotFound(attr.name))?;
-                let resource_str = get_resource_string(*resource_id)
-                    .ok_or(ParseError::UnknownResourceString(*resource_id))?;
-                final_name.push_str(&resource_str);
+                // Android only resolves attributes when they are queried, so
+                // broken attributes nobody looks at do not stop it.
+                Err(cause) if config.drop_unresolved => push_warning(
+                    warnings,
+                    Warning::DroppedAttribute {
+                        element: tag.clone(),
+                        cause,
+                    },
+                ),
+                Err(e) => return Err(e),
             }
-
-            attributes.insert(final_name, value.to_string());
         }
 
         Ok(Element {
             attributes,
-            tag: name,
+            tag,
             children: Vec::new(),
+            namespace: string_pool.get(e.attr_ext.ns).map(|n| n.to_string()),
+            name: name.to_string(),
+            line_number: e.header.line_no,
+            typed_attributes,
+            namespace_declarations: Vec::new(),
+            comments: Vec::new(),
         })
     }
+
+    fn process_attribute(
+        attr: &XmlAttribute,
+        string_pool: &ResStringPool,
+        namespaces: &HashMap<Rc<String>, Rc<String>>,
+        resource_map: &[u32],
+        config: &ParseConfig,
+    ) -> Result<Attribute, ParseError> {
+        let value = attr
+            .typed_value
+            .format(string_pool, config.float_format)?
+            .to_string();
+        let raw_value = string_pool.get(attr.raw_value).map(|s| s.to_string());
+        let resource_id = usize::try_from(attr.name)
+            .ok()
+            .and_then(|i| resource_map.get(i))
+            .copied();
+
+        // The Android runtime identifies framework attributes purely by their
+        // resource id, ignoring whatever name and namespace strings they have.
+        if config.tolerate_obfuscation {
+            if let Some(resource_str) = resource_id.and_then(get_resource_string) {
+                let ns_prefix = namespaces
+                    .get(&ANDROID_NAMESPACE.to_string())
+                    .map_or("android", |p| p.as_str());
+                return Ok(Attribute {
+                    qualified_name: format!("{}:{}", ns_prefix, resource_str),
+                    namespace: Some(ANDROID_NAMESPACE.to_string()),
+                    name: resource_str,
+                    resource_id,
+                    raw_value,
+                    typed_value: attr.typed_value.clone(),
+                    value,
+                });
+            }
+        }
+
+        // Names past the end of the resource map, which hand-built manifests
+        // often have, are plain strings. The resource map only names the
+        // attribute when its string is empty.
+        let ns = string_pool.get(attr.ns);
+        let name = string_pool
+            .get(attr.name)
+            .ok_or(ParseError::StringNotFound(attr.name))?;
+
+        let mut final_name = String::new();
+        let name = if !name.is_empty() {
+            if let Some(n) = &ns {
+                let ns_prefix = namespaces
+                    .get(n)
+                    .ok_or_else(|| ParseError::NamespaceNotFound(n.to_string()))?;
+                push_prefix(&mut final_name, ns_prefix);
+            }
+            final_name.push_str(&name);
+            name.to_string()
+        } else {
+            let resource_id = resource_id.ok_or(if resource_map.is_empty() {
+                ParseError::MissingResourceMapChunk
+            } else {
+                ParseError::ResourceIdNotFound(attr.name)
+            })?;
+            let resource_str = get_resource_string(resource_id)
+                .ok_or(ParseError::UnknownResourceString(resource_id))?;
+            final_name.push_str(&resource_str);
+            resource_str
+        };
+
+        Ok(Attribute {
+            qualified_name: final_name,
+            namespace: ns.map(|n| n.to_string()),
+            name,
+            resource_id,
+            raw_value,
+            typed_value: attr.typed_value.clone(),
+            value,
+        })
+    }
+}
+
+///Incrementally assembles the element tree from a stream of binary XML chunks.
+struct TreeBuilder<'a> {
+    string_pool: &'a ResStringPool,
+    resource_map: &'a [u32],
+    config: &'a ParseConfig,
+    namespaces: HashMap<Rc<String>, Rc<String>>,
+    declared_namespaces: Vec<(String, String)>,
+    ///Namespaces declared since the last start element, which they belong
+    ///to.
+    pending_namespaces: Vec<(String, String)>,
+    ///Comments of the namespace declarations in `pending_namespaces`.
+    pending_comments: Vec<String>,
+    element_tracker: Vec<Element>,
+    ///Namespace and name string indices of the open elements, innermost last.
+    open_tags: Vec<(u32, u32)>,
+    roots: Vec<Element>,
+    warnings: Vec<Warning>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    fn new(
+        string_pool: &'a ResStringPool,
+        resource_map: &'a [u32],
+        config: &'a ParseConfig,
+    ) -> Self {
+        Self {
+            string_pool,
+            resource_map,
+            config,
+            namespaces: HashMap::new(),
+            declared_namespaces: Vec::new(),
+            pending_namespaces: Vec::new(),
+            pending_comments: Vec::new(),
+            element_tracker: Vec::new(),
+            open_tags: Vec::new(),
+            roots: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, element: XmlElement) -> Result<(), ParseError> {
+        match element {
+            XmlElement::XmlStartNameSpace(e) => {
+                if let Some(comment) = self.comment(&e.header) {
+                    self.pending_comments.push(comment);
+                }
+                match XmlDocument::process_start_namespace(&e, self.string_pool) {
+                    // Repeated declarations are used to mask the real prefix,
+                    // so only the first binding of each URI is kept.
+                    Ok((uri, prefix))
+                        if self.config.tolerate_obfuscation
+                            && self.namespaces.contains_key(&uri) =>
+                    {
+                        let kept = &self.namespaces[&uri];
+                        if *kept != prefix {
+                            push_warning(
+                                &mut self.warnings,
+                                Warning::DuplicateNamespace {
+                                    uri: uri.to_string(),
+                                    prefix: prefix.to_string(),
+                                },
+                            );
+                        }
+                        // The element still declares the namespace, as the
+                        // earlier declaration may have gone out of scope.
+                        let declaration = (kept.to_string(), uri.to_string());
+                        if !self.pending_namespaces.contains(&declaration) {
+                            self.pending_namespaces.push(declaration);
+                        }
+                    }
+                    Ok((uri, prefix)) => {
+                        self.pending_namespaces
+                            .push((prefix.to_string(), uri.to_string()));
+                        self.namespaces.insert(uri, prefix);
+                    }
+                    Err(e) if self.config.drop_unresolved => {
+                        push_warning(&mut self.warnings, Warning::DroppedNamespace(e));
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            XmlElement::XmlEndNameSpace(_) | XmlElement::Raw(_) => {}
+            XmlElement::XmlStartElement(e) => {
+                if let Some(max) = self.config.max_depth {
+                    if self.element_tracker.len() >= max {
+                        return Err(ParseError::LimitExceeded {
+                            limit: "nesting depth",
+                            max,
+                        });
+                    }
+                }
+                self.open_tags.push((e.attr_ext.ns, e.attr_ext.name));
+                let mut element = XmlDocument::process_start_element(
+                    &e,
+                    self.string_pool,
+                    &self.namespaces,
+                    self.resource_map,
+                    self.config,
+                    &mut self.warnings,
+                )?;
+                element.comments = std::mem::take(&mut self.pending_comments);
+                element.comments.extend(self.comment(&e.header));
+                // Declarations preceding the root are kept on the document.
+                if self.element_tracker.is_empty() {
+                    self.declare_pending_namespaces();
+                } else {
+                    element.namespace_declarations = std::mem::take(&mut self.pending_namespaces);
+                }
+                self.element_tracker.push(element);
+            }
+            XmlElement::XmlEndElement(_) | XmlElement::XmlCdata(_)
+                if self.element_tracker.is_empty() && !self.roots.is_empty() =>
+            {
+                // Trailing chunks after the root used to be ignored, so they
+                // are still only worth a warning.
+                let warning = match element {
+                    XmlElement::XmlEndElement(_) => Warning::UnmatchedEndElement,
+                    _ => Warning::CdataOutsideElement,
+                };
+                push_warning(&mut self.warnings, warning);
+            }
+            XmlElement::XmlEndElement(end) => {
+                let position = self
+                    .open_tags
+                    .iter()
+                    .rposition(|&tag| tag == (end.ns, end.name));
+                if let (Some(open), false) = (
+                    self.element_tracker.last(),
+                    position.is_some() && position == self.open_tags.len().checked_sub(1),
+                ) {
+                    let expected = open.tag.clone();
+                    let expected_line = open.line_number;
+                    let found = self.end_tag(&end);
+                    let line = end.header.line_no;
+                    if self.config.enforce_tag_matching {
+                        return Err(ParseError::MismatchedEndElement {
+                            expected,
+                            expected_line,
+                            found,
+                            line,
+                        });
+                    }
+                    push_warning(
+                        &mut self.warnings,
+                        Warning::MismatchedEndElement {
+                            expected,
+                            found,
+                            line,
+                        },
+                    );
+                    // An end element for an outer element closes the ones
+                    // left open inside it. One that matches nothing closes
+                    // the innermost element, as Android ignores end names.
+                    if let Some(position) = position {
+                        while self.open_tags.len() > position + 1 {
+                            self.open_tags.pop();
+                            if let Some(e) = self.element_tracker.pop() {
+                                self.close(e);
+                            }
+                        }
+                    }
+                }
+                self.open_tags.pop();
+
+                let e = match self.element_tracker.pop() {
+                    Some(e) => e,
+                    None if !self.config.enforce_tag_matching => {
+                        push_warning(&mut self.warnings, Warning::UnmatchedEndElement);
+                        return Ok(());
+                    }
+                    None => return Err(ParseError::UnmatchedEndElement),
+                };
+
+                self.close(e);
+            }
+            XmlElement::XmlCdata(e) => {
+                let cdata = match XmlDocument::process_cdata(&e, self.string_pool, self.config) {
+                    Ok(cdata) => cdata,
+                    Err(e) if self.config.drop_unresolved => {
+                        push_warning(&mut self.warnings, Warning::DroppedCdata(e));
+                        return Ok(());
+                    }
+                    Err(e) => return Err(e),
+                };
+                match self.element_tracker.last_mut() {
+                    Some(parent) => match parent.children.last_mut() {
+                        Some(Node::Cdata(previous))
+                            if self.config.merge_cdata
+                                && previous.typed_value.is_none()
+                                && cdata.typed_value.is_none() =>
+                        {
+                            previous.data.push_str(&cdata.data)
+                        }
+                        _ => parent.insert_children(Node::Cdata(cdata)),
+                    },
+                    None if !self.config.enforce_tag_matching => {
+                        push_warning(&mut self.warnings, Warning::CdataOutsideElement)
+                    }
+                    None => return Err(ParseError::CdataOutsideElement),
+                }
+            }
+        };
+
+        Ok(())
+    }
+
+    ///Resolves the comment of a chunk. Comments have no effect on the
+    ///document, so ones that cannot be resolved are ignored.
+    fn comment(&self, header: &XmlNodeHeader) -> Option<String> {
+        self.string_pool.get(header.comment).map(|c| c.to_string())
+    }
+
+    fn declare_pending_namespaces(&mut self) {
+        for declaration in self.pending_namespaces.drain(..) {
+            if !self.declared_namespaces.contains(&declaration) {
+                self.declared_namespaces.push(declaration);
+            }
+        }
+    }
+
+    ///Returns the qualified name of an end element, for diagnostics.
+    fn end_tag(&self, end: &XmlEndElement) -> String {
+        let mut tag = String::new();
+        if let Some(prefix) = self
+            .string_pool
+            .get(end.ns)
+            .and_then(|uri| self.namespaces.get(&uri))
+        {
+            push_prefix(&mut tag, prefix);
+        }
+        match self.string_pool.get(end.name) {
+            Some(name) => tag.push_str(&name),
+            None => tag.push_str(&format!("#{}", end.name)),
+        }
+        tag
+    }
+
+    ///Adds a completed element to its parent, or to the top-level elements.
+    fn close(&mut self, e: Element) {
+        match self.element_tracker.last_mut() {
+            Some(parent) => parent.insert_children(Node::Element(e)),
+            None => {
+                if !self.roots.is_empty() {
+                    push_warning(
+                        &mut self.warnings,
+                        Warning::StrayRoot { tag: e.tag.clone() },
+                    );
+                }
+                self.roots.push(e);
+            }
+        }
+    }
+
+    ///Consumes the builder. Elements that were never closed are folded into
+    ///their parents, so that a truncated document or one missing its final
+    ///end tags still yields a root.
+    fn finish(mut self) -> (XmlDocument, Vec<Warning>) {
+        self.declare_pending_namespaces();
+        while let Some(e) = self.element_tracker.pop() {
+            push_warning(
+                &mut self.warnings,
+                Warning::UnclosedElement { tag: e.tag.clone() },
+            );
+            self.close(e);
+        }
+
+        let document = XmlDocument {
+            roots: self.roots.into_iter().map(Node::Element).collect(),
+            namespaces: self.declared_namespaces,
+            metadata: None,
+        };
+        (document, self.warnings)
+    }
+}
+
+///Visits elements depth first in document order.
+struct Elements<'a> {
+    ///Elements still to visit, the next one last.
+    stack: Vec<&'a Element>,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = &'a Element;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = self.stack.pop()?;
+        self.stack
+            .extend(e.children.iter().rev().filter_map(|c| match c {
+                Node::Element(e) => Some(e),
+                Node::Cdata(_) => None,
+            }));
+        Some(e)
+    }
 }
 
 ///Enum representing possible nodes within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
+// Most nodes are elements, and boxing them would change every match on
+// `Node::Element` in user code.
+#[allow(clippy::large_enum_variant)]
 pub enum Node {
     Element(Element),
     Cdata(Cdata),
 }
 
 ///Struct representing an element within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Element {
-    attributes: HashMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_sorted"))]
+    pub(crate) attributes: IndexMap<String, String>,
     tag: String,
     children: Vec<Node>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) namespace: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) name: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) line_number: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) typed_attributes: Vec<Attribute>,
+    ///Namespace declarations made on this element as `(prefix, uri)` pairs.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) namespace_declarations: Vec<(String, String)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) comments: Vec<String>,
 }
 
 impl Element {
-    ///Returns a map of attributes associated with the element.
-    pub fn get_attributes(&self) -> &HashMap<String, String> {
-        &self.attributes
+    ///Creates an element without attributes or children. The tag may carry
+    ///a namespace prefix such as `android:`.
+    pub fn new(tag: &str) -> Self {
+        Self {
+            attributes: IndexMap::new(),
+            tag: tag.to_string(),
+            children: Vec::new(),
+            namespace: None,
+            name: split_prefix(tag).1.to_string(),
+            line_number: 0,
+            typed_attributes: Vec::new(),
+            namespace_declarations: Vec::new(),
+            comments: Vec::new(),
+        }
+    }
+
+    ///Returns the value of the attribute `name`, including its namespace
+    ///prefix such as `android:`. Which value is returned for a name stored
+    ///more than once is set by
+    ///[ParseConfig::duplicate_attributes](crate::ParseConfig::duplicate_attributes).
+    ///
+    ///```rust
+    ///use axmldecoder::Element;
+    ///let mut e = Element::new("uses-sdk");
+    ///e.set_attribute("android:minSdkVersion", "21");
+    ///assert_eq!(e.get_attribute("android:minSdkVersion"), Some("21"));
+    ///assert_eq!(e.get_attribute("minSdkVersion"), None);
+    ///```
+    pub fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    ///Returns the attributes as `(name, value)` pairs in the order they
+    ///were stored or set. A name that was stored more than once is only
+    ///visited at its first position, with the value of
+    ///[Element::get_attribute].
+    ///
+    ///```rust
+    ///use axmldecoder::Element;
+    ///let mut e = Element::new("uses-sdk");
+    ///e.set_attribute("android:targetSdkVersion", "33");
+    ///e.set_attribute("android:minSdkVersion", "21");
+    ///let names: Vec<_> = e.attributes().map(|(name, _)| name).collect();
+    ///assert_eq!(names, ["android:targetSdkVersion", "android:minSdkVersion"]);
+    ///```
+    pub fn attributes(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    ///Returns the attributes with the type, raw string and resource id they
+    ///were stored with, in the order they were stored or set. Unlike
+    ///[Element::attributes], names stored more than once are visited each
+    ///time.
+    ///
+    ///```rust
+    ///use axmldecoder::{parse, ResourceValueType};
+    ///# let manifest_file = "examples/AndroidManifest.xml";
+    ///let mut f = std::fs::File::open(manifest_file).unwrap();
+    ///let xml = parse(&mut f).unwrap();
+    ///let application = xml.find_first(|e| e.get_tag() == "application").unwrap();
+    ///for attr in application.typed_attributes() {
+    ///    if attr.value.data_type() == ResourceValueType::Reference {
+    ///        println!("{} refers to 0x{:08x}", attr.name, attr.value.data());
+    ///    }
+    ///}
+    ///```
+    pub fn typed_attributes(&self) -> impl Iterator<Item = TypedAttribute<'_>> + '_ {
+        self.typed_attributes.iter().map(|attr| TypedAttribute {
+            namespace: attr.namespace.as_deref(),
+            qualified_name: &attr.qualified_name,
+            name: &attr.name,
+            value: &attr.typed_value,
+            raw_value: attr.raw_value.as_deref(),
+            resource_id: attr.resource_id,
+        })
     }
 
     ///Returns the element tag.
@@ -169,1368 +816,1744 @@ impl Element {
         &self.tag
     }
 
+    ///Returns the namespace URI of the element, including the default
+    ///namespace of unprefixed tags. Elements that were created rather than
+    ///parsed only have a namespace once the document is encoded.
+    pub fn get_namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
     ///Returns a list of child nodes.
     pub fn get_children(&self) -> &Vec<Node> {
         &self.children
     }
 
+    ///Returns a mutable list of child nodes.
+    pub fn get_children_mut(&mut self) -> &mut Vec<Node> {
+        &mut self.children
+    }
+
+    ///Returns every descendant of this element with the given tag, in
+    ///document order. The element itself is not included.
+    pub fn get_elements_by_tag_name(&self, tag: &str) -> Vec<&Element> {
+        let mut elements = Vec::new();
+        self.collect_elements_by_tag_name(tag, &mut elements);
+        elements
+    }
+
+    fn collect_elements_by_tag_name<'a>(&'a self, tag: &str, elements: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if let Node::Element(e) = child {
+                if e.tag == tag {
+                    elements.push(e);
+                }
+                e.collect_elements_by_tag_name(tag, elements);
+            }
+        }
+    }
+
+    ///Returns the comments attached to the element, in the order they appear
+    ///in the binary XML. These include the comments of the namespace
+    ///declarations preceding the element.
+    pub fn get_comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    ///Returns the namespace declarations made on this element as
+    ///`(prefix, uri)` pairs. Declarations made before the root element are
+    ///kept on the [XmlDocument] instead.
+    pub fn get_namespace_declarations(&self) -> &[(String, String)] {
+        &self.namespace_declarations
+    }
+
+    ///Declares the namespace `uri` with the given `prefix` on this element,
+    ///making it available to the element and its descendants.
+    pub fn declare_namespace(&mut self, prefix: &str, uri: &str) {
+        let declaration = (prefix.to_string(), uri.to_string());
+        if !self.namespace_declarations.contains(&declaration) {
+            self.namespace_declarations.push(declaration);
+        }
+    }
+
+    ///Sets the attribute `name`, which may carry a namespace prefix such as
+    ///`android:`, to `value`.
+    ///
+    ///An existing attribute keeps its binary type if `value` can be stored
+    ///as that type. Otherwise the type is inferred from `value`: `true` and
+    ///`false` are stored as booleans, decimal and `0x` prefixed numbers as
+    ///integers and everything else as a string.
+    pub fn set_attribute(&mut self, name: &str, value: &str) {
+        let existing = self
+            .typed_attributes
+            .iter()
+            .position(|a| a.qualified_name == name);
+
+        let typed_value = existing
+            .and_then(|i| {
+                ResourceValue::from_str_as(self.typed_attributes[i].typed_value.data_type, value)
+            })
+            .unwrap_or_else(|| ResourceValue::infer(value));
+        let raw_value = if typed_value.data_type == ResourceValueType::String {
+            Some(value.to_string())
+        } else {
+            None
+        };
+
+        match existing {
+            Some(i) => {
+                let attr = &mut self.typed_attributes[i];
+                attr.typed_value = typed_value;
+                attr.raw_value = raw_value;
+                attr.value = value.to_string();
+            }
+            None => self.typed_attributes.push(Attribute {
+                qualified_name: name.to_string(),
+                namespace: None,
+                name: split_prefix(name).1.to_string(),
+                resource_id: None,
+                raw_value,
+                typed_value,
+                value: value.to_string(),
+            }),
+        }
+
+        self.attributes.insert(name.to_string(), value.to_string());
+    }
+
+    ///Returns the attribute `name` in the android namespace.
+    pub(crate) fn android_attribute(&self, name: &str) -> Option<&Attribute> {
+        self.typed_attributes
+            .iter()
+            .find(|a| a.name == name && a.is_android())
+    }
+
+    ///Returns the value of the attribute `name` in the android namespace.
+    pub(crate) fn android_value(&self, name: &str) -> Option<&str> {
+        self.android_attribute(name).map(|attr| attr.value.as_str())
+    }
+
+    ///Returns the attribute `name` in the android namespace as a boolean, or
+    ///`None` if it is missing or neither `true` nor `false`.
+    pub(crate) fn android_bool(&self, name: &str) -> Option<bool> {
+        match self.android_value(name)? {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+
+    ///Sets the attribute `name` to a value of a specific binary type, for
+    ///when the type inferred by [Element::set_attribute] is not the right
+    ///one.
+    ///
+    ///`name` may carry a namespace prefix such as `android:`. The prefix is
+    ///resolved against the declarations of the document when it is encoded,
+    ///unless the namespace URI is given as `namespace`.
+    ///
+    ///```rust
+    ///use axmldecoder::{Element, ResourceValue};
+    ///let mut application = Element::new("application");
+    ///application.set_typed_attribute(None, "android:debuggable", ResourceValue::boolean(true));
+    ///application.set_typed_attribute(None, "android:icon", ResourceValue::reference(0x7f010001));
+    ///assert_eq!(application.get_attribute("android:icon"), Some("@0x7f010001"));
+    ///```
+    pub fn set_typed_attribute(
+        &mut self,
+        namespace: Option<&str>,
+        name: &str,
+        typed_value: ResourceValue,
+    ) {
+        // Only strings need the pool, and those cannot be constructed.
+        let value = typed_value
+            .get_value(&ResStringPool::new(false))
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+
+        let attribute = Attribute {
+            qualified_name: name.to_string(),
+            namespace: namespace.map(str::to_string),
+            name: split_prefix(name).1.to_string(),
+            resource_id: None,
+            raw_value: None,
+            typed_value,
+            value: value.clone(),
+        };
+        match self
+            .typed_attributes
+            .iter_mut()
+            .find(|a| a.qualified_name == name)
+        {
+            Some(existing) => *existing = attribute,
+            None => self.typed_attributes.push(attribute),
+        }
+
+        self.attributes.insert(name.to_string(), value);
+    }
+
+    ///Stores `attribute`, replacing the attribute of the same name if there
+    ///is one.
+    pub(crate) fn put_attribute(&mut self, attribute: Attribute) {
+        self.attributes
+            .insert(attribute.qualified_name.clone(), attribute.value.clone());
+        match self
+            .typed_attributes
+            .iter_mut()
+            .find(|a| a.qualified_name == attribute.qualified_name)
+        {
+            Some(existing) => *existing = attribute,
+            None => self.typed_attributes.push(attribute),
+        }
+    }
+
+    ///Removes the attributes for which `keep` returns false.
+    pub(crate) fn retain_attributes(&mut self, mut keep: impl FnMut(&Attribute) -> bool) {
+        self.typed_attributes.retain(|a| keep(a));
+        let typed_attributes = &self.typed_attributes;
+        self.attributes
+            .retain(|name, _| typed_attributes.iter().any(|a| a.qualified_name == *name));
+    }
+
+    ///Renames namespace prefixes of the tags and attribute names in this
+    ///subtree, except where a prefix is declared again further down.
+    pub(crate) fn rename_prefixes(&mut self, renames: &HashMap<String, String>) {
+        let shadowed = self
+            .namespace_declarations
+            .iter()
+            .any(|(prefix, _)| renames.contains_key(prefix));
+        let renames = if shadowed {
+            let mut renames = renames.clone();
+            for (prefix, _) in &self.namespace_declarations {
+                renames.remove(prefix);
+            }
+            std::borrow::Cow::Owned(renames)
+        } else {
+            std::borrow::Cow::Borrowed(renames)
+        };
+        let rename = |name: &str| match split_prefix(name) {
+            (Some(prefix), local) => renames
+                .get(prefix)
+                .map(|prefix| format!("{}:{}", prefix, local)),
+            (None, _) => None,
+        };
+
+        if let Some(tag) = rename(&self.tag) {
+            self.tag = tag;
+        }
+        for attr in &mut self.typed_attributes {
+            if let Some(name) = rename(&attr.qualified_name) {
+                attr.qualified_name = name;
+            }
+        }
+        self.attributes = std::mem::take(&mut self.attributes)
+            .into_iter()
+            .map(|(name, value)| (rename(&name).unwrap_or(name), value))
+            .collect();
+        for child in &mut self.children {
+            if let Node::Element(e) = child {
+                e.rename_prefixes(&renames);
+            }
+        }
+    }
+
     fn insert_children(&mut self, child: Node) {
         self.children.push(child);
     }
+
+    ///Appends an attribute built from another representation of the
+    ///document.
+    #[cfg(feature = "proto")]
+    pub(crate) fn push_attribute(&mut self, attribute: Attribute) {
+        self.attributes
+            .insert(attribute.qualified_name.clone(), attribute.value.clone());
+        self.typed_attributes.push(attribute);
+    }
+}
+
+///Serializes the first of the top-level nodes, or `null` if there are none.
+#[cfg(feature = "serde")]
+fn serialize_first<S: serde::Serializer>(roots: &[Node], serializer: S) -> Result<S::Ok, S::Error> {
+    serde::Serialize::serialize(&roots.first(), serializer)
+}
+
+///Serializes the attribute map sorted by name, so that the output does not
+///depend on the iteration order of the map.
+#[cfg(feature = "serde")]
+fn serialize_sorted<S: serde::Serializer>(
+    attributes: &IndexMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        attributes
+            .iter()
+            .collect::<std::collections::BTreeMap<_, _>>(),
+    )
+}
+
+///An attribute as it was stored in the binary document, kept alongside the
+///formatted value so that the element can be encoded again.
+#[derive(Debug, Clone)]
+pub(crate) struct Attribute {
+    ///Name of the attribute in [Element::get_attribute].
+    pub(crate) qualified_name: String,
+    pub(crate) namespace: Option<String>,
+    pub(crate) name: String,
+    pub(crate) resource_id: Option<u32>,
+    pub(crate) raw_value: Option<String>,
+    pub(crate) typed_value: ResourceValue,
+    pub(crate) value: String,
+}
+
+///An attribute as stored in the binary XML, as returned by
+///[Element::typed_attributes].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypedAttribute<'a> {
+    ///The namespace URI. Attributes that were set rather than parsed only
+    ///have the prefix in `qualified_name`.
+    pub namespace: Option<&'a str>,
+    ///The name with its namespace prefix, as passed to
+    ///[Element::get_attribute].
+    pub qualified_name: &'a str,
+    ///The name without its namespace prefix.
+    pub name: &'a str,
+    pub value: &'a ResourceValue,
+    ///The raw string stored alongside the typed value, which aapt keeps for
+    ///strings and for values it was asked to keep as written.
+    pub raw_value: Option<&'a str>,
+    ///The resource id of the attribute, for framework attributes.
+    pub resource_id: Option<u32>,
+}
+
+impl Attribute {
+    ///Returns whether the attribute is in the android namespace. Attributes
+    ///that were set rather than parsed only know their prefix.
+    pub(crate) fn is_android(&self) -> bool {
+        match &self.namespace {
+            Some(namespace) => namespace == ANDROID_NAMESPACE,
+            None => split_prefix(&self.qualified_name).0 == Some("android"),
+        }
+    }
+
+    ///Returns the value as an unsigned integer, whether it is stored as one
+    ///or as a string.
+    pub(crate) fn integer_value(&self) -> Option<u32> {
+        match self.typed_value.data_type {
+            ResourceValueType::Dec | ResourceValueType::Hex => Some(self.typed_value.data),
+            _ => self.value.parse().ok(),
+        }
+    }
 }
 
 ///Struct representing a Cdata element within the parsed XML document.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Cdata {
     data: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    typed_value: Option<ResourceValue>,
 }
 
 impl Cdata {
+    ///Creates a text node.
+    pub fn new(data: &str) -> Self {
+        Self {
+            data: data.to_string(),
+            typed_value: None,
+        }
+    }
+
     pub fn get_data(&self) -> &str {
         &self.data
     }
+
+    ///Returns the typed value the text was stored as, unless it was stored
+    ///as a plain string. [Cdata::get_data] holds the formatted value.
+    pub fn get_typed_value(&self) -> Option<&ResourceValue> {
+        self.typed_value.as_ref()
+    }
 }
 
+///The namespace URI of the framework attributes, usually bound to the
+///`android` prefix.
+pub const ANDROID_NAMESPACE: &str = "http://schemas.android.com/apk/res/android";
+
 // Logic borrowed from:
 // https://github.com/ytsutano/axmldec/blob/master/lib/jitana/util/axml_parser.cpp#L504
+const RESOURCE_STRINGS: &[&str] = &[
+    "theme",
+    "label",
+    "icon",
+    "name",
+    "manageSpaceActivity",
+    "allowClearUserData",
+    "permission",
+    "readPermission",
+    "writePermission",
+    "protectionLevel",
+    "permissionGroup",
+    "sharedUserId",
+    "hasCode",
+    "persistent",
+    "enabled",
+    "debuggable",
+    "exported",
+    "process",
+    "taskAffinity",
+    "multiprocess",
+    "finishOnTaskLaunch",
+    "clearTaskOnLaunch",
+    "stateNotNeeded",
+    "excludeFromRecents",
+    "authorities",
+    "syncable",
+    "initOrder",
+    "grantUriPermissions",
+    "priority",
+    "launchMode",
+    "screenOrientation",
+    "configChanges",
+    "description",
+    "targetPackage",
+    "handleProfiling",
+    "functionalTest",
+    "value",
+    "resource",
+    "mimeType",
+    "scheme",
+    "host",
+    "port",
+    "path",
+    "pathPrefix",
+    "pathPattern",
+    "action",
+    "data",
+    "targetClass",
+    "colorForeground",
+    "colorBackground",
+    "backgroundDimAmount",
+    "disabledAlpha",
+    "textAppearance",
+    "textAppearanceInverse",
+    "textColorPrimary",
+    "textColorPrimaryDisableOnly",
+    "textColorSecondary",
+    "textColorPrimaryInverse",
+    "textColorSecondaryInverse",
+    "textColorPrimaryNoDisable",
+    "textColorSecondaryNoDisable",
+    "textColorPrimaryInverseNoDisable",
+    "textColorSecondaryInverseNoDisable",
+    "textColorHintInverse",
+    "textAppearanceLarge",
+    "textAppearanceMedium",
+    "textAppearanceSmall",
+    "textAppearanceLargeInverse",
+    "textAppearanceMediumInverse",
+    "textAppearanceSmallInverse",
+    "textCheckMark",
+    "textCheckMarkInverse",
+    "buttonStyle",
+    "buttonStyleSmall",
+    "buttonStyleInset",
+    "buttonStyleToggle",
+    "galleryItemBackground",
+    "listPreferredItemHeight",
+    "expandableListPreferredItemPaddingLeft",
+    "expandableListPreferredChildPaddingLeft",
+    "expandableListPreferredItemIndicatorLeft",
+    "expandableListPreferredItemIndicatorRight",
+    "expandableListPreferredChildIndicatorLeft",
+    "expandableListPreferredChildIndicatorRight",
+    "windowBackground",
+    "windowFrame",
+    "windowNoTitle",
+    "windowIsFloating",
+    "windowIsTranslucent",
+    "windowContentOverlay",
+    "windowTitleSize",
+    "windowTitleStyle",
+    "windowTitleBackgroundStyle",
+    "alertDialogStyle",
+    "panelBackground",
+    "panelFullBackground",
+    "panelColorForeground",
+    "panelColorBackground",
+    "panelTextAppearance",
+    "scrollbarSize",
+    "scrollbarThumbHorizontal",
+    "scrollbarThumbVertical",
+    "scrollbarTrackHorizontal",
+    "scrollbarTrackVertical",
+    "scrollbarAlwaysDrawHorizontalTrack",
+    "scrollbarAlwaysDrawVerticalTrack",
+    "absListViewStyle",
+    "autoCompleteTextViewStyle",
+    "checkboxStyle",
+    "dropDownListViewStyle",
+    "editTextStyle",
+    "expandableListViewStyle",
+    "galleryStyle",
+    "gridViewStyle",
+    "imageButtonStyle",
+    "imageWellStyle",
+    "listViewStyle",
+    "listViewWhiteStyle",
+    "popupWindowStyle",
+    "progressBarStyle",
+    "progressBarStyleHorizontal",
+    "progressBarStyleSmall",
+    "progressBarStyleLarge",
+    "seekBarStyle",
+    "ratingBarStyle",
+    "ratingBarStyleSmall",
+    "radioButtonStyle",
+    "scrollbarStyle",
+    "scrollViewStyle",
+    "spinnerStyle",
+    "starStyle",
+    "tabWidgetStyle",
+    "textViewStyle",
+    "webViewStyle",
+    "dropDownItemStyle",
+    "spinnerDropDownItemStyle",
+    "dropDownHintAppearance",
+    "spinnerItemStyle",
+    "mapViewStyle",
+    "preferenceScreenStyle",
+    "preferenceCategoryStyle",
+    "preferenceInformationStyle",
+    "preferenceStyle",
+    "checkBoxPreferenceStyle",
+    "yesNoPreferenceStyle",
+    "dialogPreferenceStyle",
+    "editTextPreferenceStyle",
+    "ringtonePreferenceStyle",
+    "preferenceLayoutChild",
+    "textSize",
+    "typeface",
+    "textStyle",
+    "textColor",
+    "textColorHighlight",
+    "textColorHint",
+    "textColorLink",
+    "state_focused",
+    "state_window_focused",
+    "state_enabled",
+    "state_checkable",
+    "state_checked",
+    "state_selected",
+    "state_active",
+    "state_single",
+    "state_first",
+    "state_middle",
+    "state_last",
+    "state_pressed",
+    "state_expanded",
+    "state_empty",
+    "state_above_anchor",
+    "ellipsize",
+    "x",
+    "y",
+    "windowAnimationStyle",
+    "gravity",
+    "autoLink",
+    "linksClickable",
+    "entries",
+    "layout_gravity",
+    "windowEnterAnimation",
+    "windowExitAnimation",
+    "windowShowAnimation",
+    "windowHideAnimation",
+    "activityOpenEnterAnimation",
+    "activityOpenExitAnimation",
+    "activityCloseEnterAnimation",
+    "activityCloseExitAnimation",
+    "taskOpenEnterAnimation",
+    "taskOpenExitAnimation",
+    "taskCloseEnterAnimation",
+    "taskCloseExitAnimation",
+    "taskToFrontEnterAnimation",
+    "taskToFrontExitAnimation",
+    "taskToBackEnterAnimation",
+    "taskToBackExitAnimation",
+    "orientation",
+    "keycode",
+    "fullDark",
+    "topDark",
+    "centerDark",
+    "bottomDark",
+    "fullBright",
+    "topBright",
+    "centerBright",
+    "bottomBright",
+    "bottomMedium",
+    "centerMedium",
+    "id",
+    "tag",
+    "scrollX",
+    "scrollY",
+    "background",
+    "padding",
+    "paddingLeft",
+    "paddingTop",
+    "paddingRight",
+    "paddingBottom",
+    "focusable",
+    "focusableInTouchMode",
+    "visibility",
+    "fitsSystemWindows",
+    "scrollbars",
+    "fadingEdge",
+    "fadingEdgeLength",
+    "nextFocusLeft",
+    "nextFocusRight",
+    "nextFocusUp",
+    "nextFocusDown",
+    "clickable",
+    "longClickable",
+    "saveEnabled",
+    "drawingCacheQuality",
+    "duplicateParentState",
+    "clipChildren",
+    "clipToPadding",
+    "layoutAnimation",
+    "animationCache",
+    "persistentDrawingCache",
+    "alwaysDrawnWithCache",
+    "addStatesFromChildren",
+    "descendantFocusability",
+    "layout",
+    "inflatedId",
+    "layout_width",
+    "layout_height",
+    "layout_margin",
+    "layout_marginLeft",
+    "layout_marginTop",
+    "layout_marginRight",
+    "layout_marginBottom",
+    "listSelector",
+    "drawSelectorOnTop",
+    "stackFromBottom",
+    "scrollingCache",
+    "textFilterEnabled",
+    "transcriptMode",
+    "cacheColorHint",
+    "dial",
+    "hand_hour",
+    "hand_minute",
+    "format",
+    "checked",
+    "button",
+    "checkMark",
+    "foreground",
+    "measureAllChildren",
+    "groupIndicator",
+    "childIndicator",
+    "indicatorLeft",
+    "indicatorRight",
+    "childIndicatorLeft",
+    "childIndicatorRight",
+    "childDivider",
+    "animationDuration",
+    "spacing",
+    "horizontalSpacing",
+    "verticalSpacing",
+    "stretchMode",
+    "columnWidth",
+    "numColumns",
+    "src",
+    "antialias",
+    "filter",
+    "dither",
+    "scaleType",
+    "adjustViewBounds",
+    "maxWidth",
+    "maxHeight",
+    "tint",
+    "baselineAlignBottom",
+    "cropToPadding",
+    "textOn",
+    "textOff",
+    "baselineAligned",
+    "baselineAlignedChildIndex",
+    "weightSum",
+    "divider",
+    "dividerHeight",
+    "choiceMode",
+    "itemTextAppearance",
+    "horizontalDivider",
+    "verticalDivider",
+    "headerBackground",
+    "itemBackground",
+    "itemIconDisabledAlpha",
+    "rowHeight",
+    "maxRows",
+    "maxItemsPerRow",
+    "moreIcon",
+    "max",
+    "progress",
+    "secondaryProgress",
+    "indeterminate",
+    "indeterminateOnly",
+    "indeterminateDrawable",
+    "progressDrawable",
+    "indeterminateDuration",
+    "indeterminateBehavior",
+    "minWidth",
+    "minHeight",
+    "interpolator",
+    "thumb",
+    "thumbOffset",
+    "numStars",
+    "rating",
+    "stepSize",
+    "isIndicator",
+    "checkedButton",
+    "stretchColumns",
+    "shrinkColumns",
+    "collapseColumns",
+    "layout_column",
+    "layout_span",
+    "bufferType",
+    "text",
+    "hint",
+    "textScaleX",
+    "cursorVisible",
+    "maxLines",
+    "lines",
+    "height",
+    "minLines",
+    "maxEms",
+    "ems",
+    "width",
+    "minEms",
+    "scrollHorizontally",
+    "password",
+    "singleLine",
+    "selectAllOnFocus",
+    "includeFontPadding",
+    "maxLength",
+    "shadowColor",
+    "shadowDx",
+    "shadowDy",
+    "shadowRadius",
+    "numeric",
+    "digits",
+    "phoneNumber",
+    "inputMethod",
+    "capitalize",
+    "autoText",
+    "editable",
+    "freezesText",
+    "drawableTop",
+    "drawableBottom",
+    "drawableLeft",
+    "drawableRight",
+    "drawablePadding",
+    "completionHint",
+    "completionHintView",
+    "completionThreshold",
+    "dropDownSelector",
+    "popupBackground",
+    "inAnimation",
+    "outAnimation",
+    "flipInterval",
+    "fillViewport",
+    "prompt",
+    "startYear",
+    "endYear",
+    "mode",
+    "layout_x",
+    "layout_y",
+    "layout_weight",
+    "layout_toLeftOf",
+    "layout_toRightOf",
+    "layout_above",
+    "layout_below",
+    "layout_alignBaseline",
+    "layout_alignLeft",
+    "layout_alignTop",
+    "layout_alignRight",
+    "layout_alignBottom",
+    "layout_alignParentLeft",
+    "layout_alignParentTop",
+    "layout_alignParentRight",
+    "layout_alignParentBottom",
+    "layout_centerInParent",
+    "layout_centerHorizontal",
+    "layout_centerVertical",
+    "layout_alignWithParentIfMissing",
+    "layout_scale",
+    "visible",
+    "variablePadding",
+    "constantSize",
+    "oneshot",
+    "duration",
+    "drawable",
+    "shape",
+    "innerRadiusRatio",
+    "thicknessRatio",
+    "startColor",
+    "endColor",
+    "useLevel",
+    "angle",
+    "type",
+    "centerX",
+    "centerY",
+    "gradientRadius",
+    "color",
+    "dashWidth",
+    "dashGap",
+    "radius",
+    "topLeftRadius",
+    "topRightRadius",
+    "bottomLeftRadius",
+    "bottomRightRadius",
+    "left",
+    "top",
+    "right",
+    "bottom",
+    "minLevel",
+    "maxLevel",
+    "fromDegrees",
+    "toDegrees",
+    "pivotX",
+    "pivotY",
+    "insetLeft",
+    "insetRight",
+    "insetTop",
+    "insetBottom",
+    "shareInterpolator",
+    "fillBefore",
+    "fillAfter",
+    "startOffset",
+    "repeatCount",
+    "repeatMode",
+    "zAdjustment",
+    "fromXScale",
+    "toXScale",
+    "fromYScale",
+    "toYScale",
+    "fromXDelta",
+    "toXDelta",
+    "fromYDelta",
+    "toYDelta",
+    "fromAlpha",
+    "toAlpha",
+    "delay",
+    "animation",
+    "animationOrder",
+    "columnDelay",
+    "rowDelay",
+    "direction",
+    "directionPriority",
+    "factor",
+    "cycles",
+    "searchMode",
+    "searchSuggestAuthority",
+    "searchSuggestPath",
+    "searchSuggestSelection",
+    "searchSuggestIntentAction",
+    "searchSuggestIntentData",
+    "queryActionMsg",
+    "suggestActionMsg",
+    "suggestActionMsgColumn",
+    "menuCategory",
+    "orderInCategory",
+    "checkableBehavior",
+    "title",
+    "titleCondensed",
+    "alphabeticShortcut",
+    "numericShortcut",
+    "checkable",
+    "selectable",
+    "orderingFromXml",
+    "key",
+    "summary",
+    "order",
+    "widgetLayout",
+    "dependency",
+    "defaultValue",
+    "shouldDisableView",
+    "summaryOn",
+    "summaryOff",
+    "disableDependentsState",
+    "dialogTitle",
+    "dialogMessage",
+    "dialogIcon",
+    "positiveButtonText",
+    "negativeButtonText",
+    "dialogLayout",
+    "entryValues",
+    "ringtoneType",
+    "showDefault",
+    "showSilent",
+    "scaleWidth",
+    "scaleHeight",
+    "scaleGravity",
+    "ignoreGravity",
+    "foregroundGravity",
+    "tileMode",
+    "targetActivity",
+    "alwaysRetainTaskState",
+    "allowTaskReparenting",
+    "searchButtonText",
+    "colorForegroundInverse",
+    "textAppearanceButton",
+    "listSeparatorTextViewStyle",
+    "streamType",
+    "clipOrientation",
+    "centerColor",
+    "minSdkVersion",
+    "windowFullscreen",
+    "unselectedAlpha",
+    "progressBarStyleSmallTitle",
+    "ratingBarStyleIndicator",
+    "apiKey",
+    "textColorTertiary",
+    "textColorTertiaryInverse",
+    "listDivider",
+    "soundEffectsEnabled",
+    "keepScreenOn",
+    "lineSpacingExtra",
+    "lineSpacingMultiplier",
+    "listChoiceIndicatorSingle",
+    "listChoiceIndicatorMultiple",
+    "versionCode",
+    "versionName",
+    "marqueeRepeatLimit",
+    "windowNoDisplay",
+    "backgroundDimEnabled",
+    "inputType",
+    "isDefault",
+    "windowDisablePreview",
+    "privateImeOptions",
+    "editorExtras",
+    "settingsActivity",
+    "fastScrollEnabled",
+    "reqTouchScreen",
+    "reqKeyboardType",
+    "reqHardKeyboard",
+    "reqNavigation",
+    "windowSoftInputMode",
+    "imeFullscreenBackground",
+    "noHistory",
+    "headerDividersEnabled",
+    "footerDividersEnabled",
+    "candidatesTextStyleSpans",
+    "smoothScrollbar",
+    "reqFiveWayNav",
+    "keyBackground",
+    "keyTextSize",
+    "labelTextSize",
+    "keyTextColor",
+    "keyPreviewLayout",
+    "keyPreviewOffset",
+    "keyPreviewHeight",
+    "verticalCorrection",
+    "popupLayout",
+    "state_long_pressable",
+    "keyWidth",
+    "keyHeight",
+    "horizontalGap",
+    "verticalGap",
+    "rowEdgeFlags",
+    "codes",
+    "popupKeyboard",
+    "popupCharacters",
+    "keyEdgeFlags",
+    "isModifier",
+    "isSticky",
+    "isRepeatable",
+    "iconPreview",
+    "keyOutputText",
+    "keyLabel",
+    "keyIcon",
+    "keyboardMode",
+    "isScrollContainer",
+    "fillEnabled",
+    "updatePeriodMillis",
+    "initialLayout",
+    "voiceSearchMode",
+    "voiceLanguageModel",
+    "voicePromptText",
+    "voiceLanguage",
+    "voiceMaxResults",
+    "bottomOffset",
+    "topOffset",
+    "allowSingleTap",
+    "handle",
+    "content",
+    "animateOnClick",
+    "configure",
+    "hapticFeedbackEnabled",
+    "innerRadius",
+    "thickness",
+    "sharedUserLabel",
+    "dropDownWidth",
+    "dropDownAnchor",
+    "imeOptions",
+    "imeActionLabel",
+    "imeActionId",
+    "UNKNOWN",
+    "imeExtractEnterAnimation",
+    "imeExtractExitAnimation",
+    "tension",
+    "extraTension",
+    "anyDensity",
+    "searchSuggestThreshold",
+    "includeInGlobalSearch",
+    "onClick",
+    "targetSdkVersion",
+    "maxSdkVersion",
+    "testOnly",
+    "contentDescription",
+    "gestureStrokeWidth",
+    "gestureColor",
+    "uncertainGestureColor",
+    "fadeOffset",
+    "fadeDuration",
+    "gestureStrokeType",
+    "gestureStrokeLengthThreshold",
+    "gestureStrokeSquarenessThreshold",
+    "gestureStrokeAngleThreshold",
+    "eventsInterceptionEnabled",
+    "fadeEnabled",
+    "backupAgent",
+    "allowBackup",
+    "glEsVersion",
+    "queryAfterZeroResults",
+    "dropDownHeight",
+    "smallScreens",
+    "normalScreens",
+    "largeScreens",
+    "progressBarStyleInverse",
+    "progressBarStyleSmallInverse",
+    "progressBarStyleLargeInverse",
+    "searchSettingsDescription",
+    "textColorPrimaryInverseDisableOnly",
+    "autoUrlDetect",
+    "resizeable",
+    "required",
+    "accountType",
+    "contentAuthority",
+    "userVisible",
+    "windowShowWallpaper",
+    "wallpaperOpenEnterAnimation",
+    "wallpaperOpenExitAnimation",
+    "wallpaperCloseEnterAnimation",
+    "wallpaperCloseExitAnimation",
+    "wallpaperIntraOpenEnterAnimation",
+    "wallpaperIntraOpenExitAnimation",
+    "wallpaperIntraCloseEnterAnimation",
+    "wallpaperIntraCloseExitAnimation",
+    "supportsUploading",
+    "killAfterRestore",
+    "restoreNeedsApplication",
+    "smallIcon",
+    "accountPreferences",
+    "textAppearanceSearchResultSubtitle",
+    "textAppearanceSearchResultTitle",
+    "summaryColumn",
+    "detailColumn",
+    "detailSocialSummary",
+    "thumbnail",
+    "detachWallpaper",
+    "finishOnCloseSystemDialogs",
+    "scrollbarFadeDuration",
+    "scrollbarDefaultDelayBeforeFade",
+    "fadeScrollbars",
+    "colorBackgroundCacheHint",
+    "dropDownHorizontalOffset",
+    "dropDownVerticalOffset",
+    "quickContactBadgeStyleWindowSmall",
+    "quickContactBadgeStyleWindowMedium",
+    "quickContactBadgeStyleWindowLarge",
+    "quickContactBadgeStyleSmallWindowSmall",
+    "quickContactBadgeStyleSmallWindowMedium",
+    "quickContactBadgeStyleSmallWindowLarge",
+    "author",
+    "autoStart",
+    "expandableListViewWhiteStyle",
+    "installLocation",
+    "vmSafeMode",
+    "webTextViewStyle",
+    "restoreAnyVersion",
+    "tabStripLeft",
+    "tabStripRight",
+    "tabStripEnabled",
+    "logo",
+    "xlargeScreens",
+    "immersive",
+    "overScrollMode",
+    "overScrollHeader",
+    "overScrollFooter",
+    "filterTouchesWhenObscured",
+    "textSelectHandleLeft",
+    "textSelectHandleRight",
+    "textSelectHandle",
+    "textSelectHandleWindowStyle",
+    "popupAnimationStyle",
+    "screenSize",
+    "screenDensity",
+    "allContactsName",
+    "windowActionBar",
+    "actionBarStyle",
+    "navigationMode",
+    "displayOptions",
+    "subtitle",
+    "customNavigationLayout",
+    "hardwareAccelerated",
+    "measureWithLargestChild",
+    "animateFirstView",
+    "dropDownSpinnerStyle",
+    "actionDropDownStyle",
+    "actionButtonStyle",
+    "showAsAction",
+    "previewImage",
+    "actionModeBackground",
+    "actionModeCloseDrawable",
+    "windowActionModeOverlay",
+    "valueFrom",
+    "valueTo",
+    "valueType",
+    "propertyName",
+    "ordering",
+    "fragment",
+    "windowActionBarOverlay",
+    "fragmentOpenEnterAnimation",
+    "fragmentOpenExitAnimation",
+    "fragmentCloseEnterAnimation",
+    "fragmentCloseExitAnimation",
+    "fragmentFadeEnterAnimation",
+    "fragmentFadeExitAnimation",
+    "actionBarSize",
+    "imeSubtypeLocale",
+    "imeSubtypeMode",
+    "imeSubtypeExtraValue",
+    "splitMotionEvents",
+    "listChoiceBackgroundIndicator",
+    "spinnerMode",
+    "animateLayoutChanges",
+    "actionBarTabStyle",
+    "actionBarTabBarStyle",
+    "actionBarTabTextStyle",
+    "actionOverflowButtonStyle",
+    "actionModeCloseButtonStyle",
+    "titleTextStyle",
+    "subtitleTextStyle",
+    "iconifiedByDefault",
+    "actionLayout",
+    "actionViewClass",
+    "activatedBackgroundIndicator",
+    "state_activated",
+    "listPopupWindowStyle",
+    "popupMenuStyle",
+    "textAppearanceLargePopupMenu",
+    "textAppearanceSmallPopupMenu",
+    "breadCrumbTitle",
+    "breadCrumbShortTitle",
+    "listDividerAlertDialog",
+    "textColorAlertDialogListItem",
+    "loopViews",
+    "dialogTheme",
+    "alertDialogTheme",
+    "dividerVertical",
+    "homeAsUpIndicator",
+    "enterFadeDuration",
+    "exitFadeDuration",
+    "selectableItemBackground",
+    "autoAdvanceViewId",
+    "useIntrinsicSizeAsMinimum",
+    "actionModeCutDrawable",
+    "actionModeCopyDrawable",
+    "actionModePasteDrawable",
+    "textEditPasteWindowLayout",
+    "textEditNoPasteWindowLayout",
+    "textIsSelectable",
+    "windowEnableSplitTouch",
+    "indeterminateProgressStyle",
+    "progressBarPadding",
+    "animationResolution",
+    "state_accelerated",
+    "baseline",
+    "homeLayout",
+    "opacity",
+    "alpha",
+    "transformPivotX",
+    "transformPivotY",
+    "translationX",
+    "translationY",
+    "scaleX",
+    "scaleY",
+    "rotation",
+    "rotationX",
+    "rotationY",
+    "showDividers",
+    "dividerPadding",
+    "borderlessButtonStyle",
+    "dividerHorizontal",
+    "itemPadding",
+    "buttonBarStyle",
+    "buttonBarButtonStyle",
+    "segmentedButtonStyle",
+    "staticWallpaperPreview",
+    "allowParallelSyncs",
+    "isAlwaysSyncable",
+    "verticalScrollbarPosition",
+    "fastScrollAlwaysVisible",
+    "fastScrollThumbDrawable",
+    "fastScrollPreviewBackgroundLeft",
+    "fastScrollPreviewBackgroundRight",
+    "fastScrollTrackDrawable",
+    "fastScrollOverlayPosition",
+    "customTokens",
+    "nextFocusForward",
+    "firstDayOfWeek",
+    "showWeekNumber",
+    "minDate",
+    "maxDate",
+    "shownWeekCount",
+    "selectedWeekBackgroundColor",
+    "focusedMonthDateColor",
+    "unfocusedMonthDateColor",
+    "weekNumberColor",
+    "weekSeparatorLineColor",
+    "selectedDateVerticalBar",
+    "weekDayTextAppearance",
+    "dateTextAppearance",
+    "UNKNOWN",
+    "spinnersShown",
+    "calendarViewShown",
+    "state_multiline",
+    "detailsElementBackground",
+    "textColorHighlightInverse",
+    "textColorLinkInverse",
+    "editTextColor",
+    "editTextBackground",
+    "horizontalScrollViewStyle",
+    "layerType",
+    "alertDialogIcon",
+    "windowMinWidthMajor",
+    "windowMinWidthMinor",
+    "queryHint",
+    "fastScrollTextColor",
+    "largeHeap",
+    "windowCloseOnTouchOutside",
+    "datePickerStyle",
+    "calendarViewStyle",
+    "textEditSidePasteWindowLayout",
+    "textEditSideNoPasteWindowLayout",
+    "actionMenuTextAppearance",
+    "actionMenuTextColor",
+    "textCursorDrawable",
+    "resizeMode",
+    "requiresSmallestWidthDp",
+    "compatibleWidthLimitDp",
+    "largestWidthLimitDp",
+    "state_hovered",
+    "state_drag_can_accept",
+    "state_drag_hovered",
+    "stopWithTask",
+    "switchTextOn",
+    "switchTextOff",
+    "switchPreferenceStyle",
+    "switchTextAppearance",
+    "track",
+    "switchMinWidth",
+    "switchPadding",
+    "thumbTextPadding",
+    "textSuggestionsWindowStyle",
+    "textEditSuggestionItemLayout",
+    "rowCount",
+    "rowOrderPreserved",
+    "columnCount",
+    "columnOrderPreserved",
+    "useDefaultMargins",
+    "alignmentMode",
+    "layout_row",
+    "layout_rowSpan",
+    "layout_columnSpan",
+    "actionModeSelectAllDrawable",
+    "isAuxiliary",
+    "accessibilityEventTypes",
+    "packageNames",
+    "accessibilityFeedbackType",
+    "notificationTimeout",
+    "accessibilityFlags",
+    "canRetrieveWindowContent",
+    "listPreferredItemHeightLarge",
+    "listPreferredItemHeightSmall",
+    "actionBarSplitStyle",
+    "actionProviderClass",
+    "backgroundStacked",
+    "backgroundSplit",
+    "textAllCaps",
+    "colorPressedHighlight",
+    "colorLongPressedHighlight",
+    "colorFocusedHighlight",
+    "colorActivatedHighlight",
+    "colorMultiSelectHighlight",
+    "drawableStart",
+    "drawableEnd",
+    "actionModeStyle",
+    "minResizeWidth",
+    "minResizeHeight",
+    "actionBarWidgetTheme",
+    "uiOptions",
+    "subtypeLocale",
+    "subtypeExtraValue",
+    "actionBarDivider",
+    "actionBarItemBackground",
+    "actionModeSplitBackground",
+    "textAppearanceListItem",
+    "textAppearanceListItemSmall",
+    "targetDescriptions",
+    "directionDescriptions",
+    "overridesImplicitlyEnabledSubtype",
+    "listPreferredItemPaddingLeft",
+    "listPreferredItemPaddingRight",
+    "requiresFadingEdge",
+    "publicKey",
+    "parentActivityName",
+    "UNKNOWN",
+    "isolatedProcess",
+    "importantForAccessibility",
+    "keyboardLayout",
+    "fontFamily",
+    "mediaRouteButtonStyle",
+    "mediaRouteTypes",
+    "supportsRtl",
+    "textDirection",
+    "textAlignment",
+    "layoutDirection",
+    "paddingStart",
+    "paddingEnd",
+    "layout_marginStart",
+    "layout_marginEnd",
+    "layout_toStartOf",
+    "layout_toEndOf",
+    "layout_alignStart",
+    "layout_alignEnd",
+    "layout_alignParentStart",
+    "layout_alignParentEnd",
+    "listPreferredItemPaddingStart",
+    "listPreferredItemPaddingEnd",
+    "singleUser",
+    "presentationTheme",
+    "subtypeId",
+    "initialKeyguardLayout",
+    "UNKNOWN",
+    "widgetCategory",
+    "permissionGroupFlags",
+    "labelFor",
+    "permissionFlags",
+    "checkedTextViewStyle",
+    "showOnLockScreen",
+    "format12Hour",
+    "format24Hour",
+    "timeZone",
+    "mipMap",
+    "mirrorForRtl",
+    "windowOverscan",
+    "requiredForAllUsers",
+    "indicatorStart",
+    "indicatorEnd",
+    "childIndicatorStart",
+    "childIndicatorEnd",
+    "restrictedAccountType",
+    "requiredAccountType",
+    "canRequestTouchExplorationMode",
+    "canRequestEnhancedWebAccessibility",
+    "canRequestFilterKeyEvents",
+    "layoutMode",
+    "keySet",
+    "targetId",
+    "fromScene",
+    "toScene",
+    "transition",
+    "transitionOrdering",
+    "fadingMode",
+    "startDelay",
+    "ssp",
+    "sspPrefix",
+    "sspPattern",
+    "addPrintersActivity",
+    "vendor",
+    "category",
+    "isAsciiCapable",
+    "autoMirrored",
+    "supportsSwitchingToNextInputMethod",
+    "requireDeviceUnlock",
+    "apduServiceBanner",
+    "accessibilityLiveRegion",
+    "windowTranslucentStatus",
+    "windowTranslucentNavigation",
+    "advancedPrintOptionsActivity",
+    "banner",
+    "windowSwipeToDismiss",
+    "isGame",
+    "allowEmbedded",
+    "setupActivity",
+    "fastScrollStyle",
+    "windowContentTransitions",
+    "windowContentTransitionManager",
+    "translationZ",
+    "tintMode",
+    "controlX1",
+    "controlY1",
+    "controlX2",
+    "controlY2",
+    "transitionName",
+    "transitionGroup",
+    "viewportWidth",
+    "viewportHeight",
+    "fillColor",
+    "pathData",
+    "strokeColor",
+    "strokeWidth",
+    "trimPathStart",
+    "trimPathEnd",
+    "trimPathOffset",
+    "strokeLineCap",
+    "strokeLineJoin",
+    "strokeMiterLimit",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "colorControlNormal",
+    "colorControlActivated",
+    "colorButtonNormal",
+    "colorControlHighlight",
+    "persistableMode",
+    "titleTextAppearance",
+    "subtitleTextAppearance",
+    "slideEdge",
+    "actionBarTheme",
+    "textAppearanceListItemSecondary",
+    "colorPrimary",
+    "colorPrimaryDark",
+    "colorAccent",
+    "nestedScrollingEnabled",
+    "windowEnterTransition",
+    "windowExitTransition",
+    "windowSharedElementEnterTransition",
+    "windowSharedElementExitTransition",
+    "windowAllowReturnTransitionOverlap",
+    "windowAllowEnterTransitionOverlap",
+    "sessionService",
+    "stackViewStyle",
+    "switchStyle",
+    "elevation",
+    "excludeId",
+    "excludeClass",
+    "hideOnContentScroll",
+    "actionOverflowMenuStyle",
+    "documentLaunchMode",
+    "maxRecents",
+    "autoRemoveFromRecents",
+    "stateListAnimator",
+    "toId",
+    "fromId",
+    "reversible",
+    "splitTrack",
+    "targetName",
+    "excludeName",
+    "matchOrder",
+    "windowDrawsSystemBarBackgrounds",
+    "statusBarColor",
+    "navigationBarColor",
+    "contentInsetStart",
+    "contentInsetEnd",
+    "contentInsetLeft",
+    "contentInsetRight",
+    "paddingMode",
+    "layout_rowWeight",
+    "layout_columnWeight",
+    "translateX",
+    "translateY",
+    "selectableItemBackgroundBorderless",
+    "elegantTextHeight",
+    "UNKNOWN",
+    "UNKNOWN",
+    "UNKNOWN",
+    "windowTransitionBackgroundFadeDuration",
+    "overlapAnchor",
+    "progressTint",
+    "progressTintMode",
+    "progressBackgroundTint",
+    "progressBackgroundTintMode",
+    "secondaryProgressTint",
+    "secondaryProgressTintMode",
+    "indeterminateTint",
+    "indeterminateTintMode",
+    "backgroundTint",
+    "backgroundTintMode",
+    "foregroundTint",
+    "foregroundTintMode",
+    "buttonTint",
+    "buttonTintMode",
+    "thumbTint",
+    "thumbTintMode",
+    "fullBackupOnly",
+    "propertyXName",
+    "propertyYName",
+    "relinquishTaskIdentity",
+    "tileModeX",
+    "tileModeY",
+    "actionModeShareDrawable",
+    "actionModeFindDrawable",
+    "actionModeWebSearchDrawable",
+    "transitionVisibilityMode",
+    "minimumHorizontalAngle",
+    "minimumVerticalAngle",
+    "maximumAngle",
+    "searchViewStyle",
+    "closeIcon",
+    "goIcon",
+    "searchIcon",
+    "voiceIcon",
+    "commitIcon",
+    "suggestionRowLayout",
+    "queryBackground",
+    "submitBackground",
+    "buttonBarPositiveButtonStyle",
+    "buttonBarNeutralButtonStyle",
+    "buttonBarNegativeButtonStyle",
+    "popupElevation",
+    "actionBarPopupTheme",
+    "multiArch",
+    "touchscreenBlocksFocus",
+    "windowElevation",
+    "launchTaskBehindTargetAnimation",
+    "launchTaskBehindSourceAnimation",
+    "restrictionType",
+    "dayOfWeekBackground",
+    "dayOfWeekTextAppearance",
+    "headerMonthTextAppearance",
+    "headerDayOfMonthTextAppearance",
+    "headerYearTextAppearance",
+    "yearListItemTextAppearance",
+    "yearListSelectorColor",
+    "calendarTextColor",
+    "recognitionService",
+    "timePickerStyle",
+    "timePickerDialogTheme",
+    "headerTimeTextAppearance",
+    "headerAmPmTextAppearance",
+    "numbersTextColor",
+    "numbersBackgroundColor",
+    "numbersSelectorColor",
+    "amPmTextColor",
+    "amPmBackgroundColor",
+    "UNKNOWN",
+    "checkMarkTint",
+    "checkMarkTintMode",
+    "popupTheme",
+    "toolbarStyle",
+    "windowClipToOutline",
+    "datePickerDialogTheme",
+    "showText",
+    "windowReturnTransition",
+    "windowReenterTransition",
+    "windowSharedElementReturnTransition",
+    "windowSharedElementReenterTransition",
+    "resumeWhilePausing",
+    "datePickerMode",
+    "timePickerMode",
+    "inset",
+    "letterSpacing",
+    "fontFeatureSettings",
+    "outlineProvider",
+    "contentAgeHint",
+    "country",
+    "windowSharedElementsUseOverlay",
+    "reparent",
+    "reparentWithOverlay",
+    "ambientShadowAlpha",
+    "spotShadowAlpha",
+    "navigationIcon",
+    "navigationContentDescription",
+    "fragmentExitTransition",
+    "fragmentEnterTransition",
+    "fragmentSharedElementEnterTransition",
+    "fragmentReturnTransition",
+    "fragmentSharedElementReturnTransition",
+    "fragmentReenterTransition",
+    "fragmentAllowEnterTransitionOverlap",
+    "fragmentAllowReturnTransitionOverlap",
+    "patternPathData",
+    "strokeAlpha",
+    "fillAlpha",
+    "windowActivityTransitions",
+    "colorEdgeEffect",
+    "resizeClip",
+    "collapseContentDescription",
+    "accessibilityTraversalBefore",
+    "accessibilityTraversalAfter",
+    "dialogPreferredPadding",
+    "searchHintIcon",
+    "revisionCode",
+    "drawableTint",
+    "drawableTintMode",
+    "fraction",
+    "trackTint",
+    "trackTintMode",
+    "start",
+    "end",
+    "breakStrategy",
+    "hyphenationFrequency",
+    "allowUndo",
+    "windowLightStatusBar",
+    "numbersInnerTextColor",
+    "colorBackgroundFloating",
+    "titleTextColor",
+    "subtitleTextColor",
+    "thumbPosition",
+    "scrollIndicators",
+    "contextClickable",
+    "fingerprintAuthDrawable",
+    "logoDescription",
+    "extractNativeLibs",
+    "fullBackupContent",
+    "usesCleartextTraffic",
+    "lockTaskMode",
+    "autoVerify",
+    "showForAllUsers",
+    "supportsAssist",
+    "supportsLaunchVoiceAssistFromKeyguard",
+    "listMenuViewStyle",
+    "subMenuArrow",
+    "defaultWidth",
+    "defaultHeight",
+    "resizeableActivity",
+    "supportsPictureInPicture",
+    "titleMargin",
+    "titleMarginStart",
+    "titleMarginEnd",
+    "titleMarginTop",
+    "titleMarginBottom",
+    "maxButtonHeight",
+    "buttonGravity",
+    "collapseIcon",
+    "level",
+    "contextPopupMenuStyle",
+    "textAppearancePopupMenuHeader",
+    "windowBackgroundFallback",
+    "defaultToDeviceProtectedStorage",
+    "directBootAware",
+    "preferenceFragmentStyle",
+    "canControlMagnification",
+    "languageTag",
+    "pointerIcon",
+    "tickMark",
+    "tickMarkTint",
+    "tickMarkTintMode",
+    "canPerformGestures",
+    "externalService",
+    "supportsLocalInteraction",
+    "startX",
+    "startY",
+    "endX",
+    "endY",
+    "offset",
+    "use32bitAbi",
+    "bitmap",
+    "hotSpotX",
+    "hotSpotY",
+    "version",
+    "backupInForeground",
+    "countDown",
+    "canRecord",
+    "tunerCount",
+    "fillType",
+    "popupEnterTransition",
+    "popupExitTransition",
+    "forceHasOverlappingRendering",
+    "contentInsetStartWithNavigation",
+    "contentInsetEndWithActions",
+    "numberPickerStyle",
+    "enableVrMode",
+    "UNKNOWN",
+    "networkSecurityConfig",
+    "shortcutId",
+    "shortcutShortLabel",
+    "shortcutLongLabel",
+    "shortcutDisabledMessage",
+    "roundIcon",
+    "contextUri",
+    "contextDescription",
+    "showMetadataInPreview",
+    "colorSecondary",
+];
+
+///Appends `prefix` and a colon to a qualified name being built. Names in the
+///default namespace, which has an empty prefix, are left unprefixed.
+fn push_prefix(qualified_name: &mut String, prefix: &str) {
+    if !prefix.is_empty() {
+        qualified_name.push_str(prefix);
+        qualified_name.push(':');
+    }
+}
+
+///Splits a qualified name such as `android:name` into its prefix and its
+///local name.
+pub(crate) fn split_prefix(name: &str) -> (Option<&str>, &str) {
+    match name.find(':') {
+        Some(i) => (Some(&name[..i]), &name[i + 1..]),
+        None => (None, name),
+    }
+}
+
+///Returns the resource id of the framework attribute `name`, given without
+///the `android:` prefix.
+///
+///```rust
+///use axmldecoder::{framework_attribute_id, framework_attribute_name};
+///assert_eq!(framework_attribute_id("name"), Some(0x01010003));
+///assert_eq!(framework_attribute_name(0x01010003), Some("name"));
+///```
+pub fn framework_attribute_id(name: &str) -> Option<u32> {
+    get_resource_id(name)
+}
+
+///Returns the name of the framework attribute with the resource id `id`,
+///without the `android:` prefix.
+pub fn framework_attribute_name(id: u32) -> Option<&'static str> {
+    let i = id.checked_sub(0x1010000)?;
+    RESOURCE_STRINGS.get(usize::try_from(i).ok()?).copied()
+}
+
 fn get_resource_string(resource_id: u32) -> Option<String> {
-    const RESOURCE_STRINGS: &[&str] = &[
-        "theme",
-        "label",
-        "icon",
-        "name",
-        "manageSpaceActivity",
-        "allowClearUserData",
-        "permission",
-        "readPermission",
-        "writePermission",
-        "protectionLevel",
-        "permissionGroup",
-        "sharedUserId",
-        "hasCode",
-        "persistent",
-        "enabled",
-        "debuggable",
-        "exported",
-        "process",
-        "taskAffinity",
-        "multiprocess",
-        "finishOnTaskLaunch",
-        "clearTaskOnLaunch",
-        "stateNotNeeded",
-        "excludeFromRecents",
-        "authorities",
-        "syncable",
-        "initOrder",
-        "grantUriPermissions",
-        "priority",
-        "launchMode",
-        "screenOrientation",
-        "configChanges",
-        "description",
-        "targetPackage",
-        "handleProfiling",
-        "functionalTest",
-        "value",
-        "resource",
-        "mimeType",
-        "scheme",
-        "host",
-        "port",
-        "path",
-        "pathPrefix",
-        "pathPattern",
-        "action",
-        "data",
-        "targetClass",
-        "colorForeground",
-        "colorBackground",
-        "backgroundDimAmount",
-        "disabledAlpha",
-        "textAppearance",
-        "textAppearanceInverse",
-        "textColorPrimary",
-        "textColorPrimaryDisableOnly",
-        "textColorSecondary",
-        "textColorPrimaryInverse",
-        "textColorSecondaryInverse",
-        "textColorPrimaryNoDisable",
-        "textColorSecondaryNoDisable",
-        "textColorPrimaryInverseNoDisable",
-        "textColorSecondaryInverseNoDisable",
-        "textColorHintInverse",
-        "textAppearanceLarge",
-        "textAppearanceMedium",
-        "textAppearanceSmall",
-        "textAppearanceLargeInverse",
-        "textAppearanceMediumInverse",
-        "textAppearanceSmallInverse",
-        "textCheckMark",
-        "textCheckMarkInverse",
-        "buttonStyle",
-        "buttonStyleSmall",
-        "buttonStyleInset",
-        "buttonStyleToggle",
-        "galleryItemBackground",
-        "listPreferredItemHeight",
-        "expandableListPreferredItemPaddingLeft",
-        "expandableListPreferredChildPaddingLeft",
-        "expandableListPreferredItemIndicatorLeft",
-        "expandableListPreferredItemIndicatorRight",
-        "expandableListPreferredChildIndicatorLeft",
-        "expandableListPreferredChildIndicatorRight",
-        "windowBackground",
-        "windowFrame",
-        "windowNoTitle",
-        "windowIsFloating",
-        "windowIsTranslucent",
-        "windowContentOverlay",
-        "windowTitleSize",
-        "windowTitleStyle",
-        "windowTitleBackgroundStyle",
-        "alertDialogStyle",
-        "panelBackground",
-        "panelFullBackground",
-        "panelColorForeground",
-        "panelColorBackground",
-        "panelTextAppearance",
-        "scrollbarSize",
-        "scrollbarThumbHorizontal",
-        "scrollbarThumbVertical",
-        "scrollbarTrackHorizontal",
-        "scrollbarTrackVertical",
-        "scrollbarAlwaysDrawHorizontalTrack",
-        "scrollbarAlwaysDrawVerticalTrack",
-        "absListViewStyle",
-        "autoCompleteTextViewStyle",
-        "checkboxStyle",
-        "dropDownListViewStyle",
-        "editTextStyle",
-        "expandableListViewStyle",
-        "galleryStyle",
-        "gridViewStyle",
-        "imageButtonStyle",
-        "imageWellStyle",
-        "listViewStyle",
-        "listViewWhiteStyle",
-        "popupWindowStyle",
-        "progressBarStyle",
-        "progressBarStyleHorizontal",
-        "progressBarStyleSmall",
-        "progressBarStyleLarge",
-        "seekBarStyle",
-        "ratingBarStyle",
-        "ratingBarStyleSmall",
-        "radioButtonStyle",
-        "scrollbarStyle",
-        "scrollViewStyle",
-        "spinnerStyle",
-        "starStyle",
-        "tabWidgetStyle",
-        "textViewStyle",
-        "webViewStyle",
-        "dropDownItemStyle",
-        "spinnerDropDownItemStyle",
-        "dropDownHintAppearance",
-        "spinnerItemStyle",
-        "mapViewStyle",
-        "preferenceScreenStyle",
-        "preferenceCategoryStyle",
-        "preferenceInformationStyle",
-        "preferenceStyle",
-        "checkBoxPreferenceStyle",
-        "yesNoPreferenceStyle",
-        "dialogPreferenceStyle",
-        "editTextPreferenceStyle",
-        "ringtonePreferenceStyle",
-        "preferenceLayoutChild",
-        "textSize",
-        "typeface",
-        "textStyle",
-        "textColor",
-        "textColorHighlight",
-        "textColorHint",
-        "textColorLink",
-        "state_focused",
-        "state_window_focused",
-        "state_enabled",
-        "state_checkable",
-        "state_checked",
-        "state_selected",
-        "state_active",
-        "state_single",
-        "state_first",
-        "state_middle",
-        "state_last",
-        "state_pressed",
-        "state_expanded",
-        "state_empty",
-        "state_above_anchor",
-        "ellipsize",
-        "x",
-        "y",
-        "windowAnimationStyle",
-        "gravity",
-        "autoLink",
-        "linksClickable",
-        "entries",
-        "layout_gravity",
-        "windowEnterAnimation",
-        "windowExitAnimation",
-        "windowShowAnimation",
-        "windowHideAnimation",
-        "activityOpenEnterAnimation",
-        "activityOpenExitAnimation",
-        "activityCloseEnterAnimation",
-        "activityCloseExitAnimation",
-        "taskOpenEnterAnimation",
-        "taskOpenExitAnimation",
-        "taskCloseEnterAnimation",
-        "taskCloseExitAnimation",
-        "taskToFrontEnterAnimation",
-        "taskToFrontExitAnimation",
-        "taskToBackEnterAnimation",
-        "taskToBackExitAnimation",
-        "orientation",
-        "keycode",
-        "fullDark",
-        "topDark",
-        "centerDark",
-        "bottomDark",
-        "fullBright",
-        "topBright",
-        "centerBright",
-        "bottomBright",
-        "bottomMedium",
-        "centerMedium",
-        "id",
-        "tag",
-        "scrollX",
-        "scrollY",
-        "background",
-        "padding",
-        "paddingLeft",
-        "paddingTop",
-        "paddingRight",
-        "paddingBottom",
-        "focusable",
-        "focusableInTouchMode",
-        "visibility",
-        "fitsSystemWindows",
-        "scrollbars",
-        "fadingEdge",
-        "fadingEdgeLength",
-        "nextFocusLeft",
-        "nextFocusRight",
-        "nextFocusUp",
-        "nextFocusDown",
-        "clickable",
-        "longClickable",
-        "saveEnabled",
-        "drawingCacheQuality",
-        "duplicateParentState",
-        "clipChildren",
-        "clipToPadding",
-        "layoutAnimation",
-        "animationCache",
-        "persistentDrawingCache",
-        "alwaysDrawnWithCache",
-        "addStatesFromChildren",
-        "descendantFocusability",
-        "layout",
-        "inflatedId",
-        "layout_width",
-        "layout_height",
-        "layout_margin",
-        "layout_marginLeft",
-        "layout_marginTop",
-        "layout_marginRight",
-        "layout_marginBottom",
-        "listSelector",
-        "drawSelectorOnTop",
-        "stackFromBottom",
-        "scrollingCache",
-        "textFilterEnabled",
-        "transcriptMode",
-        "cacheColorHint",
-        "dial",
-        "hand_hour",
-        "hand_minute",
-        "format",
-        "checked",
-        "button",
-        "checkMark",
-        "foreground",
-        "measureAllChildren",
-        "groupIndicator",
-        "childIndicator",
-        "indicatorLeft",
-        "indicatorRight",
-        "childIndicatorLeft",
-        "childIndicatorRight",
-        "childDivider",
-        "animationDuration",
-        "spacing",
-        "horizontalSpacing",
-        "verticalSpacing",
-        "stretchMode",
-        "columnWidth",
-        "numColumns",
-        "src",
-        "antialias",
-        "filter",
-        "dither",
-        "scaleType",
-        "adjustViewBounds",
-        "maxWidth",
-        "maxHeight",
-        "tint",
-        "baselineAlignBottom",
-        "cropToPadding",
-        "textOn",
-        "textOff",
-        "baselineAligned",
-        "baselineAlignedChildIndex",
-        "weightSum",
-        "divider",
-        "dividerHeight",
-        "choiceMode",
-        "itemTextAppearance",
-        "horizontalDivider",
-        "verticalDivider",
-        "headerBackground",
-        "itemBackground",
-        "itemIconDisabledAlpha",
-        "rowHeight",
-        "maxRows",
-        "maxItemsPerRow",
-        "moreIcon",
-        "max",
-        "progress",
-        "secondaryProgress",
-        "indeterminate",
-        "indeterminateOnly",
-        "indeterminateDrawable",
-        "progressDrawable",
-        "indeterminateDuration",
-        "indeterminateBehavior",
-        "minWidth",
-        "minHeight",
-        "interpolator",
-        "thumb",
-        "thumbOffset",
-        "numStars",
-        "rating",
-        "stepSize",
-        "isIndicator",
-        "checkedButton",
-        "stretchColumns",
-        "shrinkColumns",
-        "collapseColumns",
-        "layout_column",
-        "layout_span",
-        "bufferType",
-        "text",
-        "hint",
-        "textScaleX",
-        "cursorVisible",
-        "maxLines",
-        "lines",
-        "height",
-        "minLines",
-        "maxEms",
-        "ems",
-        "width",
-        "minEms",
-        "scrollHorizontally",
-        "password",
-        "singleLine",
-        "selectAllOnFocus",
-        "includeFontPadding",
-        "maxLength",
-        "shadowColor",
-        "shadowDx",
-        "shadowDy",
-        "shadowRadius",
-        "numeric",
-        "digits",
-        "phoneNumber",
-        "inputMethod",
-        "capitalize",
-        "autoText",
-        "editable",
-        "freezesText",
-        "drawableTop",
-        "drawableBottom",
-        "drawableLeft",
-        "drawableRight",
-        "drawablePadding",
-        "completionHint",
-        "completionHintView",
-        "completionThreshold",
-        "dropDownSelector",
-        "popupBackground",
-        "inAnimation",
-        "outAnimation",
-        "flipInterval",
-        "fillViewport",
-        "prompt",
-        "startYear",
-        "endYear",
-        "mode",
-        "layout_x",
-        "layout_y",
-        "layout_weight",
-        "layout_toLeftOf",
-        "layout_toRightOf",
-        "layout_above",
-        "layout_below",
-        "layout_alignBaseline",
-        "layout_alignLeft",
-        "layout_alignTop",
-        "layout_alignRight",
-        "layout_alignBottom",
-        "layout_alignParentLeft",
-        "layout_alignParentTop",
-        "layout_alignParentRight",
-        "layout_alignParentBottom",
-        "layout_centerInParent",
-        "layout_centerHorizontal",
-        "layout_centerVertical",
-        "layout_alignWithParentIfMissing",
-        "layout_scale",
-        "visible",
-        "variablePadding",
-        "constantSize",
-        "oneshot",
-        "duration",
-        "drawable",
-        "shape",
-        "innerRadiusRatio",
-        "thicknessRatio",
-        "startColor",
-        "endColor",
-        "useLevel",
-        "angle",
-        "type",
-        "centerX",
-        "centerY",
-        "gradientRadius",
-        "color",
-        "dashWidth",
-        "dashGap",
-        "radius",
-        "topLeftRadius",
-        "topRightRadius",
-        "bottomLeftRadius",
-        "bottomRightRadius",
-        "left",
-        "top",
-        "right",
-        "bottom",
-        "minLevel",
-        "maxLevel",
-        "fromDegrees",
-        "toDegrees",
-        "pivotX",
-        "pivotY",
-        "insetLeft",
-        "insetRight",
-        "insetTop",
-        "insetBottom",
-        "shareInterpolator",
-        "fillBefore",
-        "fillAfter",
-        "startOffset",
-        "repeatCount",
-        "repeatMode",
-        "zAdjustment",
-        "fromXScale",
-        "toXScale",
-        "fromYScale",
-        "toYScale",
-        "fromXDelta",
-        "toXDelta",
-        "fromYDelta",
-        "toYDelta",
-        "fromAlpha",
-        "toAlpha",
-        "delay",
-        "animation",
-        "animationOrder",
-        "columnDelay",
-        "rowDelay",
-        "direction",
-        "directionPriority",
-        "factor",
-        "cycles",
-        "searchMode",
-        "searchSuggestAuthority",
-        "searchSuggestPath",
-        "searchSuggestSelection",
-        "searchSuggestIntentAction",
-        "searchSuggestIntentData",
-        "queryActionMsg",
-        "suggestActionMsg",
-        "suggestActionMsgColumn",
-        "menuCategory",
-        "orderInCategory",
-        "checkableBehavior",
-        "title",
-        "titleCondensed",
-        "alphabeticShortcut",
-        "numericShortcut",
-        "checkable",
-        "selectable",
-        "orderingFromXml",
-        "key",
-        "summary",
-        "order",
-        "widgetLayout",
-        "dependency",
-        "defaultValue",
-        "shouldDisableView",
-        "summaryOn",
-        "summaryOff",
-        "disableDependentsState",
-        "dialogTitle",
-        "dialogMessage",
-        "dialogIcon",
-        "positiveButtonText",
-        "negativeButtonText",
-        "dialogLayout",
-        "entryValues",
-        "ringtoneType",
-        "showDefault",
-        "showSilent",
-        "scaleWidth",
-        "scaleHeight",
-        "scaleGravity",
-        "ignoreGravity",
-        "foregroundGravity",
-        "tileMode",
-        "targetActivity",
-        "alwaysRetainTaskState",
-        "allowTaskReparenting",
-        "searchButtonText",
-        "colorForegroundInverse",
-        "textAppearanceButton",
-        "listSeparatorTextViewStyle",
-        "streamType",
-        "clipOrientation",
-        "centerColor",
-        "minSdkVersion",
-        "windowFullscreen",
-        "unselectedAlpha",
-        "progressBarStyleSmallTitle",
-        "ratingBarStyleIndicator",
-        "apiKey",
-        "textColorTertiary",
-        "textColorTertiaryInverse",
-        "listDivider",
-        "soundEffectsEnabled",
-        "keepScreenOn",
-        "lineSpacingExtra",
-        "lineSpacingMultiplier",
-        "listChoiceIndicatorSingle",
-        "listChoiceIndicatorMultiple",
-        "versionCode",
-        "versionName",
-        "marqueeRepeatLimit",
-        "windowNoDisplay",
-        "backgroundDimEnabled",
-        "inputType",
-        "isDefault",
-        "windowDisablePreview",
-        "privateImeOptions",
-        "editorExtras",
-        "settingsActivity",
-        "fastScrollEnabled",
-        "reqTouchScreen",
-        "reqKeyboardType",
-        "reqHardKeyboard",
-        "reqNavigation",
-        "windowSoftInputMode",
-        "imeFullscreenBackground",
-        "noHistory",
-        "headerDividersEnabled",
-        "footerDividersEnabled",
-        "candidatesTextStyleSpans",
-        "smoothScrollbar",
-        "reqFiveWayNav",
-        "keyBackground",
-        "keyTextSize",
-        "labelTextSize",
-        "keyTextColor",
-        "keyPreviewLayout",
-        "keyPreviewOffset",
-        "keyPreviewHeight",
-        "verticalCorrection",
-        "popupLayout",
-        "state_long_pressable",
-        "keyWidth",
-        "keyHeight",
-        "horizontalGap",
-        "verticalGap",
-        "rowEdgeFlags",
-        "codes",
-        "popupKeyboard",
-        "popupCharacters",
-        "keyEdgeFlags",
-        "isModifier",
-        "isSticky",
-        "isRepeatable",
-        "iconPreview",
-        "keyOutputText",
-        "keyLabel",
-        "keyIcon",
-        "keyboardMode",
-        "isScrollContainer",
-        "fillEnabled",
-        "updatePeriodMillis",
-        "initialLayout",
-        "voiceSearchMode",
-        "voiceLanguageModel",
-        "voicePromptText",
-        "voiceLanguage",
-        "voiceMaxResults",
-        "bottomOffset",
-        "topOffset",
-        "allowSingleTap",
-        "handle",
-        "content",
-        "animateOnClick",
-        "configure",
-        "hapticFeedbackEnabled",
-        "innerRadius",
-        "thickness",
-        "sharedUserLabel",
-        "dropDownWidth",
-        "dropDownAnchor",
-        "imeOptions",
-        "imeActionLabel",
-        "imeActionId",
-        "UNKNOWN",
-        "imeExtractEnterAnimation",
-        "imeExtractExitAnimation",
-        "tension",
-        "extraTension",
-        "anyDensity",
-        "searchSuggestThreshold",
-        "includeInGlobalSearch",
-        "onClick",
-        "targetSdkVersion",
-        "maxSdkVersion",
-        "testOnly",
-        "contentDescription",
-        "gestureStrokeWidth",
-        "gestureColor",
-        "uncertainGestureColor",
-        "fadeOffset",
-        "fadeDuration",
-        "gestureStrokeType",
-        "gestureStrokeLengthThreshold",
-        "gestureStrokeSquarenessThreshold",
-        "gestureStrokeAngleThreshold",
-        "eventsInterceptionEnabled",
-        "fadeEnabled",
-        "backupAgent",
-        "allowBackup",
-        "glEsVersion",
-        "queryAfterZeroResults",
-        "dropDownHeight",
-        "smallScreens",
-        "normalScreens",
-        "largeScreens",
-        "progressBarStyleInverse",
-        "progressBarStyleSmallInverse",
-        "progressBarStyleLargeInverse",
-        "searchSettingsDescription",
-        "textColorPrimaryInverseDisableOnly",
-        "autoUrlDetect",
-        "resizeable",
-        "required",
-        "accountType",
-        "contentAuthority",
-        "userVisible",
-        "windowShowWallpaper",
-        "wallpaperOpenEnterAnimation",
-        "wallpaperOpenExitAnimation",
-        "wallpaperCloseEnterAnimation",
-        "wallpaperCloseExitAnimation",
-        "wallpaperIntraOpenEnterAnimation",
-        "wallpaperIntraOpenExitAnimation",
-        "wallpaperIntraCloseEnterAnimation",
-        "wallpaperIntraCloseExitAnimation",
-        "supportsUploading",
-        "killAfterRestore",
-        "restoreNeedsApplication",
-        "smallIcon",
-        "accountPreferences",
-        "textAppearanceSearchResultSubtitle",
-        "textAppearanceSearchResultTitle",
-        "summaryColumn",
-        "detailColumn",
-        "detailSocialSummary",
-        "thumbnail",
-        "detachWallpaper",
-        "finishOnCloseSystemDialogs",
-        "scrollbarFadeDuration",
-        "scrollbarDefaultDelayBeforeFade",
-        "fadeScrollbars",
-        "colorBackgroundCacheHint",
-        "dropDownHorizontalOffset",
-        "dropDownVerticalOffset",
-        "quickContactBadgeStyleWindowSmall",
-        "quickContactBadgeStyleWindowMedium",
-        "quickContactBadgeStyleWindowLarge",
-        "quickContactBadgeStyleSmallWindowSmall",
-        "quickContactBadgeStyleSmallWindowMedium",
-        "quickContactBadgeStyleSmallWindowLarge",
-        "author",
-        "autoStart",
-        "expandableListViewWhiteStyle",
-        "installLocation",
-        "vmSafeMode",
-        "webTextViewStyle",
-        "restoreAnyVersion",
-        "tabStripLeft",
-        "tabStripRight",
-        "tabStripEnabled",
-        "logo",
-        "xlargeScreens",
-        "immersive",
-        "overScrollMode",
-        "overScrollHeader",
-        "overScrollFooter",
-        "filterTouchesWhenObscured",
-        "textSelectHandleLeft",
-        "textSelectHandleRight",
-        "textSelectHandle",
-        "textSelectHandleWindowStyle",
-        "popupAnimationStyle",
-        "screenSize",
-        "screenDensity",
-        "allContactsName",
-        "windowActionBar",
-        "actionBarStyle",
-        "navigationMode",
-        "displayOptions",
-        "subtitle",
-        "customNavigationLayout",
-        "hardwareAccelerated",
-        "measureWithLargestChild",
-        "animateFirstView",
-        "dropDownSpinnerStyle",
-        "actionDropDownStyle",
-        "actionButtonStyle",
-        "showAsAction",
-        "previewImage",
-        "actionModeBackground",
-        "actionModeCloseDrawable",
-        "windowActionModeOverlay",
-        "valueFrom",
-        "valueTo",
-        "valueType",
-        "propertyName",
-        "ordering",
-        "fragment",
-        "windowActionBarOverlay",
-        "fragmentOpenEnterAnimation",
-        "fragmentOpenExitAnimation",
-        "fragmentCloseEnterAnimation",
-        "fragmentCloseExitAnimation",
-        "fragmentFadeEnterAnimation",
-        "fragmentFadeExitAnimation",
-        "actionBarSize",
-        "imeSubtypeLocale",
-        "imeSubtypeMode",
-        "imeSubtypeExtraValue",
-        "splitMotionEvents",
-        "listChoiceBackgroundIndicator",
-        "spinnerMode",
-        "animateLayoutChanges",
-        "actionBarTabStyle",
-        "actionBarTabBarStyle",
-        "actionBarTabTextStyle",
-        "actionOverflowButtonStyle",
-        "actionModeCloseButtonStyle",
-        "titleTextStyle",
-        "subtitleTextStyle",
-        "iconifiedByDefault",
-        "actionLayout",
-        "actionViewClass",
-        "activatedBackgroundIndicator",
-        "state_activated",
-        "listPopupWindowStyle",
-        "popupMenuStyle",
-        "textAppearanceLargePopupMenu",
-        "textAppearanceSmallPopupMenu",
-        "breadCrumbTitle",
-        "breadCrumbShortTitle",
-        "listDividerAlertDialog",
-        "textColorAlertDialogListItem",
-        "loopViews",
-        "dialogTheme",
-        "alertDialogTheme",
-        "dividerVertical",
-        "homeAsUpIndicator",
-        "enterFadeDuration",
-        "exitFadeDuration",
-        "selectableItemBackground",
-        "autoAdvanceViewId",
-        "useIntrinsicSizeAsMinimum",
-        "actionModeCutDrawable",
-        "actionModeCopyDrawable",
-        "actionModePasteDrawable",
-        "textEditPasteWindowLayout",
-        "textEditNoPasteWindowLayout",
-        "textIsSelectable",
-        "windowEnableSplitTouch",
-        "indeterminateProgressStyle",
-        "progressBarPadding",
-        "animationResolution",
-        "state_accelerated",
-        "baseline",
-        "homeLayout",
-        "opacity",
-        "alpha",
-        "transformPivotX",
-        "transformPivotY",
-        "translationX",
-        "translationY",
-        "scaleX",
-        "scaleY",
-        "rotation",
-        "rotationX",
-        "rotationY",
-        "showDividers",
-        "dividerPadding",
-        "borderlessButtonStyle",
-        "dividerHorizontal",
-        "itemPadding",
-        "buttonBarStyle",
-        "buttonBarButtonStyle",
-        "segmentedButtonStyle",
-        "staticWallpaperPreview",
-        "allowParallelSyncs",
-        "isAlwaysSyncable",
-        "verticalScrollbarPosition",
-        "fastScrollAlwaysVisible",
-        "fastScrollThumbDrawable",
-        "fastScrollPreviewBackgroundLeft",
-        "fastScrollPreviewBackgroundRight",
-        "fastScrollTrackDrawable",
-        "fastScrollOverlayPosition",
-        "customTokens",
-        "nextFocusForward",
-        "firstDayOfWeek",
-        "showWeekNumber",
-        "minDate",
-        "maxDate",
-        "shownWeekCount",
-        "selectedWeekBackgroundColor",
-        "focusedMonthDateColor",
-        "unfocusedMonthDateColor",
-        "weekNumberColor",
-        "weekSeparatorLineColor",
-        "selectedDateVerticalBar",
-        "weekDayTextAppearance",
-        "dateTextAppearance",
-        "UNKNOWN",
-        "spinnersShown",
-        "calendarViewShown",
-        "state_multiline",
-        "detailsElementBackground",
-        "textColorHighlightInverse",
-        "textColorLinkInverse",
-        "editTextColor",
-        "editTextBackground",
-        "horizontalScrollViewStyle",
-        "layerType",
-        "alertDialogIcon",
-        "windowMinWidthMajor",
-        "windowMinWidthMinor",
-        "queryHint",
-        "fastScrollTextColor",
-        "largeHeap",
-        "windowCloseOnTouchOutside",
-        "datePickerStyle",
-        "calendarViewStyle",
-        "textEditSidePasteWindowLayout",
-        "textEditSideNoPasteWindowLayout",
-        "actionMenuTextAppearance",
-        "actionMenuTextColor",
-        "textCursorDrawable",
-        "resizeMode",
-        "requiresSmallestWidthDp",
-        "compatibleWidthLimitDp",
-        "largestWidthLimitDp",
-        "state_hovered",
-        "state_drag_can_accept",
-        "state_drag_hovered",
-        "stopWithTask",
-        "switchTextOn",
-        "switchTextOff",
-        "switchPreferenceStyle",
-        "switchTextAppearance",
-        "track",
-        "switchMinWidth",
-        "switchPadding",
-        "thumbTextPadding",
-        "textSuggestionsWindowStyle",
-        "textEditSuggestionItemLayout",
-        "rowCount",
-        "rowOrderPreserved",
-        "columnCount",
-        "columnOrderPreserved",
-        "useDefaultMargins",
-        "alignmentMode",
-        "layout_row",
-        "layout_rowSpan",
-        "layout_columnSpan",
-        "actionModeSelectAllDrawable",
-        "isAuxiliary",
-        "accessibilityEventTypes",
-        "packageNames",
-        "accessibilityFeedbackType",
-        "notificationTimeout",
-        "accessibilityFlags",
-        "canRetrieveWindowContent",
-        "listPreferredItemHeightLarge",
-        "listPreferredItemHeightSmall",
-        "actionBarSplitStyle",
-        "actionProviderClass",
-        "backgroundStacked",
-        "backgroundSplit",
-        "textAllCaps",
-        "colorPressedHighlight",
-        "colorLongPressedHighlight",
-        "colorFocusedHighlight",
-        "colorActivatedHighlight",
-        "colorMultiSelectHighlight",
-        "drawableStart",
-        "drawableEnd",
-        "actionModeStyle",
-        "minResizeWidth",
-        "minResizeHeight",
-        "actionBarWidgetTheme",
-        "uiOptions",
-        "subtypeLocale",
-        "subtypeExtraValue",
-        "actionBarDivider",
-        "actionBarItemBackground",
-        "actionModeSplitBackground",
-        "textAppearanceListItem",
-        "textAppearanceListItemSmall",
-        "targetDescriptions",
-        "directionDescriptions",
-        "overridesImplicitlyEnabledSubtype",
-        "listPreferredItemPaddingLeft",
-        "listPreferredItemPaddingRight",
-        "requiresFadingEdge",
-        "publicKey",
-        "parentActivityName",
-        "UNKNOWN",
-        "isolatedProcess",
-        "importantForAccessibility",
-        "keyboardLayout",
-        "fontFamily",
-        "mediaRouteButtonStyle",
-        "mediaRouteTypes",
-        "supportsRtl",
-        "textDirection",
-        "textAlignment",
-        "layoutDirection",
-        "paddingStart",
-        "paddingEnd",
-        "layout_marginStart",
-        "layout_marginEnd",
-        "layout_toStartOf",
-        "layout_toEndOf",
-        "layout_alignStart",
-        "layout_alignEnd",
-        "layout_alignParentStart",
-        "layout_alignParentEnd",
-        "listPreferredItemPaddingStart",
-        "listPreferredItemPaddingEnd",
-        "singleUser",
-        "presentationTheme",
-        "subtypeId",
-        "initialKeyguardLayout",
-        "UNKNOWN",
-        "widgetCategory",
-        "permissionGroupFlags",
-        "labelFor",
-        "permissionFlags",
-        "checkedTextViewStyle",
-        "showOnLockScreen",
-        "format12Hour",
-        "format24Hour",
-        "timeZone",
-        "mipMap",
-        "mirrorForRtl",
-        "windowOverscan",
-        "requiredForAllUsers",
-        "indicatorStart",
-        "indicatorEnd",
-        "childIndicatorStart",
-        "childIndicatorEnd",
-        "restrictedAccountType",
-        "requiredAccountType",
-        "canRequestTouchExplorationMode",
-        "canRequestEnhancedWebAccessibility",
-        "canRequestFilterKeyEvents",
-        "layoutMode",
-        "keySet",
-        "targetId",
-        "fromScene",
-        "toScene",
-        "transition",
-        "transitionOrdering",
-        "fadingMode",
-        "startDelay",
-        "ssp",
-        "sspPrefix",
-        "sspPattern",
-        "addPrintersActivity",
-        "vendor",
-        "category",
-        "isAsciiCapable",
-        "autoMirrored",
-        "supportsSwitchingToNextInputMethod",
-        "requireDeviceUnlock",
-        "apduServiceBanner",
-        "accessibilityLiveRegion",
-        "windowTranslucentStatus",
-        "windowTranslucentNavigation",
-        "advancedPrintOptionsActivity",
-        "banner",
-        "windowSwipeToDismiss",
-        "isGame",
-        "allowEmbedded",
-        "setupActivity",
-        "fastScrollStyle",
-        "windowContentTransitions",
-        "windowContentTransitionManager",
-        "translationZ",
-        "tintMode",
-        "controlX1",
-        "controlY1",
-        "controlX2",
-        "controlY2",
-        "transitionName",
-        "transitionGroup",
-        "viewportWidth",
-        "viewportHeight",
-        "fillColor",
-        "pathData",
-        "strokeColor",
-        "strokeWidth",
-        "trimPathStart",
-        "trimPathEnd",
-        "trimPathOffset",
-        "strokeLineCap",
-        "strokeLineJoin",
-        "strokeMiterLimit",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "colorControlNormal",
-        "colorControlActivated",
-        "colorButtonNormal",
-        "colorControlHighlight",
-        "persistableMode",
-        "titleTextAppearance",
-        "subtitleTextAppearance",
-        "slideEdge",
-        "actionBarTheme",
-        "textAppearanceListItemSecondary",
-        "colorPrimary",
-        "colorPrimaryDark",
-        "colorAccent",
-        "nestedScrollingEnabled",
-        "windowEnterTransition",
-        "windowExitTransition",
-        "windowSharedElementEnterTransition",
-        "windowSharedElementExitTransition",
-        "windowAllowReturnTransitionOverlap",
-        "windowAllowEnterTransitionOverlap",
-        "sessionService",
-        "stackViewStyle",
-        "switchStyle",
-        "elevation",
-        "excludeId",
-        "excludeClass",
-        "hideOnContentScroll",
-        "actionOverflowMenuStyle",
-        "documentLaunchMode",
-        "maxRecents",
-        "autoRemoveFromRecents",
-        "stateListAnimator",
-        "toId",
-        "fromId",
-        "reversible",
-        "splitTrack",
-        "targetName",
-        "excludeName",
-        "matchOrder",
-        "windowDrawsSystemBarBackgrounds",
-        "statusBarColor",
-        "navigationBarColor",
-        "contentInsetStart",
-        "contentInsetEnd",
-        "contentInsetLeft",
-        "contentInsetRight",
-        "paddingMode",
-        "layout_rowWeight",
-        "layout_columnWeight",
-        "translateX",
-        "translateY",
-        "selectableItemBackgroundBorderless",
-        "elegantTextHeight",
-        "UNKNOWN",
-        "UNKNOWN",
-        "UNKNOWN",
-        "windowTransitionBackgroundFadeDuration",
-        "overlapAnchor",
-        "progressTint",
-        "progressTintMode",
-        "progressBackgroundTint",
-        "progressBackgroundTintMode",
-        "secondaryProgressTint",
-        "secondaryProgressTintMode",
-        "indeterminateTint",
-        "indeterminateTintMode",
-        "backgroundTint",
-        "backgroundTintMode",
-        "foregroundTint",
-        "foregroundTintMode",
-        "buttonTint",
-        "buttonTintMode",
-        "thumbTint",
-        "thumbTintMode",
-        "fullBackupOnly",
-        "propertyXName",
-        "propertyYName",
-        "relinquishTaskIdentity",
-        "tileModeX",
-        "tileModeY",
-        "actionModeShareDrawable",
-        "actionModeFindDrawable",
-        "actionModeWebSearchDrawable",
-        "transitionVisibilityMode",
-        "minimumHorizontalAngle",
-        "minimumVerticalAngle",
-        "maximumAngle",
-        "searchViewStyle",
-        "closeIcon",
-        "goIcon",
-        "searchIcon",
-        "voiceIcon",
-        "commitIcon",
-        "suggestionRowLayout",
-        "queryBackground",
-        "submitBackground",
-        "buttonBarPositiveButtonStyle",
-        "buttonBarNeutralButtonStyle",
-        "buttonBarNegativeButtonStyle",
-        "popupElevation",
-        "actionBarPopupTheme",
-        "multiArch",
-        "touchscreenBlocksFocus",
-        "windowElevation",
-        "launchTaskBehindTargetAnimation",
-        "launchTaskBehindSourceAnimation",
-        "restrictionType",
-        "dayOfWeekBackground",
-        "dayOfWeekTextAppearance",
-        "headerMonthTextAppearance",
-        "headerDayOfMonthTextAppearance",
-        "headerYearTextAppearance",
-        "yearListItemTextAppearance",
-        "yearListSelectorColor",
-        "calendarTextColor",
-        "recognitionService",
-        "timePickerStyle",
-        "timePickerDialogTheme",
-        "headerTimeTextAppearance",
-        "headerAmPmTextAppearance",
-        "numbersTextColor",
-        "numbersBackgroundColor",
-        "numbersSelectorColor",
-        "amPmTextColor",
-        "amPmBackgroundColor",
-        "UNKNOWN",
-        "checkMarkTint",
-        "checkMarkTintMode",
-        "popupTheme",
-        "toolbarStyle",
-        "windowClipToOutline",
-        "datePickerDialogTheme",
-        "showText",
-        "windowReturnTransition",
-        "windowReenterTransition",
-        "windowSharedElementReturnTransition",
-        "windowSharedElementReenterTransition",
-        "resumeWhilePausing",
-        "datePickerMode",
-        "timePickerMode",
-        "inset",
-        "letterSpacing",
-        "fontFeatureSettings",
-        "outlineProvider",
-        "contentAgeHint",
-        "country",
-        "windowSharedElementsUseOverlay",
-        "reparent",
-        "reparentWithOverlay",
-        "ambientShadowAlpha",
-        "spotShadowAlpha",
-        "navigationIcon",
-        "navigationContentDescription",
-        "fragmentExitTransition",
-        "fragmentEnterTransition",
-        "fragmentSharedElementEnterTransition",
-        "fragmentReturnTransition",
-        "fragmentSharedElementReturnTransition",
-        "fragmentReenterTransition",
-        "fragmentAllowEnterTransitionOverlap",
-        "fragmentAllowReturnTransitionOverlap",
-        "patternPathData",
-        "strokeAlpha",
-        "fillAlpha",
-        "windowActivityTransitions",
-        "colorEdgeEffect",
-        "resizeClip",
-        "collapseContentDescription",
-        "accessibilityTraversalBefore",
-        "accessibilityTraversalAfter",
-        "dialogPreferredPadding",
-        "searchHintIcon",
-        "revisionCode",
-        "drawableTint",
-        "drawableTintMode",
-        "fraction",
-        "trackTint",
-        "trackTintMode",
-        "start",
-        "end",
-        "breakStrategy",
-        "hyphenationFrequency",
-        "allowUndo",
-        "windowLightStatusBar",
-        "numbersInnerTextColor",
-        "colorBackgroundFloating",
-        "titleTextColor",
-        "subtitleTextColor",
-        "thumbPosition",
-        "scrollIndicators",
-        "contextClickable",
-        "fingerprintAuthDrawable",
-        "logoDescription",
-        "extractNativeLibs",
-        "fullBackupContent",
-        "usesCleartextTraffic",
-        "lockTaskMode",
-        "autoVerify",
-        "showForAllUsers",
-        "supportsAssist",
-        "supportsLaunchVoiceAssistFromKeyguard",
-        "listMenuViewStyle",
-        "subMenuArrow",
-        "defaultWidth",
-        "defaultHeight",
-        "resizeableActivity",
-        "supportsPictureInPicture",
-        "titleMargin",
-        "titleMarginStart",
-        "titleMarginEnd",
-        "titleMarginTop",
-        "titleMarginBottom",
-        "maxButtonHeight",
-        "buttonGravity",
-        "collapseIcon",
-        "level",
-        "contextPopupMenuStyle",
-        "textAppearancePopupMenuHeader",
-        "windowBackgroundFallback",
-        "defaultToDeviceProtectedStorage",
-        "directBootAware",
-        "preferenceFragmentStyle",
-        "canControlMagnification",
-        "languageTag",
-        "pointerIcon",
-        "tickMark",
-        "tickMarkTint",
-        "tickMarkTintMode",
-        "canPerformGestures",
-        "externalService",
-        "supportsLocalInteraction",
-        "startX",
-        "startY",
-        "endX",
-        "endY",
-        "offset",
-        "use32bitAbi",
-        "bitmap",
-        "hotSpotX",
-        "hotSpotY",
-        "version",
-        "backupInForeground",
-        "countDown",
-        "canRecord",
-        "tunerCount",
-        "fillType",
-        "popupEnterTransition",
-        "popupExitTransition",
-        "forceHasOverlappingRendering",
-        "contentInsetStartWithNavigation",
-        "contentInsetEndWithActions",
-        "numberPickerStyle",
-        "enableVrMode",
-        "UNKNOWN",
-        "networkSecurityConfig",
-        "shortcutId",
-        "shortcutShortLabel",
-        "shortcutLongLabel",
-        "shortcutDisabledMessage",
-        "roundIcon",
-        "contextUri",
-        "contextDescription",
-        "showMetadataInPreview",
-        "colorSecondary",
-    ];
-
-    let i = resource_id - 0x1010000;
-
-    Some(
-        RESOURCE_STRINGS
-            .get(usize::try_from(i).unwrap())?
-            .to_string(),
-    )
+    framework_attribute_name(resource_id).map(str::to_string)
+}
+
+///The inverse of [get_resource_string].
+pub(crate) fn get_resource_id(name: &str) -> Option<u32> {
+    let i = RESOURCE_STRINGS.iter().position(|s| *s == name)?;
+
+    Some(0x1010000 + u32::try_from(i).ok()?)
 }